@@ -1,25 +1,32 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use crate::pages;
-use crate::slint_generated::MainWindow;
+use crate::widgets;
+use crate::slint_generated::{MainWindow, QuickSearchWindow, AppLogic};
+use crate::pages::features::FileSearcher;
+use crate::utils::listen_for_commands;
 use crate::updater::Updater;
 use std::error::Error;
+use std::sync::Arc;
 use slint::ComponentHandle;
 use windows::Win32::UI::Shell::ShellExecuteW;
 use windows::Win32::UI::WindowsAndMessaging::SW_SHOW;
 use windows::core::HSTRING;
 
-pub fn run() -> Result<(), Box<dyn Error>> {
+// `quick` : n'ouvre que la fenêtre de recherche rapide, la fenêtre principale
+// reste cachée jusqu'à ce qu'on la demande
+pub fn run(quick: bool) -> Result<(), Box<dyn Error>> {
     if cfg!(windows) && !is_elevated::is_elevated() {
         println!("=== Redémarrage en mode administrateur ===");
         let current_exe = std::env::current_exe()?;
+        let args: Vec<String> = std::env::args().skip(1).collect();
         
         unsafe {
             let _ = ShellExecuteW(
                 None,
                 &HSTRING::from("runas"),
                 &HSTRING::from(current_exe.to_str().unwrap()),
-                None,
+                &HSTRING::from(args.join(" ")),
                 None,
                 SW_SHOW,
             );
@@ -33,11 +40,48 @@ pub fn run() -> Result<(), Box<dyn Error>> {
         window.set_current_version(env!("CARGO_PKG_VERSION").into());
     }
 
+    widgets::init_notifications(&ui.as_weak());
     pages::settings::init(&ui.as_weak());
-    pages::everysup::init(&ui.as_weak());
+    let searcher = Arc::new(FileSearcher::new());
+    pages::everysup::init(&ui.as_weak(), searcher.clone());
     pages::about::init(&ui.as_weak(), updater.into());
     pages::cleartool::init(&ui.as_weak());
+    pages::snapshots::init(&ui.as_weak());
+    pages::maintenance::init(&ui.as_weak(), searcher.clone());
+    pages::audit::init(&ui.as_weak(), searcher.clone());
+    pages::timeline::init(&ui.as_weak(), searcher.clone());
+
+    let quick_search = QuickSearchWindow::new()?;
+    pages::quick_search::init(&quick_search.as_weak(), searcher.clone(), {
+        let ui_weak = ui.as_weak();
+        move |query| {
+            if let Some(ui) = ui_weak.upgrade() {
+                ui.global::<AppLogic>().invoke_launch_search(query.into());
+            }
+        }
+    });
 
-    ui.run()?;
+    // Commandes des instances lancées ensuite (`suptool --quick`)
+    let quick_search_weak = quick_search.as_weak();
+    if let Err(e) = listen_for_commands(move |command| {
+        let quick_search_weak = quick_search_weak.clone();
+        slint::invoke_from_event_loop(move || {
+            match (command.as_str(), quick_search_weak.upgrade()) {
+                ("quick", Some(quick_search)) => pages::quick_search::show(&quick_search),
+                _ => eprintln!("Commande inconnue: {}", command),
+            }
+        }).unwrap();
+    }) {
+        eprintln!("Impossible d'écouter les autres instances: {}", e);
+    }
+
+    if quick {
+        // L'application reste lancée en arrière-plan pour les prochains `--quick`
+        pages::quick_search::load_saved_index(&quick_search.as_weak(), searcher);
+        pages::quick_search::show(&quick_search);
+        slint::run_event_loop_until_quit()?;
+    } else {
+        ui.run()?;
+    }
     Ok(())
 }
\ No newline at end of file
@@ -1 +1,262 @@
-// TODO: Implement the ClearTool functions
\ No newline at end of file
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Arc;
+use std::path::PathBuf;
+
+#[derive(Clone)]
+pub struct CleaningGroup {
+    pub name: String,
+    pub enabled: bool,
+    pub paths: Vec<PathBuf>,
+    pub size: u64,
+}
+
+#[derive(Clone)]
+pub struct DetectedAppInternal {
+    pub name: String,
+    pub paths: Vec<PathBuf>,
+    pub size: u64,
+    pub cleanable: bool,
+}
+
+// Groupe ou application qui serait nettoyé, pour un aperçu sans suppression
+pub struct CleanTarget {
+    pub name: String,
+    pub paths: Vec<PathBuf>,
+    pub size: u64,
+}
+
+// Résultat d'un nettoyage : seuls les fichiers réellement supprimés comptent
+#[derive(Default)]
+pub struct CleanReport {
+    pub freed: u64,
+    // Fichiers et dossiers qui n'ont pas pu être supprimés (en cours d'utilisation...)
+    pub failed: Vec<PathBuf>,
+}
+
+#[derive(Clone)]
+pub struct ClearTool {
+    pub groups: Arc<Mutex<HashMap<String, CleaningGroup>>>,
+    pub detected_apps: Arc<Mutex<HashMap<String, DetectedAppInternal>>>,
+}
+
+impl ClearTool {
+    pub fn new() -> Self {
+        let mut groups = HashMap::new();
+        
+        // Groupe Windows Temp par défaut
+        groups.insert("Windows Temp".to_string(), CleaningGroup {
+            name: "Windows Temp".to_string(),
+            enabled: true,
+            paths: vec![
+                PathBuf::from(std::env::var("windir").unwrap_or("C:\\Windows".to_string())).join("Temp"),
+                PathBuf::from(std::env::var("TEMP").unwrap_or_else(|_| {
+                    PathBuf::from(std::env::var("USERPROFILE").unwrap_or("C:\\Users\\Default".to_string()))
+                        .join("AppData\\Local\\Temp")
+                        .to_string_lossy()
+                        .to_string()
+                })),
+            ],
+            size: 0,
+        });
+
+        Self {
+            groups: Arc::new(Mutex::new(groups)),
+            detected_apps: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    fn calculate_dir_size(&self, path: &PathBuf) -> u64 {
+        let mut total_size = 0;
+
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let path = entry.path();
+                if let Ok(metadata) = fs::metadata(&path) {
+                    if metadata.is_file() {
+                        total_size += metadata.len();
+                    } else if metadata.is_dir() {
+                        total_size += self.calculate_dir_size(&path);
+                    }
+                }
+            }
+        }
+
+        total_size
+    }
+
+    // Vide `path` sans le supprimer
+    fn clean_directory(&self, path: &PathBuf, report: &mut CleanReport) {
+        let Ok(entries) = fs::read_dir(path) else {
+            report.failed.push(path.clone());
+            return;
+        };
+
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if let Ok(metadata) = fs::metadata(&path) {
+                if metadata.is_file() {
+                    match fs::remove_file(&path) {
+                        Ok(()) => report.freed += metadata.len(),
+                        Err(_) => report.failed.push(path),
+                    }
+                } else if metadata.is_dir() {
+                    let failed_before = report.failed.len();
+                    self.clean_directory(&path, report);
+                    // Un dossier dont un fichier est resté ne peut pas être supprimé : déjà compté
+                    if fs::remove_dir(&path).is_err() && report.failed.len() == failed_before {
+                        report.failed.push(path);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn detect_apps(&self) {
+        let mut apps = HashMap::new();
+        
+        if let Some(local_appdata) = std::env::var_os("LOCALAPPDATA") {
+            let fivem_base = PathBuf::from(local_appdata).join("FiveM\\FiveM.app");
+            let mut total_size = 0;
+            let mut fivem_paths = Vec::new();
+            
+            let cache_paths = vec![
+                (fivem_base.join("data\\cache"), "Cache"),
+                (fivem_base.join("data\\server-cache"), "Server Cache"),
+                (fivem_base.join("data\\server-cache-priv"), "Server Cache Priv"),
+                (fivem_base.join("logs"), "Logs"),
+                (fivem_base.join("crashes"), "Crashes"),
+            ];
+
+            for (path, _) in &cache_paths {
+                if path.exists() {
+                    let size = self.calculate_dir_size(path);
+                    total_size += size;
+                    fivem_paths.push(path.clone());
+                }
+            }
+
+            if !fivem_paths.is_empty() {
+                apps.insert("FiveM".to_string(), DetectedAppInternal {
+                    name: "FiveM".to_string(),
+                    paths: fivem_paths,
+                    size: total_size,
+                    cleanable: true,
+                });
+            }
+        }
+
+        *self.detected_apps.lock() = apps;
+    }
+
+    pub fn refresh_sizes(&self) {
+        let mut groups = self.groups.lock();
+        for group in groups.values_mut() {
+            let mut total_size = 0;
+            for path in &group.paths {
+                if path.exists() {
+                    total_size += self.calculate_dir_size(path);
+                }
+            }
+            group.size = total_size;
+        }
+    }
+
+    pub fn clean_selected(&self) -> CleanReport {
+        let mut report = CleanReport::default();
+        
+        // Nettoyage des groupes
+        let groups = self.groups.lock();
+        for group in groups.values() {
+            if !group.enabled {
+                continue;
+            }
+
+            for path in &group.paths {
+                if !path.exists() {
+                    continue;
+                }
+                self.clean_directory(path, &mut report);
+            }
+        }
+        drop(groups);
+
+        let mut apps = self.detected_apps.lock();
+        for app in apps.values_mut() {
+            if app.cleanable {
+                for path in &app.paths {
+                    if path.exists() {
+                        self.clean_directory(path, &mut report);
+                    }
+                }
+                
+                let mut new_size = 0;
+                for path in &app.paths {
+                    if path.exists() {
+                        new_size += self.calculate_dir_size(path);
+                    }
+                }
+                app.size = new_size;
+            }
+        }
+
+        report
+    }
+
+    // Groupes activés et applications à nettoyer, avec les tailles du dernier
+    // `refresh_sizes` / `detect_apps`
+    pub fn selected_targets(&self) -> Vec<CleanTarget> {
+        let groups = self.groups.lock();
+        let apps = self.detected_apps.lock();
+
+        groups.values()
+            .filter(|group| group.enabled)
+            .map(|group| CleanTarget {
+                name: group.name.clone(),
+                paths: group.paths.iter().filter(|path| path.exists()).cloned().collect(),
+                size: group.size,
+            })
+            .chain(apps.values()
+                .filter(|app| app.cleanable)
+                .map(|app| CleanTarget {
+                    name: app.name.clone(),
+                    paths: app.paths.clone(),
+                    size: app.size,
+                }))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clean_directory_vide_le_dossier_et_compte_les_octets_supprimes() {
+        let root = std::env::temp_dir().join(format!("suptool-cleartool-{}", std::process::id()));
+        fs::create_dir_all(root.join("cache").join("été")).unwrap();
+        fs::write(root.join("journal.log"), [0u8; 10]).unwrap();
+        fs::write(root.join("cache").join("été").join("données.bin"), [0u8; 32]).unwrap();
+
+        let mut report = CleanReport::default();
+        ClearTool::new().clean_directory(&root, &mut report);
+
+        assert_eq!(report.freed, 42);
+        assert!(report.failed.is_empty());
+        // Le dossier nettoyé lui-même est conservé
+        assert!(root.exists());
+        assert_eq!(fs::read_dir(&root).unwrap().count(), 0);
+        let _ = fs::remove_dir(&root);
+    }
+
+    #[test]
+    fn clean_directory_signale_un_dossier_illisible() {
+        let missing = std::env::temp_dir().join(format!("suptool-cleartool-absent-{}", std::process::id()));
+        let mut report = CleanReport::default();
+        ClearTool::new().clean_directory(&missing, &mut report);
+
+        assert_eq!(report.freed, 0);
+        assert_eq!(report.failed, vec![missing]);
+    }
+}
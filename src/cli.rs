@@ -0,0 +1,447 @@
+// Mode ligne de commande : `suptool <commande> [options]`, sans ouvrir de
+// fenêtre. Même logique que l'interface (FileSearcher, ClearTool, Updater),
+// sortie lisible ou JSON (`--json`) et codes de sortie exploitables en script.
+
+use crate::cleartool::ClearTool;
+use crate::pages::features::{FileSearcher, NamedProfile};
+use crate::updater::Updater;
+use crate::utils::format_size;
+use serde_json::json;
+use std::io::Write;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+pub const COMMANDS: &[&str] = &["search", "index", "clean", "update", "help", "--help", "-h"];
+
+// Codes de sortie
+const EXIT_OK: i32 = 0;
+// Résultat négatif : aucun fichier trouvé, mise à jour disponible
+const EXIT_NEGATIVE: i32 = 1;
+const EXIT_USAGE: i32 = 2;
+const EXIT_ERROR: i32 = 3;
+
+const DEFAULT_LIMIT: usize = 50;
+
+const USAGE: &str = "\
+Usage : suptool <commande> [options]
+
+Commandes :
+  search <requête...> Cherche dans l'index sauvegardé (lancez d'abord `suptool index` ou indexez depuis EverySup)
+      --profile NOM   Cherche dans un seul profil d'index (par défaut : tous les profils)
+      --limit N       Nombre maximal de résultats (par défaut : 50)
+  index               Indexe et sauvegarde chaque profil
+      --profile NOM   Indexe un seul profil
+  clean               Nettoie les groupes ClearTool activés et les applications détectées
+      --dry-run       Indique seulement ce qui serait supprimé
+  update check        Cherche une version plus récente sur GitHub
+
+Options communes :
+  --json              Sortie lisible par une machine sur stdout
+
+Codes de sortie : 0 succès, 1 rien trouvé / mise à jour disponible, 2 erreur d'usage, 3 échec";
+
+struct Options {
+    json: bool,
+    dry_run: bool,
+    profile: Option<String>,
+    limit: usize,
+    // Arguments qui ne sont pas des options
+    words: Vec<String>,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = Self {
+            json: false,
+            dry_run: false,
+            profile: None,
+            limit: DEFAULT_LIMIT,
+            words: Vec::new(),
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--json" => options.json = true,
+                "--dry-run" => options.dry_run = true,
+                "--profile" => options.profile = Some(
+                    args.next().ok_or("--profile attend un nom de profil")?.clone()
+                ),
+                "--limit" => options.limit = args.next()
+                    .and_then(|limit| limit.parse().ok())
+                    .filter(|&limit| limit > 0)
+                    .ok_or("--limit attend un nombre positif")?,
+                "--" => options.words.extend(args.by_ref().cloned()),
+                option if option.starts_with("--") => return Err(format!("option inconnue : {}", option)),
+                word => options.words.push(word.to_string()),
+            }
+        }
+        Ok(options)
+    }
+}
+
+// Exécute `command` et renvoie le code de sortie
+pub fn run(command: &str, args: &[String]) -> i32 {
+    #[cfg(windows)]
+    unsafe {
+        // L'exécutable est une application fenêtrée : on écrit dans la console qui l'a lancé
+        use windows::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+
+    let options = match Options::parse(args) {
+        Ok(options) => options,
+        Err(e) => return usage_error(&e),
+    };
+
+    match command {
+        "search" => search(&options),
+        "index" => index(&options),
+        "clean" => clean(&options),
+        "update" => match options.words.first().map(String::as_str) {
+            Some("check") => update_check(&options),
+            _ => usage_error("attendu : `suptool update check`"),
+        },
+        _ => {
+            out(USAGE);
+            EXIT_OK
+        }
+    }
+}
+
+fn search(options: &Options) -> i32 {
+    let query = options.words.join(" ");
+    if query.trim().is_empty() {
+        return usage_error("search attend une requête");
+    }
+
+    let searcher = FileSearcher::new();
+    let profiles = match selected_profiles(&searcher, options) {
+        Ok(profiles) => profiles,
+        Err(e) => return failure(options, &e),
+    };
+
+    let loaded = logs_to_stderr(|| {
+        profiles.iter().filter(|profile| searcher.load_saved_index(&profile.name)).count()
+    });
+    if loaded == 0 {
+        return failure(options, "aucun index sauvegardé, lancez d'abord `suptool index`");
+    }
+
+    let results = searcher.search_in(options.profile.as_deref(), &query, options.limit);
+    let code = if results.is_empty() { EXIT_NEGATIVE } else { EXIT_OK };
+
+    if options.json {
+        let results: Vec<serde_json::Value> = results.iter()
+            .map(|hit| json!({
+                "name": hit.result.name,
+                "path": hit.result.path,
+                "size": hit.result.size,
+                "is_dir": hit.result.is_dir,
+                "modified": unix_seconds(hit.result.modified),
+                "volume": hit.result.volume,
+                "offline": hit.result.offline,
+                "archive": hit.result.archive,
+            }))
+            .collect();
+        print_json(json!({ "query": query, "count": results.len(), "results": results }));
+    } else {
+        for hit in &results {
+            out(&hit.result.path);
+        }
+    }
+    code
+}
+
+fn index(options: &Options) -> i32 {
+    let searcher = FileSearcher::new();
+    let profiles = match selected_profiles(&searcher, options) {
+        Ok(profiles) => profiles,
+        Err(e) => return failure(options, &e),
+    };
+
+    let mut reports = Vec::new();
+    let mut failed = false;
+
+    for profile in &profiles {
+        let start = Instant::now();
+        let saved = logs_to_stderr(|| {
+            searcher.build_index(&profile.name, || false);
+            searcher.save_index(&profile.name)
+        });
+        let seconds = start.elapsed().as_secs_f64();
+
+        match saved {
+            Ok(files) => {
+                if !options.json {
+                    out(format!("{} : {} fichiers indexés en {:.1} s", profile.name, files, seconds));
+                }
+                reports.push(json!({ "profile": profile.name, "files": files, "seconds": seconds }));
+            }
+            Err(e) => {
+                failed = true;
+                eprintln!("{} : index non sauvegardé : {}", profile.name, e);
+                reports.push(json!({ "profile": profile.name, "error": e.to_string() }));
+            }
+        }
+    }
+
+    let errors = searcher.index_errors();
+    if options.json {
+        print_json(json!({ "profiles": reports, "index_errors": errors.len() }));
+    } else if !errors.is_empty() {
+        out(format!("{} chemins n'ont pas pu être indexés", errors.len()));
+    }
+
+    if failed { EXIT_ERROR } else { EXIT_OK }
+}
+
+fn clean(options: &Options) -> i32 {
+    let cleartool = ClearTool::new();
+    logs_to_stderr(|| {
+        cleartool.detect_apps();
+        cleartool.refresh_sizes();
+    });
+    let targets = cleartool.selected_targets();
+    let planned: u64 = targets.iter().map(|target| target.size).sum();
+
+    let report = if options.dry_run {
+        None
+    } else {
+        Some(logs_to_stderr(|| cleartool.clean_selected()))
+    };
+    let freed = report.as_ref().map(|report| report.freed);
+    let failed: Vec<String> = report.as_ref()
+        .map(|report| report.failed.iter().map(|path| path.display().to_string()).collect())
+        .unwrap_or_default();
+
+    if options.json {
+        let targets: Vec<serde_json::Value> = targets.iter()
+            .map(|target| json!({
+                "name": target.name,
+                "paths": target.paths,
+                "size": target.size,
+            }))
+            .collect();
+        print_json(json!({
+            "dry_run": options.dry_run,
+            "targets": targets,
+            "size": planned,
+            "freed": freed,
+            "failed": failed,
+        }));
+    } else {
+        for target in &targets {
+            out(format!("{} ({})", target.name, format_size(target.size)));
+            for path in &target.paths {
+                out(format!("  {}", path.display()));
+            }
+        }
+        match freed {
+            Some(freed) => out(format!("{} libérés", format_size(freed))),
+            None => out(format!("{} seraient libérés", format_size(planned))),
+        }
+    }
+
+    // Fichiers en cours d'utilisation, droits insuffisants...
+    if failed.is_empty() {
+        return EXIT_OK;
+    }
+    eprintln!("suptool : {} éléments n'ont pas pu être supprimés", failed.len());
+    for path in &failed {
+        eprintln!("  {}", path);
+    }
+    EXIT_ERROR
+}
+
+fn update_check(options: &Options) -> i32 {
+    let updater = Updater::headless();
+
+    match updater.check_for_updates() {
+        Ok(available) => {
+            let latest = updater.available_version();
+            if options.json {
+                print_json(json!({
+                    "current": updater.current_version(),
+                    "latest": latest,
+                    "update_available": available,
+                }));
+            } else if let Some(latest) = &latest {
+                out(format!("Mise à jour disponible : {} (version actuelle {})", latest, updater.current_version()));
+            } else {
+                out(format!("À jour ({})", updater.current_version()));
+            }
+            if available { EXIT_NEGATIVE } else { EXIT_OK }
+        }
+        Err(e) => failure(options, &e.to_string()),
+    }
+}
+
+fn selected_profiles(searcher: &FileSearcher, options: &Options) -> Result<Vec<NamedProfile>, String> {
+    let profiles = searcher.profiles();
+    match &options.profile {
+        Some(name) => profiles.into_iter()
+            .find(|profile| &profile.name == name)
+            .map(|profile| vec![profile])
+            .ok_or_else(|| format!("profil inconnu : {}", name)),
+        None => Ok(profiles),
+    }
+}
+
+// Les journaux de l'indexation et du nettoyage (println!) partent sur stderr
+// pour que stdout ne contienne que le résultat (chemins ou JSON)
+fn logs_to_stderr<T>(operation: impl FnOnce() -> T) -> T {
+    let _ = std::io::stdout().flush();
+    match StdoutRedirect::to_stderr() {
+        Some(_redirect) => operation(),
+        None => {
+            eprintln!("Impossible de rediriger stdout, les journaux s'y mêleront au résultat");
+            operation()
+        }
+    }
+}
+
+// Remet stdout en place à la fin de l'opération, même si elle panique
+#[cfg(unix)]
+struct StdoutRedirect {
+    saved: libc::c_int,
+}
+
+#[cfg(unix)]
+impl StdoutRedirect {
+    fn to_stderr() -> Option<Self> {
+        unsafe {
+            let saved = libc::dup(1);
+            if saved < 0 {
+                return None;
+            }
+            if libc::dup2(2, 1) < 0 {
+                libc::close(saved);
+                return None;
+            }
+            Some(Self { saved })
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for StdoutRedirect {
+    fn drop(&mut self) {
+        let _ = std::io::stdout().flush();
+        unsafe {
+            if libc::dup2(self.saved, 1) < 0 {
+                eprintln!("Impossible de rétablir stdout");
+            }
+            libc::close(self.saved);
+        }
+    }
+}
+
+#[cfg(windows)]
+struct StdoutRedirect {
+    saved: windows::Win32::Foundation::HANDLE,
+}
+
+#[cfg(windows)]
+impl StdoutRedirect {
+    fn to_stderr() -> Option<Self> {
+        use windows::Win32::System::Console::{GetStdHandle, SetStdHandle, STD_ERROR_HANDLE, STD_OUTPUT_HANDLE};
+        unsafe {
+            let saved = GetStdHandle(STD_OUTPUT_HANDLE).ok()?;
+            let stderr = GetStdHandle(STD_ERROR_HANDLE).ok()?;
+            SetStdHandle(STD_OUTPUT_HANDLE, stderr).ok()?;
+            Some(Self { saved })
+        }
+    }
+}
+
+#[cfg(windows)]
+impl Drop for StdoutRedirect {
+    fn drop(&mut self) {
+        use windows::Win32::System::Console::{SetStdHandle, STD_OUTPUT_HANDLE};
+        let _ = std::io::stdout().flush();
+        unsafe {
+            let _ = SetStdHandle(STD_OUTPUT_HANDLE, self.saved);
+        }
+    }
+}
+
+fn print_json(value: serde_json::Value) {
+    out(serde_json::to_string_pretty(&value).unwrap_or_default());
+}
+
+// Comme println!, sans paniquer si stdout est fermé (`suptool search x | head`)
+fn out(text: impl std::fmt::Display) {
+    let _ = writeln!(std::io::stdout(), "{}", text);
+}
+
+fn failure(options: &Options, message: &str) -> i32 {
+    if options.json {
+        print_json(json!({ "error": message }));
+    }
+    eprintln!("suptool : {}", message);
+    EXIT_ERROR
+}
+
+fn usage_error(message: &str) -> i32 {
+    eprintln!("suptool : {}\n\n{}", message, USAGE);
+    EXIT_USAGE
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn logs_to_stderr_retablit_stdout_apres_une_panique() {
+        let stdout_stat = || unsafe {
+            let mut stat: libc::stat = std::mem::zeroed();
+            libc::fstat(1, &mut stat);
+            (stat.st_dev, stat.st_ino)
+        };
+        let original = stdout_stat();
+
+        let panicked = std::panic::catch_unwind(|| logs_to_stderr(|| panic!("échec de l'opération")));
+        assert!(panicked.is_err());
+        assert_eq!(stdout_stat(), original);
+    }
+
+    #[test]
+    fn parse_lit_options_et_mots() {
+        let options = Options::parse(&args(&["rapport", "--json", "--profile", "Projets", "--limit", "5", "été"])).unwrap();
+        assert!(options.json && !options.dry_run);
+        assert_eq!(options.profile.as_deref(), Some("Projets"));
+        assert_eq!(options.limit, 5);
+        assert_eq!(options.words, args(&["rapport", "été"]));
+    }
+
+    #[test]
+    fn parse_garde_les_mots_apres_double_tiret() {
+        let options = Options::parse(&args(&["--dry-run", "--", "--json", "-x"])).unwrap();
+        assert!(options.dry_run && !options.json);
+        assert_eq!(options.limit, DEFAULT_LIMIT);
+        assert_eq!(options.words, args(&["--json", "-x"]));
+    }
+
+    #[test]
+    fn parse_refuse_les_options_invalides() {
+        assert!(Options::parse(&args(&["--verbose"])).is_err());
+        assert!(Options::parse(&args(&["--profile"])).is_err());
+        assert!(Options::parse(&args(&["--limit", "0"])).is_err());
+        assert!(Options::parse(&args(&["--limit", "beaucoup"])).is_err());
+    }
+
+    #[test]
+    fn unix_seconds_ramene_les_dates_anterieures_a_zero() {
+        assert_eq!(unix_seconds(UNIX_EPOCH + std::time::Duration::from_secs(42)), 42);
+        assert_eq!(unix_seconds(UNIX_EPOCH - std::time::Duration::from_secs(1)), 0);
+    }
+}
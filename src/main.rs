@@ -1,7 +1,10 @@
 use std::env;
 use std::io::Write;
+use std::path::Path;
 
 mod app;
+mod cli;
+mod cleartool;
 mod pages;
 mod widgets;
 mod slint_generated;
@@ -24,7 +27,38 @@ fn main() {
         }
     }
 
-    if let Err(e) = app::run() {
+    // Mode sans interface utilisé pour réindexer avec élévation (voir FileSearcher::retry_failed_elevated)
+    let args: Vec<String> = env::args().collect();
+    if args.len() == 4 && args[1] == "--index-paths" {
+        if let Err(e) = pages::features::run_index_helper(Path::new(&args[2]), Path::new(&args[3])) {
+            eprintln!("Erreur lors de l'indexation: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Lancé par D-Bus quand GNOME Shell ou KRunner cherche sans que l'application soit ouverte
+    #[cfg(target_os = "linux")]
+    if args.len() == 2 && args[1] == "--search-provider" {
+        if let Err(e) = pages::features::run_search_provider() {
+            eprintln!("Fournisseur de recherche D-Bus indisponible: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Ligne de commande sans interface (`suptool search ...`, voir cli.rs)
+    if args.len() > 1 && cli::COMMANDS.contains(&args[1].as_str()) {
+        std::process::exit(cli::run(&args[1], &args[2..]));
+    }
+
+    // Recherche rapide : confiée à l'instance déjà lancée s'il y en a une
+    let quick = args.len() == 2 && args[1] == "--quick";
+    if quick && utils::send_to_running_instance("quick") {
+        return;
+    }
+
+    if let Err(e) = app::run(quick) {
         eprintln!("Erreur lors du lancement de l'application: {}", e);
         print!("Appuyez sur Entrée pour continuer...");
         std::io::stdout().flush().unwrap();
@@ -0,0 +1,172 @@
+use crate::slint_generated::{MainWindow, AppLogic};
+use crate::pages::features::{findings_to_csv, findings_to_json, AuditFinding, FileSearcher, Severity};
+use crate::utils::pick_export_path;
+use crate::widgets::show_notification;
+use slint::{ComponentHandle, Weak, ModelRc, VecModel, SharedString, StandardListViewItem};
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::thread;
+
+// Le tableau n'affiche que les findings les plus graves, l'export contient tout
+const MAX_AUDIT_ROWS: usize = 5000;
+
+struct AuditState {
+    findings: Vec<AuditFinding>,
+    checked: usize,
+    // Gravité minimale affichée (index dans Severity::ALL)
+    min_severity: usize,
+}
+
+// `searcher` est celui d'EverySup : l'audit porte sur le profil sélectionné
+pub fn init(window: &Weak<MainWindow>, searcher: Arc<FileSearcher>) {
+    let state = Arc::new(Mutex::new(AuditState {
+        findings: Vec::new(),
+        checked: 0,
+        min_severity: Severity::ALL.len() - 1,
+    }));
+
+    if let Some(window) = window.upgrade() {
+        let logic = window.global::<AppLogic>();
+
+        logic.on_run_audit({
+            let searcher = searcher.clone();
+            let state = state.clone();
+            let window_weak = window.as_weak();
+            move || {
+                if searcher.file_count() == 0 {
+                    show_notification(
+                        &window_weak,
+                        "audit-empty",
+                        "Index vide",
+                        "Lancez une indexation avant l'audit",
+                        "warning"
+                    );
+                    return;
+                }
+
+                if let Some(window) = window_weak.upgrade() {
+                    window.global::<AppLogic>().set_audit_running(true);
+                }
+
+                let searcher = searcher.clone();
+                let state = state.clone();
+                let window_weak = window_weak.clone();
+
+                thread::spawn(move || {
+                    let result = searcher.run_audit();
+
+                    slint::invoke_from_event_loop(move || {
+                        let Some(window) = window_weak.upgrade() else {
+                            return;
+                        };
+                        let logic = window.global::<AppLogic>();
+                        logic.set_audit_running(false);
+
+                        match result {
+                            Ok(report) => {
+                                let mut state = state.lock();
+                                state.findings = report.findings;
+                                state.checked = report.checked;
+                                update_audit(&logic, &state);
+                            }
+                            Err(e) => show_notification(
+                                &window_weak,
+                                "audit-error",
+                                "Audit impossible",
+                                &e.to_string(),
+                                "error"
+                            ),
+                        }
+                    }).unwrap();
+                });
+            }
+        });
+
+        logic.on_audit_severity_changed({
+            let state = state.clone();
+            let window_weak = window.as_weak();
+            move |index| {
+                let mut state = state.lock();
+                state.min_severity = (index.max(0) as usize).min(Severity::ALL.len() - 1);
+                if let Some(window) = window_weak.upgrade() {
+                    update_audit(&window.global::<AppLogic>(), &state);
+                }
+            }
+        });
+
+        logic.on_export_audit({
+            let state = state.clone();
+            let window_weak = window.as_weak();
+            move |format| {
+                let state = state.lock();
+                if state.findings.is_empty() {
+                    return;
+                }
+                let Some(path) = pick_export_path("security_audit", &format) else {
+                    return;
+                };
+
+                let content = if format == "json" {
+                    findings_to_json(&state.findings).unwrap_or_default()
+                } else {
+                    findings_to_csv(&state.findings)
+                };
+
+                match std::fs::write(&path, content) {
+                    Ok(()) => show_notification(
+                        &window_weak,
+                        "audit-export",
+                        "Export terminé",
+                        &path.to_string_lossy(),
+                        "success"
+                    ),
+                    Err(e) => show_notification(
+                        &window_weak,
+                        "audit-export",
+                        "Erreur d'export",
+                        &e.to_string(),
+                        "error"
+                    ),
+                }
+            }
+        });
+    }
+}
+
+fn update_audit(logic: &AppLogic, state: &AuditState) {
+    let min_severity = Severity::ALL[state.min_severity];
+    let shown: Vec<&AuditFinding> = state.findings.iter()
+        .filter(|finding| finding.severity <= min_severity)
+        .collect();
+
+    let rows: Vec<ModelRc<StandardListViewItem>> = shown.iter()
+        .take(MAX_AUDIT_ROWS)
+        .map(|finding| {
+            let row = [
+                finding.severity.label(),
+                finding.issue.label(),
+                &finding.path,
+                &finding.mode,
+                &finding.owner,
+                finding.issue.explanation(),
+            ];
+            ModelRc::new(VecModel::from(
+                row.into_iter()
+                    .map(|s| StandardListViewItem::from(SharedString::from(s)))
+                    .collect::<Vec<_>>()
+            ))
+        })
+        .collect();
+    logic.set_audit_rows(ModelRc::new(VecModel::from(rows)));
+
+    let count = |severity: Severity| state.findings.iter().filter(|f| f.severity == severity).count();
+    logic.set_audit_summary(SharedString::from(format!(
+        "{} entrées vérifiées : {} critiques, {} hautes, {} moyennes, {} basses ({} affichées)",
+        state.checked,
+        count(Severity::Critical),
+        count(Severity::High),
+        count(Severity::Medium),
+        count(Severity::Low),
+        shown.len().min(MAX_AUDIT_ROWS)
+    )));
+}
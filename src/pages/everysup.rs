@@ -4,72 +4,254 @@
  * - Clean le code, enlever les println! etc...
  */
 
-use crate::slint_generated::{MainWindow, AppLogic};
-use crate::pages::features::FileSearcher;
-use crate::utils::{format_size, format_time};
-use slint::{ComponentHandle, Weak};
+use crate::slint_generated::{
+    MainWindow, AppLogic, SavedSearchItem, HistoryItem,
+    FilePreview, PreviewLine, PreviewSpan, PreviewField, ResultRow, ResultCell, TextSpan,
+    ResultColumn, ColumnChoice, IndexProfileItem, ChecksumItem
+};
+use crate::pages::features::{
+    FileSearcher, SearchMatch, MAX_RESULTS, spawn_mount_monitor, MountEvent, SearchHistory, SavedSearch,
+    load_preview, PreviewContent, TokenKind, column_settings, update_column_settings,
+    Column, ColumnSettings, extension_of, group_results, GroupBy,
+    IndexError, summarize, errors_to_csv, errors_to_json, spawn_index_scheduler,
+    NamedProfile, IndexSchedule, api_settings, set_api_settings, ApiSettings, HttpApi,
+    extract_member, seven_zip_missing, split_member_path, file_tags, set_file_tags,
+    compute_checksums, find_sidecar, matching_algorithm, Checksum, HashAlgorithm
+};
+#[cfg(target_os = "linux")]
+use crate::pages::features::spawn_search_provider;
+use crate::pages::features::FilePreview as PreviewData;
+use crate::utils::{format_size, format_time, elide_spans, pick_export_path, open_path, copy_to_clipboard, VolumeInfo};
+use crate::widgets::{show_notification, show_action_notification};
+use slint::{ComponentHandle, Weak, ModelRc, VecModel, SharedString, StandardListViewItem, Color, Image, Rgba8Pixel, SharedPixelBuffer};
 use parking_lot::Mutex;
-use std::time::{Duration, Instant};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::path::Path;
 use std::thread;
 
-pub fn init(window: &Weak<MainWindow>) {
+// Génération, requête et historique d'une recherche validée
+type PendingRecord = (u64, String, Arc<Mutex<SearchHistory>>);
+
+thread_local! {
+    // Groupes de résultats repliés, conservés d'une recherche à l'autre
+    static COLLAPSED_GROUPS: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+    // Génération, requête et nombre de résultats de la recherche affichée, repris par l'historique
+    static DISPLAYED_SEARCH: RefCell<Option<(u64, String, usize)>> = const { RefCell::new(None) };
+    // Recherche validée dont les résultats ne sont pas encore arrivés, avec sa génération
+    static PENDING_RECORD: RefCell<Option<PendingRecord>> = const { RefCell::new(None) };
+}
+
+// Chaque recherche incrémente le compteur : des résultats arrivés en retard sont ignorés
+static SEARCH_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+// Largeur moyenne d'un caractère dans le tableau, pour estimer la place disponible
+const CHAR_WIDTH: f32 = 7.0;
+// Intervalle minimal entre deux mises à jour de la barre de progression des empreintes
+const CHECKSUM_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+// Dernier calcul d'empreintes lancé depuis la fenêtre Checksums
+#[derive(Default)]
+struct ChecksumRun {
+    path: String,
+    checksums: Vec<Checksum>,
+    // Levé à la fermeture de la fenêtre ou quand un autre fichier est choisi
+    cancel: Arc<AtomicBool>,
+}
+
+pub fn init(window: &Weak<MainWindow>, searcher: Arc<FileSearcher>) {
     let window_weak = window.clone();
-    let searcher = Arc::new(FileSearcher::new());
     let last_query = Arc::new(Mutex::new((String::new(), Instant::now())));
     let is_indexing = Arc::new(AtomicBool::new(false));
     
     if let Some(window) = window_weak.upgrade() {
         let searcher_clone = searcher.clone();
+        let last_query_clone = last_query.clone();
         let window_weak_clone = window.as_weak();
         let is_indexing_clone = is_indexing.clone();
         
         window.global::<AppLogic>().on_start_indexing(move || {
             let window_weak = window_weak_clone.clone();
-            let searcher = searcher_clone.clone();
             let is_indexing = is_indexing_clone.clone();
             
-            // Si déjà en cours d'indexation, on COUPE TOUT
+            // Si déjà en cours d'indexation, on COUPE TOUT (l'index en cours de
+            // construction est abandonné, l'ancien reste utilisable)
             if is_indexing.load(Ordering::SeqCst) {
                 println!("🛑 ARRÊT FORCÉ DE L'INDEXATION");
+                searcher_clone.clear_queue();
                 is_indexing.store(false, Ordering::SeqCst);
-                searcher.clear_index();
                 if let Some(window) = window_weak.upgrade() {
                     window.set_is_indexing(false);
                 }
                 return;
             }
             
-            // Nouvelle indexation
-            is_indexing.store(true, Ordering::SeqCst);
-            if let Some(window) = window_weak.upgrade() {
-                window.set_is_indexing(true);
+            // Le profil sélectionné, ou tous les profils l'un après l'autre
+            match searcher_clone.scope() {
+                Some(profile) => searcher_clone.queue_indexing(&profile),
+                None => searcher_clone.profiles()
+                    .iter()
+                    .for_each(|profile| searcher_clone.queue_indexing(&profile.name)),
             }
+            start_indexing(window_weak, searcher_clone.clone(), last_query_clone.clone(), is_indexing);
+        });
 
-            let is_indexing_thread = is_indexing.clone();
-            thread::spawn(move || {
-                searcher.build_index(move || {
-                    if !is_indexing_thread.load(Ordering::SeqCst) {
-                        println!("⚡ Interruption immédiate de l'indexation");
-                        return true;
+        update_profiles(&window, &searcher);
+        update_last_indexed(&window, &searcher);
+
+        window.global::<AppLogic>().on_index_scope_changed({
+            let searcher = searcher.clone();
+            let last_query = last_query.clone();
+            let window_weak = window.as_weak();
+            move |index| {
+                let profiles = searcher.profiles();
+                let scope = usize::try_from(index - 1).ok()
+                    .and_then(|i| profiles.get(i))
+                    .map(|profile| profile.name.clone());
+                searcher.set_scope(scope);
+
+                if let Some(window) = window_weak.upgrade() {
+                    update_last_indexed(&window, &searcher);
+                }
+                rerun_last_search(&window_weak, &searcher, &last_query);
+            }
+        });
+
+        window.global::<AppLogic>().on_save_index_profile({
+            let searcher = searcher.clone();
+            let window_weak = window.as_weak();
+            move |index, item| {
+                let previous = usize::try_from(index).ok()
+                    .and_then(|i| searcher.profiles().get(i).map(|profile| profile.name.clone()));
+
+                match searcher.save_profile(previous.as_deref(), to_named_profile(&item)) {
+                    Ok(()) => {
+                        if let Some(window) = window_weak.upgrade() {
+                            update_profiles(&window, &searcher);
+                            window.global::<AppLogic>().set_show_profile_editor(false);
+                        }
+                        show_notification(
+                            &window_weak,
+                            "index-profile",
+                            "Profil enregistré",
+                            &format!("{} : lancez l'indexation pour le remplir", item.name.trim()),
+                            "success"
+                        );
                     }
-                    false
-                });
-                
-                // Reset le flag à la fin quoi qu'il arrive
-                is_indexing.store(false, Ordering::SeqCst);
-                
-                slint::invoke_from_event_loop(move || {
-                    if let Some(window) = window_weak.upgrade() {
-                        window.set_is_indexing(false);
+                    Err(e) => show_notification(
+                        &window_weak,
+                        "index-profile",
+                        "Profil non enregistré",
+                        &e.to_string(),
+                        "error"
+                    ),
+                }
+            }
+        });
+
+        window.global::<AppLogic>().on_delete_index_profile({
+            let searcher = searcher.clone();
+            let last_query = last_query.clone();
+            let window_weak = window.as_weak();
+            move |index| {
+                let Some(profile) = usize::try_from(index).ok().and_then(|i| searcher.profiles().get(i).cloned()) else {
+                    return;
+                };
+
+                match searcher.delete_profile(&profile.name) {
+                    Ok(()) => {
+                        if let Some(window) = window_weak.upgrade() {
+                            update_profiles(&window, &searcher);
+                            update_last_indexed(&window, &searcher);
+                        }
+                        rerun_last_search(&window_weak, &searcher, &last_query);
                     }
-                }).unwrap();
-            });
+                    Err(e) => show_notification(
+                        &window_weak,
+                        "index-profile",
+                        "Suppression impossible",
+                        &e.to_string(),
+                        "error"
+                    ),
+                }
+            }
         });
 
+        spawn_index_scheduler(
+            {
+                let searcher = searcher.clone();
+                move |profile| searcher.profile_last_indexed(profile)
+            },
+            {
+                let searcher = searcher.clone();
+                let last_query = last_query.clone();
+                let is_indexing = is_indexing.clone();
+                let window_weak = window.as_weak();
+                move |profile| queue_reindex(&window_weak, &searcher, &last_query, &is_indexing, Some(profile))
+            }
+        );
+
+        // API HTTP locale, redémarrée à chaque changement de réglages
+        let http_api: Arc<Mutex<Option<HttpApi>>> = Arc::new(Mutex::new(None));
+        let settings = api_settings();
+        window.global::<AppLogic>().set_api_enabled(settings.enabled);
+        window.global::<AppLogic>().set_api_port(settings.port as i32);
+        window.global::<AppLogic>().set_api_token(SharedString::from(settings.token.as_str()));
+        if settings.enabled {
+            if let Err(e) = start_http_api(&http_api, &settings, &window.as_weak(), &searcher, &last_query, &is_indexing) {
+                eprintln!("API HTTP non démarrée: {}", e);
+            }
+        }
+
+        {
+            let searcher = searcher.clone();
+            let last_query = last_query.clone();
+            let is_indexing = is_indexing.clone();
+            let window_weak = window.as_weak();
+            window.global::<AppLogic>().on_api_settings_changed(move |enabled, port, token| {
+                let settings = ApiSettings {
+                    enabled,
+                    port: port.clamp(1, u16::MAX as i32) as u16,
+                    token: token.trim().to_string(),
+                };
+                if let Err(e) = set_api_settings(&settings) {
+                    eprintln!("Erreur lors de la sauvegarde des réglages de l'API: {}", e);
+                }
+
+                if let Some(api) = http_api.lock().take() {
+                    api.stop();
+                }
+                if !settings.enabled {
+                    return;
+                }
+                let Some(window) = window_weak.upgrade() else { return };
+                match start_http_api(&http_api, &settings, &window_weak, &searcher, &last_query, &is_indexing) {
+                    Ok(()) => show_notification(
+                        &window_weak,
+                        "http-api",
+                        "API HTTP",
+                        &format!("Disponible sur http://127.0.0.1:{}", settings.port),
+                        "success"
+                    ),
+                    Err(e) => {
+                        window.global::<AppLogic>().set_api_enabled(false);
+                        show_notification(
+                            &window_weak,
+                            "http-api",
+                            "API HTTP non démarrée",
+                            &format!("Port {} : {}", settings.port, e),
+                            "error"
+                        );
+                    }
+                }
+            });
+        }
+
         let searcher_clone = searcher.clone();
-        let last_query = last_query.clone();
+        let last_query_clone = last_query.clone();
         let window_weak = window.as_weak();
         
         window.global::<AppLogic>().on_everysup_changed(move |value| {
@@ -77,53 +259,1412 @@ pub fn init(window: &Weak<MainWindow>) {
             let now = Instant::now();
             
             {
-                let (ref last_value, ref last_time) = *last_query.lock();
+                let (ref last_value, ref last_time) = *last_query_clone.lock();
                 if now.duration_since(*last_time) < Duration::from_millis(1) 
                    || value_string == *last_value {
                     return;
                 }
             }
             
-            *last_query.lock() = (value_string.clone(), now);
+            *last_query_clone.lock() = (value_string.clone(), now);
             
             if value_string.len() < 2 {
+                // Une recherche encore en cours ne doit pas réafficher ses résultats
+                SEARCH_GENERATION.fetch_add(1, Ordering::SeqCst);
                 if let Some(window) = window_weak.upgrade() {
-                    window.set_everysup_files(slint::ModelRc::new(slint::VecModel::default()).into());
+                    window.set_everysup_files(ModelRc::default());
+                    window.set_everysup_suggestions(ModelRc::default());
+                    window.set_everysup_did_you_mean(SharedString::default());
                 }
                 return;
             }
 
-            let window_weak = window_weak.clone();
-            let searcher = searcher_clone.clone();
-            
-            std::thread::spawn(move || {
-                let results = searcher.search(&value_string);
-                
+            run_search(window_weak.clone(), searcher_clone.clone(), value_string);
+        });
+
+        let volumes: Arc<Mutex<Vec<VolumeInfo>>> = Arc::new(Mutex::new(Vec::new()));
+        let catalogs: Arc<Mutex<Vec<VolumeInfo>>> = Arc::new(Mutex::new(Vec::new()));
+        update_volumes(&window, &searcher, &volumes, &catalogs);
+
+        window.global::<AppLogic>().on_refresh_volumes({
+            let searcher = searcher.clone();
+            let volumes = volumes.clone();
+            let catalogs = catalogs.clone();
+            let window_weak = window.as_weak();
+            move || {
+                if let Some(window) = window_weak.upgrade() {
+                    update_volumes(&window, &searcher, &volumes, &catalogs);
+                }
+            }
+        });
+
+        window.global::<AppLogic>().on_catalog_volume({
+            let searcher = searcher.clone();
+            let volumes = volumes.clone();
+            let catalogs = catalogs.clone();
+            let window_weak = window.as_weak();
+            move |index| {
+                let Some(volume) = volumes.lock().get(index as usize).cloned() else {
+                    return;
+                };
+
+                if let Some(window) = window_weak.upgrade() {
+                    window.set_is_cataloging(true);
+                }
+
+                let searcher = searcher.clone();
+                let volumes = volumes.clone();
+                let catalogs = catalogs.clone();
+                let window_weak = window_weak.clone();
+
+                thread::spawn(move || {
+                    let result = searcher.catalog_volume(&volume, || false);
+
+                    slint::invoke_from_event_loop(move || {
+                        match result {
+                            Ok(count) => show_notification(
+                                &window_weak,
+                                "catalog-done",
+                                "Volume catalogué",
+                                &format!("{} : {} entrées", volume.display_name(), count),
+                                "success"
+                            ),
+                            Err(e) => show_notification(
+                                &window_weak,
+                                "catalog-error",
+                                "Erreur de catalogage",
+                                &e.to_string(),
+                                "error"
+                            ),
+                        }
+                        if let Some(window) = window_weak.upgrade() {
+                            window.set_is_cataloging(false);
+                            update_volumes(&window, &searcher, &volumes, &catalogs);
+                        }
+                    }).unwrap();
+                });
+            }
+        });
+
+        window.global::<AppLogic>().on_forget_catalog({
+            let searcher = searcher.clone();
+            let volumes = volumes.clone();
+            let catalogs = catalogs.clone();
+            let window_weak = window.as_weak();
+            move |index| {
+                let Some(volume) = catalogs.lock().get(index as usize).cloned() else {
+                    return;
+                };
+
+                if let Err(e) = searcher.forget_catalog(&volume.uuid) {
+                    show_notification(
+                        &window_weak,
+                        "catalog-error",
+                        "Erreur de catalogage",
+                        &e.to_string(),
+                        "error"
+                    );
+                }
+                if let Some(window) = window_weak.upgrade() {
+                    update_volumes(&window, &searcher, &volumes, &catalogs);
+                }
+            }
+        });
+
+        let history = Arc::new(Mutex::new(SearchHistory::load()));
+        update_history(&window, &history.lock());
+
+        window.global::<AppLogic>().on_everysup_accepted({
+            let history = history.clone();
+            let window_weak = window.as_weak();
+            move |query| {
+                record_search(&window_weak, &history, query.to_string());
+            }
+        });
+
+        window.global::<AppLogic>().on_save_search({
+            let history = history.clone();
+            let window_weak = window.as_weak();
+            move |name, query, kind, extensions| {
+                let extensions = extensions.split(',')
+                    .map(|e| e.trim().trim_start_matches('.').to_lowercase())
+                    .filter(|e| !e.is_empty())
+                    .collect();
+
+                let mut history = history.lock();
+                history.add_saved(SavedSearch {
+                    name: name.trim().to_string(),
+                    query: query.trim().to_string(),
+                    kind: kind.to_string(),
+                    extensions,
+                    pinned: false,
+                    created: SystemTime::now(),
+                });
+                save_history(&window_weak, &history);
+                if let Some(window) = window_weak.upgrade() {
+                    update_history(&window, &history);
+                }
+            }
+        });
+
+        window.global::<AppLogic>().on_run_saved_search({
+            let searcher = searcher.clone();
+            let history = history.clone();
+            let last_query = last_query.clone();
+            let window_weak = window.as_weak();
+            move |index| {
+                let query = history.lock().saved.get(index as usize).map(|s| s.to_query());
+                if let Some(query) = query {
+                    run_saved_search(&window_weak, &searcher, &history, &last_query, query);
+                }
+            }
+        });
+
+        window.global::<AppLogic>().on_run_pinned_search({
+            let searcher = searcher.clone();
+            let history = history.clone();
+            let last_query = last_query.clone();
+            let window_weak = window.as_weak();
+            move |index| {
+                let query = history.lock().pinned().get(index as usize).map(|s| s.to_query());
+                if let Some(query) = query {
+                    run_saved_search(&window_weak, &searcher, &history, &last_query, query);
+                }
+            }
+        });
+
+        window.global::<AppLogic>().on_launch_search({
+            let searcher = searcher.clone();
+            let history = history.clone();
+            let last_query = last_query.clone();
+            let window_weak = window.as_weak();
+            move |query| {
+                if let Some(window) = window_weak.upgrade() {
+                    let _ = window.show();
+                }
+                run_saved_search(&window_weak, &searcher, &history, &last_query, query.to_string());
+            }
+        });
+
+        // Recherche depuis GNOME Shell / KRunner ; « Rechercher dans l'application »
+        // ramène la fenêtre avec la requête
+        #[cfg(target_os = "linux")]
+        spawn_search_provider(searcher.clone(), {
+            let window_weak = window.as_weak();
+            move |query| {
+                let window_weak = window_weak.clone();
                 slint::invoke_from_event_loop(move || {
                     if let Some(window) = window_weak.upgrade() {
-                        let model = std::rc::Rc::new(slint::VecModel::from(
-                            results.into_iter().take(100).map(|result| {
-                                // Conversion en modèle UI
-                                let row = vec![
-                                    result.name,
-                                    result.path,
-                                    format_size(result.size).to_string(),
-                                    if result.is_dir { "Folder".into() } else { "File".into() },
-                                    format_time(result.modified),
-                                ];
-                                slint::ModelRc::new(slint::VecModel::from(
-                                    row.into_iter().map(|s| {
-                                        let mut item = slint::StandardListViewItem::default();
-                                        item.text = s.into();
-                                        item
-                                    }).collect::<Vec<_>>()
-                                ))
-                            }).collect::<Vec<_>>()
-                        ));
-                        window.set_everysup_files(model.into());
+                        window.global::<AppLogic>().invoke_launch_search(query.into());
                     }
                 }).unwrap();
-            });
+            }
+        });
+
+        window.global::<AppLogic>().on_toggle_pin_search({
+            let history = history.clone();
+            let window_weak = window.as_weak();
+            move |index| {
+                let mut history = history.lock();
+                history.toggle_pin(index as usize);
+                save_history(&window_weak, &history);
+                if let Some(window) = window_weak.upgrade() {
+                    update_history(&window, &history);
+                }
+            }
+        });
+
+        window.global::<AppLogic>().on_delete_saved_search({
+            let history = history.clone();
+            let window_weak = window.as_weak();
+            move |index| {
+                let mut history = history.lock();
+                history.remove_saved(index as usize);
+                save_history(&window_weak, &history);
+                if let Some(window) = window_weak.upgrade() {
+                    update_history(&window, &history);
+                }
+            }
+        });
+
+        window.global::<AppLogic>().on_clear_search_history({
+            let history = history.clone();
+            let window_weak = window.as_weak();
+            move || {
+                let mut history = history.lock();
+                history.clear_entries();
+                save_history(&window_weak, &history);
+                if let Some(window) = window_weak.upgrade() {
+                    update_history(&window, &history);
+                }
+            }
+        });
+
+        update_columns(&window, &column_settings());
+
+        window.global::<AppLogic>().on_column_resized({
+            let searcher = searcher.clone();
+            let last_query = last_query.clone();
+            let window_weak = window.as_weak();
+            move |index, width| {
+                let Some(&column) = column_settings().visible.get(index as usize) else {
+                    return;
+                };
+                let result = update_column_settings(|columns| {
+                    columns.widths.insert(column, width);
+                });
+                apply_column_settings(&window_weak, &searcher, &last_query, result);
+            }
+        });
+
+        window.global::<AppLogic>().on_toggle_column({
+            let searcher = searcher.clone();
+            let last_query = last_query.clone();
+            let window_weak = window.as_weak();
+            move |index| {
+                let Some(&column) = Column::ALL.get(index as usize) else {
+                    return;
+                };
+                let had_attributes = column_settings().wants_attributes();
+                let result = update_column_settings(|columns| columns.toggle(column));
+
+                // Les attributs facultatifs ne sont relevés qu'à l'indexation
+                let needs_reindex = result.as_ref()
+                    .map(|columns| !had_attributes && columns.wants_attributes())
+                    .unwrap_or(false);
+                if needs_reindex && searcher.file_count() > 0 {
+                    show_notification(
+                        &window_weak,
+                        "columns-reindex",
+                        "Nouvelles colonnes",
+                        "Relancez l'indexation pour remplir ces colonnes",
+                        "info"
+                    );
+                }
+
+                apply_column_settings(&window_weak, &searcher, &last_query, result);
+            }
+        });
+
+        window.global::<AppLogic>().on_export_index_errors({
+            let searcher = searcher.clone();
+            let window_weak = window.as_weak();
+            move |format| {
+                let errors = searcher.index_errors();
+                let Some(path) = pick_export_path("index_errors", &format) else {
+                    return;
+                };
+
+                let content = if format == "json" {
+                    errors_to_json(&errors).unwrap_or_default()
+                } else {
+                    errors_to_csv(&errors)
+                };
+
+                match std::fs::write(&path, content) {
+                    Ok(()) => show_notification(
+                        &window_weak,
+                        "index-errors-export",
+                        "Export terminé",
+                        &path.to_string_lossy(),
+                        "success"
+                    ),
+                    Err(e) => show_notification(
+                        &window_weak,
+                        "index-errors-export",
+                        "Erreur d'export",
+                        &e.to_string(),
+                        "error"
+                    ),
+                }
+            }
+        });
+
+        window.global::<AppLogic>().on_retry_index_errors({
+            let searcher = searcher.clone();
+            let last_query = last_query.clone();
+            let is_indexing = is_indexing.clone();
+            let window_weak = window.as_weak();
+            move || {
+                if is_indexing.swap(true, Ordering::SeqCst) {
+                    return;
+                }
+                if let Some(window) = window_weak.upgrade() {
+                    window.set_is_indexing(true);
+                }
+
+                let searcher = searcher.clone();
+                let last_query = last_query.clone();
+                let is_indexing = is_indexing.clone();
+                let window_weak = window_weak.clone();
+                thread::spawn(move || {
+                    let result = searcher.retry_failed_elevated();
+                    if result.is_ok() {
+                        let profiles: Vec<String> = searcher.profiles().into_iter().map(|profile| profile.name).collect();
+                        save_indexes(&searcher, &profiles);
+                    }
+                    is_indexing.store(false, Ordering::SeqCst);
+                    let errors = searcher.index_errors();
+
+                    slint::invoke_from_event_loop(move || {
+                        if let Some(window) = window_weak.upgrade() {
+                            window.set_is_indexing(false);
+                            update_index_errors(&window, &errors);
+                        }
+
+                        match result {
+                            Ok(count) => {
+                                show_notification(
+                                    &window_weak,
+                                    "index-errors",
+                                    "Réindexation terminée",
+                                    &format!("{} entrées ajoutées, {} chemins toujours inaccessibles", count, errors.len()),
+                                    if errors.is_empty() { "success" } else { "warning" }
+                                );
+                                rerun_last_search(&window_weak, &searcher, &last_query);
+                            }
+                            Err(e) => show_notification(
+                                &window_weak,
+                                "index-errors",
+                                "Réindexation impossible",
+                                &e.to_string(),
+                                "error"
+                            ),
+                        }
+                        // Les réindexations demandées entre-temps attendaient dans la file
+                        start_indexing(window_weak, searcher, last_query, is_indexing);
+                    }).unwrap();
+                });
+            }
+        });
+
+        window.global::<AppLogic>().on_group_by_changed({
+            let searcher = searcher.clone();
+            let last_query = last_query.clone();
+            let window_weak = window.as_weak();
+            move |_| {
+                COLLAPSED_GROUPS.with(|groups| groups.borrow_mut().clear());
+                rerun_last_search(&window_weak, &searcher, &last_query);
+            }
+        });
+
+        window.global::<AppLogic>().on_toggle_result_group({
+            let searcher = searcher.clone();
+            let last_query = last_query.clone();
+            let window_weak = window.as_weak();
+            move |label| {
+                COLLAPSED_GROUPS.with(|groups| {
+                    let mut groups = groups.borrow_mut();
+                    if !groups.remove(label.as_str()) {
+                        groups.insert(label.to_string());
+                    }
+                });
+                rerun_last_search(&window_weak, &searcher, &last_query);
+            }
+        });
+
+        // Chaque sélection incrémente le compteur : un aperçu arrivé en retard est ignoré
+        let preview_generation = Arc::new(AtomicU64::new(0));
+        window.global::<AppLogic>().on_preview_file({
+            let window_weak = window.as_weak();
+            move |path| {
+                let generation = preview_generation.fetch_add(1, Ordering::SeqCst) + 1;
+                let preview_generation = preview_generation.clone();
+                let window_weak = window_weak.clone();
+
+                if let Some(window) = window_weak.upgrade() {
+                    window.set_everysup_preview_loading(true);
+                }
+
+                thread::spawn(move || {
+                    let preview = load_preview(Path::new(path.as_str()));
+
+                    slint::invoke_from_event_loop(move || {
+                        if preview_generation.load(Ordering::SeqCst) != generation {
+                            return;
+                        }
+                        if let Some(window) = window_weak.upgrade() {
+                            window.set_everysup_preview(to_file_preview(&path, preview));
+                            window.set_everysup_preview_loading(false);
+                        }
+                    }).unwrap();
+                });
+            }
+        });
+
+        window.global::<AppLogic>().on_save_file_tags({
+            let searcher = searcher.clone();
+            let last_query = last_query.clone();
+            let window_weak = window.as_weak();
+            move |path, tags, note| {
+                match set_file_tags(&path, &tags, &note) {
+                    Ok(()) => {
+                        show_notification(&window_weak, "file-tags", "Tags enregistrés", &path, "success");
+                        // La colonne Tags et les recherches `tag:` changent
+                        rerun_last_search(&window_weak, &searcher, &last_query);
+                    }
+                    Err(e) => show_notification(
+                        &window_weak,
+                        "file-tags",
+                        "Erreur d'enregistrement",
+                        &e.to_string(),
+                        "error"
+                    ),
+                }
+            }
+        });
+
+        window.global::<AppLogic>().on_extract_archive_member({
+            let window_weak = window.as_weak();
+            move |path| {
+                let Some((archive, member)) = split_member_path(Path::new(path.as_str())) else {
+                    show_notification(&window_weak, "extract-member", "Extraction impossible", "Archive introuvable", "error");
+                    return;
+                };
+                if seven_zip_missing(&archive) {
+                    show_notification(
+                        &window_weak,
+                        "extract-member",
+                        "7z introuvable",
+                        "Installez 7-Zip (commande 7z, 7zz ou 7za) pour extraire les archives .7z",
+                        "error"
+                    );
+                    return;
+                }
+                let Some(dest_dir) = rfd::FileDialog::new()
+                    .set_title("Extraire vers")
+                    .pick_folder() else {
+                    return;
+                };
+
+                let window_weak = window_weak.clone();
+                thread::spawn(move || {
+                    let result = extract_member(&archive, &member, &dest_dir);
+
+                    slint::invoke_from_event_loop(move || match result {
+                        Ok(extracted) => show_action_notification(
+                            &window_weak,
+                            "extract-member",
+                            "Extraction terminée",
+                            &extracted.to_string_lossy(),
+                            "success",
+                            "Ouvrir le dossier",
+                            move || {
+                                let _ = open_path(&dest_dir);
+                            }
+                        ),
+                        Err(e) => show_notification(
+                            &window_weak,
+                            "extract-member",
+                            "Erreur d'extraction",
+                            &e.to_string(),
+                            "error"
+                        ),
+                    }).unwrap();
+                });
+            }
+        });
+
+        let checksum_run = Arc::new(Mutex::new(ChecksumRun::default()));
+
+        window.global::<AppLogic>().on_compute_checksums({
+            let checksum_run = checksum_run.clone();
+            let window_weak = window.as_weak();
+            move |path| {
+                let cancel = Arc::new(AtomicBool::new(false));
+                {
+                    let mut run = checksum_run.lock();
+                    run.cancel.store(true, Ordering::Relaxed);
+                    *run = ChecksumRun { path: path.to_string(), checksums: Vec::new(), cancel: cancel.clone() };
+                }
+                if let Some(window) = window_weak.upgrade() {
+                    let logic = window.global::<AppLogic>();
+                    logic.set_checksum_path(path.clone());
+                    logic.set_checksum_progress(0.0);
+                    logic.set_checksum_running(true);
+                    logic.set_checksum_status("Calcul des empreintes MD5, SHA-1, SHA-256, BLAKE3 et CRC32...".into());
+                    logic.set_checksum_verdict(SharedString::new());
+                    logic.set_checksums(ModelRc::new(VecModel::from(Vec::<ChecksumItem>::new())));
+                }
+
+                let checksum_run = checksum_run.clone();
+                let window_weak = window_weak.clone();
+                thread::spawn(move || {
+                    let file = Path::new(path.as_str());
+                    let last_progress = Cell::new(Instant::now());
+                    let result = compute_checksums(file, &cancel, |done, total| {
+                        if last_progress.get().elapsed() < CHECKSUM_PROGRESS_INTERVAL {
+                            return;
+                        }
+                        last_progress.set(Instant::now());
+                        let progress = if total == 0 { 1.0 } else { done as f32 / total as f32 };
+                        let window_weak = window_weak.clone();
+                        slint::invoke_from_event_loop(move || {
+                            if let Some(window) = window_weak.upgrade() {
+                                window.global::<AppLogic>().set_checksum_progress(progress);
+                            }
+                        }).unwrap();
+                    });
+                    let sidecar = result.as_ref().ok().and_then(|_| find_sidecar(file));
+
+                    slint::invoke_from_event_loop(move || {
+                        // Fenêtre fermée ou autre fichier choisi entre-temps
+                        if cancel.load(Ordering::Relaxed) {
+                            return;
+                        }
+                        let Some(window) = window_weak.upgrade() else {
+                            return;
+                        };
+                        let logic = window.global::<AppLogic>();
+                        logic.set_checksum_running(false);
+
+                        match result {
+                            Ok(checksums) => {
+                                let matched = sidecar.as_ref()
+                                    .and_then(|sidecar| matching_algorithm(&checksums, &sidecar.expected));
+                                let (status, verdict) = match (&sidecar, matched) {
+                                    (Some(sidecar), Some(algorithm)) => (
+                                        format!("{} correspond à {}", algorithm.label(), sidecar.source.display()),
+                                        "match"
+                                    ),
+                                    (Some(sidecar), None) => (
+                                        format!("Ne correspond pas à l'empreinte indiquée dans {}", sidecar.source.display()),
+                                        "mismatch"
+                                    ),
+                                    (None, _) => ("Aucun fichier d'empreintes trouvé à côté de ce fichier".to_string(), ""),
+                                };
+                                update_checksums(&window, &checksums, matched);
+                                logic.set_checksum_status(status.into());
+                                logic.set_checksum_verdict(verdict.into());
+                                checksum_run.lock().checksums = checksums;
+                            }
+                            Err(e) => {
+                                logic.set_checksum_status(format!("Calcul impossible : {}", e).into());
+                                logic.set_checksum_verdict("mismatch".into());
+                            }
+                        }
+                    }).unwrap();
+                });
+            }
+        });
+
+        window.global::<AppLogic>().on_verify_checksum({
+            let checksum_run = checksum_run.clone();
+            let window_weak = window.as_weak();
+            move |expected| {
+                let run = checksum_run.lock();
+                let Some(window) = window_weak.upgrade() else {
+                    return;
+                };
+                if run.checksums.is_empty() {
+                    return;
+                }
+
+                let matched = matching_algorithm(&run.checksums, &expected);
+                update_checksums(&window, &run.checksums, matched);
+                let logic = window.global::<AppLogic>();
+                match matched {
+                    Some(algorithm) => {
+                        logic.set_checksum_status(format!("{} correspond à l'empreinte attendue", algorithm.label()).into());
+                        logic.set_checksum_verdict("match".into());
+                    }
+                    None => {
+                        logic.set_checksum_status("Ne correspond à aucune des empreintes calculées".into());
+                        logic.set_checksum_verdict("mismatch".into());
+                    }
+                }
+            }
         });
+
+        window.global::<AppLogic>().on_copy_checksum({
+            let checksum_run = checksum_run.clone();
+            let window_weak = window.as_weak();
+            move |index| {
+                let run = checksum_run.lock();
+                // -1 = toutes les empreintes, précédées du chemin
+                let text = if index < 0 {
+                    let lines: Vec<String> = run.checksums.iter()
+                        .map(|checksum| format!("{}: {}", checksum.algorithm.label(), checksum.value))
+                        .collect();
+                    format!("{}\n{}", run.path, lines.join("\n"))
+                } else {
+                    let Some(checksum) = run.checksums.get(index as usize) else {
+                        return;
+                    };
+                    checksum.value.clone()
+                };
+
+                match copy_to_clipboard(&text) {
+                    Ok(()) => show_notification(
+                        &window_weak,
+                        "checksum-copy",
+                        "Copié",
+                        if index < 0 { "Empreintes copiées dans le presse-papiers" } else { "Empreinte copiée dans le presse-papiers" },
+                        "success"
+                    ),
+                    Err(e) => show_notification(
+                        &window_weak,
+                        "checksum-copy",
+                        "Copie impossible",
+                        &e.to_string(),
+                        "error"
+                    ),
+                }
+            }
+        });
+
+        window.global::<AppLogic>().on_cancel_checksums({
+            let checksum_run = checksum_run.clone();
+            let window_weak = window.as_weak();
+            move || {
+                checksum_run.lock().cancel.store(true, Ordering::Relaxed);
+                if let Some(window) = window_weak.upgrade() {
+                    window.global::<AppLogic>().set_checksum_running(false);
+                }
+            }
+        });
+
+        let window_weak = window.as_weak();
+        spawn_mount_monitor(move |event| {
+            let searcher = searcher.clone();
+            let is_indexing = is_indexing.clone();
+            let last_query = last_query.clone();
+            let volumes = volumes.clone();
+            let catalogs = catalogs.clone();
+            let window_weak = window_weak.clone();
+
+            // Les volumes retirés disparaissent de l'index (ils restent visibles
+            // en "offline" s'ils ont été catalogués)
+            if let MountEvent::Unmounted(volume) = &event {
+                searcher.remove_root(&volume.mount_point);
+            }
+
+            slint::invoke_from_event_loop(move || {
+                if let Some(window) = window_weak.upgrade() {
+                    update_volumes(&window, &searcher, &volumes, &catalogs);
+                }
+
+                match event {
+                    MountEvent::Mounted(volume) => {
+                        let action_window = window_weak.clone();
+                        show_action_notification(
+                            &window_weak,
+                            &format!("mount-{}", volume.uuid),
+                            "Nouveau volume détecté",
+                            &format!("{} ({})", volume.display_name(), volume.mount_point),
+                            "info",
+                            "Indexer",
+                            move || index_volume(
+                                action_window.clone(),
+                                searcher.clone(),
+                                last_query.clone(),
+                                is_indexing.clone(),
+                                volume.clone()
+                            )
+                        );
+                    }
+                    MountEvent::Unmounted(volume) => {
+                        show_notification(
+                            &window_weak,
+                            &format!("mount-{}", volume.uuid),
+                            "Volume retiré",
+                            &format!("{} ({})", volume.display_name(), volume.mount_point),
+                            "warning"
+                        );
+
+                        let query = last_query.lock().0.clone();
+                        if query.len() >= 2 {
+                            run_search(window_weak.clone(), searcher, query);
+                        }
+                    }
+                }
+            }).unwrap();
+        });
+    }
+}
+
+fn index_volume(
+    window_weak: Weak<MainWindow>,
+    searcher: Arc<FileSearcher>,
+    last_query: Arc<Mutex<(String, Instant)>>,
+    is_indexing: Arc<AtomicBool>,
+    volume: VolumeInfo
+) {
+    if is_indexing.swap(true, Ordering::SeqCst) {
+        show_notification(
+            &window_weak,
+            "index-busy",
+            "Indexation en cours",
+            "Attendez la fin de l'indexation actuelle",
+            "warning"
+        );
+        return;
+    }
+    if let Some(window) = window_weak.upgrade() {
+        window.set_is_indexing(true);
+    }
+
+    thread::spawn(move || {
+        let is_indexing_thread = is_indexing.clone();
+        searcher.index_volume(&volume.mount_point, move || !is_indexing_thread.load(Ordering::SeqCst));
+        let whole_system: Vec<String> = searcher.profiles()
+            .into_iter()
+            .filter(|profile| profile.roots.is_empty())
+            .map(|profile| profile.name)
+            .collect();
+        save_indexes(&searcher, &whole_system);
+        is_indexing.store(false, Ordering::SeqCst);
+
+        let errors = searcher.index_errors();
+
+        slint::invoke_from_event_loop(move || {
+            if let Some(window) = window_weak.upgrade() {
+                window.set_is_indexing(false);
+                update_index_errors(&window, &errors);
+            }
+            show_notification(
+                &window_weak,
+                &format!("mount-{}", volume.uuid),
+                "Volume indexé",
+                &volume.display_name(),
+                "success"
+            );
+            // Les réindexations demandées entre-temps attendaient dans la file
+            start_indexing(window_weak, searcher, last_query, is_indexing);
+        }).unwrap();
+    });
+}
+
+// Indexe en arrière-plan le prochain profil en attente, l'index actuel du
+// profil reste utilisé jusqu'à la fin. Les profils suivants sont enchaînés.
+fn start_indexing(
+    window_weak: Weak<MainWindow>,
+    searcher: Arc<FileSearcher>,
+    last_query: Arc<Mutex<(String, Instant)>>,
+    is_indexing: Arc<AtomicBool>
+) {
+    if is_indexing.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let Some(profile) = searcher.next_queued() else {
+        is_indexing.store(false, Ordering::SeqCst);
+        return;
+    };
+    if let Some(window) = window_weak.upgrade() {
+        window.set_is_indexing(true);
+    }
+
+    let is_indexing_thread = is_indexing.clone();
+    thread::spawn(move || {
+        searcher.build_index(&profile, move || {
+            if !is_indexing_thread.load(Ordering::SeqCst) {
+                println!("⚡ Interruption immédiate de l'indexation");
+                return true;
+            }
+            false
+        });
+        if is_indexing.load(Ordering::SeqCst) {
+            save_indexes(&searcher, &[profile]);
+        }
+        
+        // Reset le flag à la fin quoi qu'il arrive
+        let cancelled = !is_indexing.swap(false, Ordering::SeqCst);
+        let errors = searcher.index_errors();
+        
+        slint::invoke_from_event_loop(move || {
+            if let Some(window) = window_weak.upgrade() {
+                window.set_is_indexing(false);
+                update_index_errors(&window, &errors);
+                update_last_indexed(&window, &searcher);
+            }
+            if !cancelled {
+                rerun_last_search(&window_weak, &searcher, &last_query);
+                if !errors.is_empty() {
+                    notify_index_errors(&window_weak, &errors);
+                }
+                start_indexing(window_weak, searcher, last_query, is_indexing);
+            }
+        }).unwrap();
+    });
+}
+
+// Sauvegarde les index pour `suptool search`, `--quick` et le fournisseur de
+// recherche D-Bus, qui ne partagent pas la mémoire de cette instance
+fn save_indexes(searcher: &FileSearcher, profiles: &[String]) {
+    for profile in profiles {
+        match searcher.save_index(profile) {
+            Ok(files) => println!("💾 Index {} sauvegardé ({} entrées)", profile, files),
+            // Profil jamais indexé : rien à sauvegarder
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => eprintln!("Index {} non sauvegardé: {}", profile, e),
+        }
+    }
+}
+
+// Met en file un profil (None = tous) puis lance l'indexation depuis la
+// boucle d'événements. Appelable depuis n'importe quel thread (API, planification) :
+// si une indexation est déjà en cours, elle vide la file en se terminant.
+fn queue_reindex(
+    window_weak: &Weak<MainWindow>,
+    searcher: &Arc<FileSearcher>,
+    last_query: &Arc<Mutex<(String, Instant)>>,
+    is_indexing: &Arc<AtomicBool>,
+    profile: Option<String>
+) {
+    match profile {
+        Some(profile) => searcher.queue_indexing(&profile),
+        None => searcher.profiles()
+            .iter()
+            .for_each(|profile| searcher.queue_indexing(&profile.name)),
+    }
+    let window_weak = window_weak.clone();
+    let searcher = searcher.clone();
+    let last_query = last_query.clone();
+    let is_indexing = is_indexing.clone();
+    slint::invoke_from_event_loop(move || {
+        start_indexing(window_weak, searcher, last_query, is_indexing);
+    }).unwrap();
+}
+
+fn start_http_api(
+    http_api: &Mutex<Option<HttpApi>>,
+    settings: &ApiSettings,
+    window_weak: &Weak<MainWindow>,
+    searcher: &Arc<FileSearcher>,
+    last_query: &Arc<Mutex<(String, Instant)>>,
+    is_indexing: &Arc<AtomicBool>
+) -> std::io::Result<()> {
+    let window_weak = window_weak.clone();
+    let searcher_clone = searcher.clone();
+    let last_query = last_query.clone();
+    let is_indexing_clone = is_indexing.clone();
+    let api = HttpApi::start(settings, searcher.clone(), is_indexing.clone(), move |profile| {
+        queue_reindex(&window_weak, &searcher_clone, &last_query, &is_indexing_clone, profile);
+    })?;
+    *http_api.lock() = Some(api);
+    Ok(())
+}
+
+fn update_profiles(window: &MainWindow, searcher: &FileSearcher) {
+    let profiles = searcher.profiles();
+    let scope = searcher.scope();
+
+    let items: Vec<IndexProfileItem> = profiles.iter().map(to_profile_item).collect();
+    let names: Vec<SharedString> = std::iter::once(SharedString::from("Tous les profils"))
+        .chain(profiles.iter().map(|profile| SharedString::from(profile.name.as_str())))
+        .collect();
+    let selected = scope
+        .and_then(|scope| profiles.iter().position(|profile| profile.name == scope))
+        .map_or(0, |i| i as i32 + 1);
+
+    let logic = window.global::<AppLogic>();
+    logic.set_index_profiles(ModelRc::new(VecModel::from(items)));
+    logic.set_index_scope_names(ModelRc::new(VecModel::from(names)));
+    logic.set_index_scope(selected);
+}
+
+fn to_profile_item(profile: &NamedProfile) -> IndexProfileItem {
+    let (daily_hour, daily_minute) = profile.schedule.daily_at.unwrap_or((3, 0));
+    IndexProfileItem {
+        name: profile.name.as_str().into(),
+        roots: profile.roots.join("\n").into(),
+        exclusions: profile.exclusions.join("\n").into(),
+        on_startup: profile.schedule.on_startup,
+        every_hours: profile.schedule.every_hours as i32,
+        daily: profile.schedule.daily_at.is_some(),
+        daily_hour: daily_hour as i32,
+        daily_minute: daily_minute as i32,
+    }
+}
+
+fn to_named_profile(item: &IndexProfileItem) -> NamedProfile {
+    let lines = |text: &SharedString| -> Vec<String> {
+        text.lines()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect()
+    };
+    NamedProfile {
+        name: item.name.to_string(),
+        roots: lines(&item.roots),
+        exclusions: lines(&item.exclusions),
+        schedule: IndexSchedule {
+            on_startup: item.on_startup,
+            every_hours: item.every_hours.max(0) as u32,
+            daily_at: item.daily.then_some((item.daily_hour.clamp(0, 23) as u32, item.daily_minute.clamp(0, 59) as u32)),
+        },
     }
 }
+
+fn update_last_indexed(window: &MainWindow, searcher: &FileSearcher) {
+    let text = match searcher.last_indexed() {
+        Some(time) => format!("Dernière indexation {} · {} fichiers", format_time(time), searcher.file_count()),
+        None => "Pas encore indexé".to_string(),
+    };
+    window.set_everysup_last_indexed(text.into());
+}
+
+fn update_index_errors(window: &MainWindow, errors: &[IndexError]) {
+    let rows: Vec<ModelRc<StandardListViewItem>> = errors.iter()
+        .map(|error| {
+            let row = [error.path.as_str(), error.kind.label(), error.reason.as_str()];
+            ModelRc::new(VecModel::from(
+                row.into_iter()
+                    .map(|s| StandardListViewItem::from(SharedString::from(s)))
+                    .collect::<Vec<_>>()
+            ))
+        })
+        .collect();
+
+    let logic = window.global::<AppLogic>();
+    logic.set_index_errors(ModelRc::new(VecModel::from(rows)));
+    logic.set_index_errors_summary(summarize(errors).into());
+    if errors.is_empty() {
+        logic.set_show_index_errors(false);
+    }
+}
+
+fn update_checksums(window: &MainWindow, checksums: &[Checksum], matched: Option<HashAlgorithm>) {
+    let items: Vec<ChecksumItem> = checksums.iter()
+        .map(|checksum| ChecksumItem {
+            algorithm: checksum.algorithm.label().into(),
+            value: checksum.value.as_str().into(),
+            matched: matched == Some(checksum.algorithm),
+        })
+        .collect();
+    window.global::<AppLogic>().set_checksums(ModelRc::new(VecModel::from(items)));
+}
+
+fn notify_index_errors(window_weak: &Weak<MainWindow>, errors: &[IndexError]) {
+    let action_window = window_weak.clone();
+    show_action_notification(
+        window_weak,
+        "index-errors",
+        "Indexation terminée",
+        &summarize(errors),
+        "warning",
+        "Voir",
+        move || {
+            if let Some(window) = action_window.upgrade() {
+                window.global::<AppLogic>().set_show_index_errors(true);
+            }
+        }
+    );
+}
+
+fn run_saved_search(
+    window_weak: &Weak<MainWindow>,
+    searcher: &Arc<FileSearcher>,
+    history: &Arc<Mutex<SearchHistory>>,
+    last_query: &Mutex<(String, Instant)>,
+    query: String
+) {
+    // La requête est posée dans la barre sans repasser par le debounce de la saisie
+    *last_query.lock() = (query.clone(), Instant::now());
+    if let Some(window) = window_weak.upgrade() {
+        window.set_everysup_query(query.clone().into());
+        window.set_current_page("everysup".into());
+    }
+    run_search(window_weak.clone(), searcher.clone(), query.clone());
+    record_search(window_weak, history, query);
+}
+
+// Appelée sur le fil de l'interface : le nombre de résultats est celui de la
+// recherche affichée, qui peut encore être en cours
+fn record_search(window_weak: &Weak<MainWindow>, history: &Arc<Mutex<SearchHistory>>, query: String) {
+    if query.trim().len() < 2 {
+        return;
+    }
+
+    let generation = SEARCH_GENERATION.load(Ordering::SeqCst);
+    let hits = DISPLAYED_SEARCH.with(|displayed| match &*displayed.borrow() {
+        Some((displayed_generation, displayed_query, hits))
+            if *displayed_generation == generation && *displayed_query == query => Some(*hits),
+        _ => None,
+    });
+    match hits {
+        Some(hits) => add_to_history(window_weak, history, &query, hits),
+        None => PENDING_RECORD.with(|pending| *pending.borrow_mut() = Some((generation, query, history.clone()))),
+    }
+}
+
+fn add_to_history(window_weak: &Weak<MainWindow>, history: &Mutex<SearchHistory>, query: &str, hits: usize) {
+    let mut history = history.lock();
+    history.record(query, hits);
+    save_history(window_weak, &history);
+    if let Some(window) = window_weak.upgrade() {
+        update_history(&window, &history);
+    }
+}
+
+fn save_history(window_weak: &Weak<MainWindow>, history: &SearchHistory) {
+    if let Err(e) = history.save() {
+        show_notification(
+            window_weak,
+            "history-error",
+            "Erreur de sauvegarde",
+            &e.to_string(),
+            "error"
+        );
+    }
+}
+
+fn update_history(window: &MainWindow, history: &SearchHistory) {
+    let to_item = |search: &SavedSearch| SavedSearchItem {
+        name: search.name.clone().into(),
+        query: search.to_query().into(),
+        pinned: search.pinned,
+    };
+
+    let saved: Vec<SavedSearchItem> = history.saved.iter().map(to_item).collect();
+    let pinned: Vec<SavedSearchItem> = history.pinned().into_iter().map(to_item).collect();
+    let entries: Vec<HistoryItem> = history.entries.iter()
+        .map(|entry| HistoryItem {
+            query: entry.query.clone().into(),
+            time: format_time(entry.timestamp).into(),
+            // La recherche s'arrête à MAX_RESULTS résultats
+            hits: if entry.hits >= MAX_RESULTS { format!("{}+", MAX_RESULTS) } else { entry.hits.to_string() }.into(),
+        })
+        .collect();
+
+    let logic = window.global::<AppLogic>();
+    logic.set_saved_searches(ModelRc::new(VecModel::from(saved)));
+    logic.set_pinned_searches(ModelRc::new(VecModel::from(pinned)));
+    logic.set_search_history(ModelRc::new(VecModel::from(entries)));
+}
+
+fn to_file_preview(path: &str, preview: std::io::Result<PreviewData>) -> FilePreview {
+    let title = Path::new(path).file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string());
+
+    let preview = match preview {
+        Ok(preview) => preview,
+        Err(e) => {
+            return FilePreview {
+                kind: "none".into(),
+                title: title.into(),
+                message: e.to_string().into(),
+                ..Default::default()
+            };
+        }
+    };
+
+    let field = |label: &str, value: String| PreviewField {
+        label: label.into(),
+        value: value.into(),
+    };
+    let time = |time: Option<SystemTime>| time.map(format_time).unwrap_or_else(|| "-".to_string());
+
+    let details = &preview.details;
+    let metadata = vec![
+        field("Chemin", details.path.to_string_lossy().to_string()),
+        field("Taille", format!("{} octets", details.size)),
+        field("Création", time(details.created)),
+        field("Modification", time(details.modified)),
+        field("Dernier accès", time(details.accessed)),
+        field("Droits", details.permissions.clone()),
+        field("Propriétaire", details.owner.clone()),
+    ];
+
+    let annotations = file_tags(path).unwrap_or_default();
+    let mut result = FilePreview {
+        title: title.into(),
+        path: path.into(),
+        tags: annotations.tags.join(", ").into(),
+        note: annotations.note.into(),
+        metadata: ModelRc::new(VecModel::from(metadata)),
+        ..Default::default()
+    };
+
+    match preview.content {
+        PreviewContent::Text { lines, truncated } => {
+            let lines: Vec<PreviewLine> = lines.into_iter()
+                .map(|line| PreviewLine {
+                    spans: ModelRc::new(VecModel::from(
+                        line.into_iter()
+                            .map(|(text, kind)| PreviewSpan {
+                                text: text.into(),
+                                color: token_color(kind),
+                            })
+                            .collect::<Vec<_>>()
+                    )),
+                })
+                .collect();
+
+            result.kind = "text".into();
+            result.lines = ModelRc::new(VecModel::from(lines));
+            if truncated {
+                result.message = "Aperçu limité aux 64 premiers Ko".into();
+            }
+        }
+        PreviewContent::Image { width, height, rgba } => {
+            let buffer = SharedPixelBuffer::<Rgba8Pixel>::clone_from_slice(&rgba, width, height);
+            result.kind = "image".into();
+            result.image = Image::from_rgba8(buffer);
+            result.message = format!("{} x {}", width, height).into();
+        }
+        PreviewContent::Archive(members) => {
+            let count = members.len();
+            let entries: Vec<PreviewField> = members.into_iter()
+                .map(|member| field(
+                    &member.path,
+                    if member.is_dir { String::new() } else { format_size(member.size).to_string() }
+                ))
+                .collect();
+
+            result.kind = "archive".into();
+            result.entries = ModelRc::new(VecModel::from(entries));
+            result.message = format!("{} entrées", count).into();
+        }
+        PreviewContent::Member { archive } => {
+            result.kind = "member".into();
+            result.message = format!("Contenu dans {}", archive).into();
+        }
+        PreviewContent::Unavailable(reason) => {
+            result.kind = "none".into();
+            result.message = reason.into();
+        }
+    }
+
+    result
+}
+
+fn token_color(kind: TokenKind) -> Color {
+    match kind {
+        TokenKind::Plain => Color::from_rgb_u8(0xd4, 0xd4, 0xd4),
+        TokenKind::Keyword => Color::from_rgb_u8(0xf3, 0x9f, 0x21),
+        TokenKind::String => Color::from_rgb_u8(0xce, 0x91, 0x78),
+        TokenKind::Number => Color::from_rgb_u8(0xb5, 0xce, 0xa8),
+        TokenKind::Comment => Color::from_rgb_u8(0x6a, 0x99, 0x55),
+    }
+}
+
+fn update_volumes(
+    window: &MainWindow,
+    searcher: &FileSearcher,
+    volumes: &Mutex<Vec<VolumeInfo>>,
+    catalogs: &Mutex<Vec<VolumeInfo>>
+) {
+    let mounted = searcher.mounted_volumes();
+    let catalogued = searcher.catalogued_volumes();
+
+    let volume_names: Vec<SharedString> = mounted.iter()
+        .map(|v| SharedString::from(format!("{} ({})", v.display_name(), v.mount_point)))
+        .collect();
+    let catalog_names: Vec<SharedString> = catalogued.iter()
+        .map(|v| {
+            let status = if mounted.iter().any(|m| m.uuid == v.uuid) { "connecté" } else { "hors ligne" };
+            SharedString::from(format!("{} [{}]", v.display_name(), status))
+        })
+        .collect();
+
+    window.set_everysup_volumes(ModelRc::new(VecModel::from(volume_names)));
+    window.set_everysup_catalogs(ModelRc::new(VecModel::from(catalog_names)));
+    *volumes.lock() = mounted;
+    *catalogs.lock() = catalogued;
+}
+
+fn to_result_row(hit: SearchMatch, columns: &ColumnSettings) -> ResultRow {
+    let spans = |fragments: Vec<(String, bool)>| ModelRc::new(VecModel::from(
+        fragments.into_iter()
+            .map(|(text, highlighted)| TextSpan { text: text.into(), highlighted })
+            .collect::<Vec<_>>()
+    ));
+    let plain = |text: String| spans(vec![(text, false)]);
+
+    let result = &hit.result;
+    let attributes = result.attributes.as_deref();
+    // Valeur absente : index construit sans cette colonne, ou catalogue hors ligne
+    let missing = || "-".to_string();
+
+    let cells: Vec<ResultCell> = columns.visible.iter()
+        .map(|&column| {
+            // La largeur de la colonne détermine où les textes longs sont élidés
+            let chars = (columns.width(column) / CHAR_WIDTH) as usize;
+
+            match column {
+                Column::Name => ResultCell {
+                    spans: spans(elide_spans(&result.name, &hit.name_spans, chars)),
+                    folder: ModelRc::default(),
+                },
+                Column::Path => {
+                    let folder_len = Path::new(&result.path).parent()
+                        .map(|parent| parent.as_os_str().len())
+                        .unwrap_or(0);
+                    let folder_spans: Vec<_> = hit.path_spans.iter()
+                        .filter(|span| span.start < folder_len)
+                        .map(|span| span.start..span.end.min(folder_len))
+                        .collect();
+
+                    ResultCell {
+                        spans: spans(elide_spans(&result.path, &hit.path_spans, chars)),
+                        folder: spans(elide_spans(&result.path[..folder_len], &folder_spans, chars)),
+                    }
+                }
+                Column::LinkTarget => ResultCell {
+                    spans: spans(elide_spans(
+                        result.link_target.as_deref().unwrap_or("-"),
+                        &[],
+                        chars
+                    )),
+                    folder: ModelRc::default(),
+                },
+                _ => {
+                    let text = match column {
+                        Column::Size => format_size(result.size).to_string(),
+                        Column::Type => if result.archive.is_some() {
+                            "Dans une archive".into()
+                        } else if result.is_dir {
+                            "Dossier".into()
+                        } else {
+                            "Fichier".into()
+                        },
+                        Column::Modified => format_time(result.modified),
+                        Column::Volume => if result.offline {
+                            format!("{} (hors ligne)", result.volume)
+                        } else {
+                            result.volume.clone()
+                        },
+                        Column::Extension => extension_of(&result.name).unwrap_or_default(),
+                        Column::Created => attributes.and_then(|a| a.created).map(format_time).unwrap_or_else(missing),
+                        Column::Accessed => attributes.and_then(|a| a.accessed).map(format_time).unwrap_or_else(missing),
+                        Column::Permissions => attributes.map(|a| a.permissions.clone()).unwrap_or_else(missing),
+                        Column::Owner => attributes.map(|a| a.owner.clone()).unwrap_or_else(missing),
+                        Column::Inode => attributes.and_then(|a| a.inode).map(|i| i.to_string()).unwrap_or_else(missing),
+                        Column::Links => attributes.and_then(|a| a.links).map(|l| l.to_string()).unwrap_or_else(missing),
+                        Column::Hidden => attributes.map(|a| if a.hidden { "Oui" } else { "Non" }.to_string()).unwrap_or_else(missing),
+                        Column::Tags => file_tags(&result.path).map(|t| t.tags.join(", ")).unwrap_or_default(),
+                        Column::Name | Column::Path | Column::LinkTarget => unreachable!(),
+                    };
+                    ResultCell {
+                        spans: plain(text),
+                        folder: ModelRc::default(),
+                    }
+                }
+            }
+        })
+        .collect();
+
+    ResultRow {
+        cells: ModelRc::new(VecModel::from(cells)),
+        full_path: result.path.clone().into(),
+        ..Default::default()
+    }
+}
+
+fn rerun_last_search(
+    window_weak: &Weak<MainWindow>,
+    searcher: &Arc<FileSearcher>,
+    last_query: &Mutex<(String, Instant)>
+) {
+    let query = last_query.lock().0.clone();
+    if query.len() >= 2 {
+        run_search(window_weak.clone(), searcher.clone(), query);
+    }
+}
+
+fn apply_column_settings(
+    window_weak: &Weak<MainWindow>,
+    searcher: &Arc<FileSearcher>,
+    last_query: &Mutex<(String, Instant)>,
+    result: std::io::Result<ColumnSettings>
+) {
+    match result {
+        Ok(columns) => {
+            if let Some(window) = window_weak.upgrade() {
+                update_columns(&window, &columns);
+            }
+            rerun_last_search(window_weak, searcher, last_query);
+        }
+        Err(e) => show_notification(
+            window_weak,
+            "columns-error",
+            "Erreur de sauvegarde",
+            &e.to_string(),
+            "error"
+        ),
+    }
+}
+
+fn update_columns(window: &MainWindow, columns: &ColumnSettings) {
+    let visible: Vec<ResultColumn> = columns.visible.iter()
+        .map(|&column| ResultColumn {
+            id: column.id().into(),
+            title: column.title().into(),
+            width: columns.width(column),
+        })
+        .collect();
+    let choices: Vec<ColumnChoice> = Column::ALL.iter()
+        .map(|&column| ColumnChoice {
+            title: column.title().into(),
+            visible: columns.is_visible(column),
+            locked: column == Column::Name,
+        })
+        .collect();
+
+    let logic = window.global::<AppLogic>();
+    logic.set_result_columns(ModelRc::new(VecModel::from(visible)));
+    logic.set_column_choices(ModelRc::new(VecModel::from(choices)));
+}
+
+fn run_search(window_weak: Weak<MainWindow>, searcher: Arc<FileSearcher>, query: String) {
+    let generation = SEARCH_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+
+    std::thread::spawn(move || {
+        let results = searcher.search(&query);
+        let suggestions: Vec<SharedString> = searcher.suggest(&query)
+            .into_iter()
+            .map(SharedString::from)
+            .collect();
+        let did_you_mean = if results.is_empty() {
+            searcher.did_you_mean(&query).unwrap_or_default()
+        } else {
+            String::new()
+        };
+
+        slint::invoke_from_event_loop(move || {
+            if SEARCH_GENERATION.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            DISPLAYED_SEARCH.with(|displayed| *displayed.borrow_mut() = Some((generation, query.clone(), results.len())));
+            let pending = PENDING_RECORD.with(|pending| {
+                let mut pending = pending.borrow_mut();
+                pending.as_ref()
+                    .is_some_and(|(pending_generation, pending_query, _)| *pending_generation == generation && *pending_query == query)
+                    .then(|| pending.take())
+                    .flatten()
+            });
+            if let Some((_, _, history)) = pending {
+                add_to_history(&window_weak, &history, &query, results.len());
+            }
+
+            if let Some(window) = window_weak.upgrade() {
+                window.set_everysup_suggestions(ModelRc::new(VecModel::from(suggestions)));
+                window.set_everysup_did_you_mean(did_you_mean.into());
+
+                let columns = column_settings();
+                let group_by = GroupBy::from_index(window.get_everysup_group_by());
+                let mut rows: Vec<ResultRow> = Vec::new();
+
+                for group in group_results(results, group_by) {
+                    if group_by != GroupBy::None {
+                        let collapsed = COLLAPSED_GROUPS.with(|groups| groups.borrow().contains(&group.label));
+                        rows.push(ResultRow {
+                            group: true,
+                            collapsed,
+                            summary: format!("{} éléments · {}", group.items.len(), format_size(group.total_size)).into(),
+                            label: group.label.into(),
+                            ..Default::default()
+                        });
+                        if collapsed {
+                            continue;
+                        }
+                    }
+                    rows.extend(group.items.into_iter().map(|hit| to_result_row(hit, &columns)));
+                }
+                window.set_everysup_files(ModelRc::new(VecModel::from(rows)));
+            }
+        }).unwrap();
+    });
+}
@@ -0,0 +1,454 @@
+use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone};
+use flate2::read::GzDecoder;
+use once_cell::sync::Lazy;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Clone)]
+pub struct ArchiveMember {
+    pub path: String,
+    pub size: u64,
+    pub is_dir: bool,
+    pub modified: Option<SystemTime>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+    // Lu via l'outil 7z installé sur le système
+    SevenZip,
+}
+
+impl ArchiveFormat {
+    pub fn detect(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else if name.ends_with(".7z") {
+            Some(Self::SevenZip)
+        } else if [".zip", ".jar", ".apk", ".docx", ".xlsx", ".pptx", ".odt", ".epub"]
+            .iter()
+            .any(|ext| name.ends_with(ext))
+        {
+            Some(Self::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+// Archives dont les membres sont indexés : pas les documents bureautiques,
+// qui sont des zip mais dont le contenu n'intéresse personne
+pub fn is_indexable_archive(path: &Path) -> bool {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default();
+    [".zip", ".tar", ".tar.gz", ".tgz", ".7z"].iter().any(|ext| name.ends_with(ext))
+}
+
+// Liste au plus `limit` membres, le reste de l'archive n'est pas parcouru
+pub fn list_archive(path: &Path, limit: usize) -> io::Result<Vec<ArchiveMember>> {
+    let format = ArchiveFormat::detect(path)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Format d'archive non pris en charge"))?;
+    if format == ArchiveFormat::SevenZip {
+        return list_7z(path, limit);
+    }
+    let file = BufReader::new(File::open(path)?);
+
+    match format {
+        ArchiveFormat::Zip => list_zip(file, limit),
+        ArchiveFormat::Tar => list_tar(file, limit),
+        ArchiveFormat::TarGz => list_tar(GzDecoder::new(file), limit),
+        ArchiveFormat::SevenZip => unreachable!(),
+    }
+}
+
+// Découpe un chemin virtuel `backup.zip/docs/plan.odt` en archive + membre.
+// Renvoie None si le chemin existe sur le disque ou ne traverse aucune archive.
+pub fn split_member_path(path: &Path) -> Option<(PathBuf, String)> {
+    if path.exists() {
+        return None;
+    }
+
+    let archive = path.ancestors()
+        .skip(1)
+        .find(|ancestor| is_indexable_archive(ancestor) && ancestor.is_file())?;
+    let member = path.strip_prefix(archive).ok()?;
+    let member = member.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    Some((archive.to_path_buf(), member))
+}
+
+// Cherche un membre précis (parcours complet de l'archive si besoin)
+pub fn find_member(archive: &Path, member: &str) -> io::Result<ArchiveMember> {
+    list_archive(archive, usize::MAX)?
+        .into_iter()
+        .find(|entry| entry.path.trim_end_matches('/') == member)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Membre absent de l'archive"))
+}
+
+// Extrait un membre (fichier ou dossier) dans `dest_dir` et renvoie le chemin créé
+pub fn extract_member(archive: &Path, member: &str, dest_dir: &Path) -> io::Result<PathBuf> {
+    let format = ArchiveFormat::detect(archive)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Format d'archive non pris en charge"))?;
+    let member = member.trim_end_matches('/');
+    let name = Path::new(member).file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Chemin de membre invalide"))?;
+    let target = dest_dir.join(name);
+    // Jamais d'écrasement : ni d'un fichier existant, ni à travers un lien déjà là
+    if target.symlink_metadata().is_ok() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} existe déjà", target.display())));
+    }
+
+    let extracted = match format {
+        ArchiveFormat::Zip => extract_zip(archive, member, &target)?,
+        ArchiveFormat::Tar => extract_tar(File::open(archive)?, member, &target)?,
+        ArchiveFormat::TarGz => extract_tar(GzDecoder::new(File::open(archive)?), member, &target)?,
+        ArchiveFormat::SevenZip => extract_7z(archive, member, dest_dir)?,
+    };
+
+    if extracted == 0 {
+        return Err(io::Error::new(io::ErrorKind::NotFound, "Membre absent de l'archive"));
+    }
+    Ok(target)
+}
+
+fn list_zip(file: BufReader<File>, limit: usize) -> io::Result<Vec<ArchiveMember>> {
+    let mut archive = zip::ZipArchive::new(file).map_err(io::Error::other)?;
+    let mut members = Vec::new();
+
+    for i in 0..archive.len().min(limit) {
+        let entry = archive.by_index_raw(i).map_err(io::Error::other)?;
+        members.push(ArchiveMember {
+            path: entry.name().to_string(),
+            size: entry.size(),
+            is_dir: entry.is_dir(),
+            modified: zip_time(entry.last_modified()),
+        });
+    }
+
+    Ok(members)
+}
+
+fn list_tar<R: Read>(reader: R, limit: usize) -> io::Result<Vec<ArchiveMember>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut members = Vec::new();
+
+    for entry in archive.entries()?.take(limit) {
+        let entry = entry?;
+        members.push(ArchiveMember {
+            path: entry.path()?.to_string_lossy().to_string(),
+            size: entry.size(),
+            is_dir: entry.header().entry_type().is_dir(),
+            modified: entry.header().mtime().ok().map(|secs| UNIX_EPOCH + Duration::from_secs(secs)),
+        });
+    }
+
+    Ok(members)
+}
+
+// `7z l -slt` : un bloc "Clé = valeur" par membre, après la ligne "----------"
+fn list_7z(path: &Path, limit: usize) -> io::Result<Vec<ArchiveMember>> {
+    let output = Command::new(seven_zip_command()?)
+        .arg("l")
+        .arg("-slt")
+        .arg(path)
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout).replace("\r\n", "\n");
+    let listing = stdout.split_once("\n----------").map(|(_, rest)| rest).unwrap_or("");
+    let mut members = Vec::new();
+
+    for block in listing.split("\n\n").filter(|block| !block.trim().is_empty()) {
+        if members.len() >= limit {
+            break;
+        }
+
+        let mut member = ArchiveMember { path: String::new(), size: 0, is_dir: false, modified: None };
+        for line in block.lines() {
+            let Some((key, value)) = line.split_once(" = ") else { continue };
+            match key.trim() {
+                "Path" => member.path = value.replace('\\', "/"),
+                "Size" => member.size = value.parse().unwrap_or(0),
+                "Folder" => member.is_dir = value == "+",
+                "Attributes" => member.is_dir |= value.starts_with('D'),
+                // "2024-03-01 10:42:17" (parfois suivi de fractions de seconde)
+                "Modified" => {
+                    let value = value.get(..19).unwrap_or(value);
+                    member.modified = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+                        .ok()
+                        .and_then(local_time);
+                }
+                _ => {}
+            }
+        }
+
+        if !member.path.is_empty() {
+            members.push(member);
+        }
+    }
+
+    Ok(members)
+}
+
+fn extract_zip(archive: &Path, member: &str, target: &Path) -> io::Result<usize> {
+    let mut archive = zip::ZipArchive::new(BufReader::new(File::open(archive)?)).map_err(io::Error::other)?;
+    let prefix = format!("{}/", member);
+    let mut extracted = 0;
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(io::Error::other)?;
+        let name = entry.name().to_string();
+        let Some(relative) = member_relative(&name, member, &prefix) else { continue };
+        let output = target.join(relative);
+
+        if entry.is_dir() {
+            fs::create_dir_all(&output)?;
+        } else {
+            if let Some(parent) = output.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            io::copy(&mut entry, &mut create_new(&output)?)?;
+        }
+        extracted += 1;
+    }
+
+    Ok(extracted)
+}
+
+fn extract_tar<R: Read>(reader: R, member: &str, target: &Path) -> io::Result<usize> {
+    let mut archive = tar::Archive::new(reader);
+    let prefix = format!("{}/", member);
+    let mut extracted = 0;
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().to_string();
+        let Some(relative) = member_relative(&name, member, &prefix) else { continue };
+        let output = target.join(relative);
+
+        // Les liens symboliques et durs sont ignorés : une archive piégée pourrait
+        // sinon faire écrire ses membres suivants hors du dossier choisi
+        match entry.header().entry_type() {
+            tar::EntryType::Directory => fs::create_dir_all(&output)?,
+            tar::EntryType::Regular | tar::EntryType::Continuous => {
+                if let Some(parent) = output.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                let mut file = create_new(&output)?;
+                io::copy(&mut entry, &mut file)?;
+                #[cfg(unix)]
+                if let Ok(mode) = entry.header().mode() {
+                    use std::os::unix::fs::PermissionsExt;
+                    file.set_permissions(fs::Permissions::from_mode(mode & 0o777))?;
+                }
+            }
+            _ => continue,
+        }
+        extracted += 1;
+    }
+
+    Ok(extracted)
+}
+
+// Ne suit pas un lien qui se trouverait déjà à la place du fichier
+fn create_new(path: &Path) -> io::Result<File> {
+    fs::OpenOptions::new().write(true).create_new(true).open(path)
+}
+
+fn extract_7z(archive: &Path, member: &str, dest_dir: &Path) -> io::Result<usize> {
+    // `x` recrée l'arborescence : on extrait dans un dossier temporaire
+    // puis on déplace le membre demandé dans le dossier choisi
+    let staging = dest_dir.join(format!(".suptool-extract-{}", std::process::id()));
+    let output = Command::new(seven_zip_command()?)
+        .arg("x")
+        .arg("-y")
+        .arg(format!("-o{}", staging.display()))
+        .arg(archive)
+        .arg(member)
+        .output()?;
+
+    let result = if !output.status.success() {
+        Err(io::Error::other(String::from_utf8_lossy(&output.stderr).trim().to_string()))
+    } else {
+        let source = staging.join(member);
+        let name = source.file_name().unwrap_or_default().to_owned();
+        if source.exists() {
+            fs::rename(&source, dest_dir.join(name)).map(|_| 1)
+        } else {
+            Ok(0)
+        }
+    };
+
+    let _ = fs::remove_dir_all(&staging);
+    result
+}
+
+// Chemin relatif du membre `name` sous `member`, sans composant qui sorte du
+// dossier de destination (`..`, chemins absolus, lecteurs `C:`). Les archives
+// créées sous Windows utilisent parfois `\` : la règle est la même sur tous
+// les systèmes.
+fn member_relative(name: &str, member: &str, prefix: &str) -> Option<PathBuf> {
+    let name = name.trim_end_matches('/');
+    let relative = if name == member {
+        ""
+    } else {
+        name.strip_prefix(prefix)?
+    };
+
+    let relative = relative.replace('\\', "/");
+    if relative.starts_with('/') {
+        return None;
+    }
+    let mut path = PathBuf::new();
+    for part in relative.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => return None,
+            part if part.contains(':') => return None,
+            part => path.push(part),
+        }
+    }
+    Some(path)
+}
+
+// Recherché une seule fois : l'indexation peut croiser beaucoup d'archives 7z
+static SEVEN_ZIP: Lazy<Option<PathBuf>> = Lazy::new(|| {
+    ["7z", "7zz", "7za"].iter()
+        .map(PathBuf::from)
+        .chain(cfg!(windows).then(|| PathBuf::from(r"C:\Program Files\7-Zip\7z.exe")))
+        .find(|candidate| Command::new(candidate).arg("i").output().is_ok_and(|o| o.status.success()))
+});
+
+fn seven_zip_command() -> io::Result<PathBuf> {
+    SEVEN_ZIP.clone()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "7z introuvable : installez 7-Zip (7z, 7zz ou 7za) pour ouvrir les archives .7z"))
+}
+
+// Vrai pour une archive 7z alors que 7-Zip n'est pas installé
+pub fn seven_zip_missing(archive: &Path) -> bool {
+    ArchiveFormat::detect(archive) == Some(ArchiveFormat::SevenZip) && SEVEN_ZIP.is_none()
+}
+
+// Les dates zip (MS-DOS) sont en heure locale
+fn zip_time(time: zip::DateTime) -> Option<SystemTime> {
+    NaiveDate::from_ymd_opt(time.year() as i32, time.month() as u32, time.day() as u32)?
+        .and_hms_opt(time.hour() as u32, time.minute() as u32, time.second() as u32)
+        .and_then(local_time)
+}
+
+fn local_time(datetime: NaiveDateTime) -> Option<SystemTime> {
+    Local.from_local_datetime(&datetime).earliest().map(SystemTime::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relative(name: &str) -> Option<PathBuf> {
+        member_relative(name, "docs", "docs/")
+    }
+
+    fn malicious_tar(outside: &Path) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut link = |name: &str, kind: tar::EntryType| {
+            let mut header = tar::Header::new_gnu();
+            header.set_entry_type(kind);
+            header.set_size(0);
+            header.set_mode(0o777);
+            builder.append_link(&mut header, name, outside).unwrap();
+        };
+        link("docs/lien", tar::EntryType::Symlink);
+        link("docs/dur", tar::EntryType::Link);
+
+        let content = "écrit par l'archive".as_bytes();
+        for name in ["docs/lien/piège.txt", "docs/été.txt"] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            builder.append_data(&mut header, name, content).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn extract_tar_ne_suit_pas_les_liens_de_l_archive() {
+        let root = std::env::temp_dir().join(format!("suptool-archive-liens-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let outside = root.join("dehors");
+        fs::create_dir_all(&outside).unwrap();
+        let target = root.join("docs");
+
+        let extracted = extract_tar(io::Cursor::new(malicious_tar(&outside)), "docs", &target).unwrap();
+
+        assert_eq!(extracted, 2);
+        assert!(!outside.join("piège.txt").exists());
+        assert!(target.join("lien").symlink_metadata().unwrap().is_dir());
+        assert!(target.join("dur").symlink_metadata().is_err());
+        assert_eq!(fs::read_to_string(target.join("été.txt")).unwrap(), "écrit par l'archive");
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn extract_member_refuse_d_ecraser_la_destination() {
+        let root = std::env::temp_dir().join(format!("suptool-archive-existe-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let archive = root.join("piège.tar");
+        fs::write(&archive, malicious_tar(&root)).unwrap();
+        fs::write(root.join("docs"), "déjà là").unwrap();
+
+        let error = extract_member(&archive, "docs", &root).err().unwrap();
+
+        assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(fs::read_to_string(root.join("docs")).unwrap(), "déjà là");
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn member_relative_garde_les_chemins_sous_le_membre() {
+        assert_eq!(relative("docs"), Some(PathBuf::new()));
+        assert_eq!(relative("docs/"), Some(PathBuf::new()));
+        assert_eq!(relative("docs/été/notes.txt"), Some(Path::new("été").join("notes.txt")));
+        assert_eq!(relative("docs/./a.txt"), Some(PathBuf::from("a.txt")));
+        assert_eq!(relative("docs2/a.txt"), None);
+    }
+
+    #[test]
+    fn member_relative_refuse_les_remontees() {
+        assert_eq!(relative("docs/../secret"), None);
+        assert_eq!(relative("docs/a/../../b"), None);
+        assert_eq!(relative("docs/..\\..\\b"), None);
+    }
+
+    #[test]
+    fn member_relative_refuse_les_chemins_absolus() {
+        assert_eq!(relative("docs//etc/passwd"), None);
+        assert_eq!(relative("docs/\\Windows\\win.ini"), None);
+    }
+
+    #[test]
+    fn member_relative_refuse_les_lecteurs_windows() {
+        assert_eq!(relative("docs/C:/Windows/win.ini"), None);
+        assert_eq!(relative("docs/C:\\Windows\\win.ini"), None);
+        assert_eq!(relative("docs/c:win.ini"), None);
+    }
+
+    #[test]
+    fn member_relative_accepte_les_separateurs_windows() {
+        assert_eq!(relative("docs/sous\\fichier.txt"), Some(Path::new("sous").join("fichier.txt")));
+    }
+}
@@ -0,0 +1,302 @@
+use crate::utils::csv_field;
+use serde::{Serialize, Deserialize};
+use std::io;
+
+// Dossiers où des binaires setuid/setgid sont attendus (sudo, passwd, ping...)
+#[cfg(unix)]
+const SYSTEM_BINARY_DIRS: &[&str] = &["/bin", "/sbin", "/usr/bin", "/usr/sbin", "/usr/lib", "/usr/libexec", "/snap"];
+// Extensions de fichiers qui contiennent en général une clé privée ou un certificat
+#[cfg(unix)]
+const SECRET_EXTENSIONS: &[&str] = &["pem", "key", "p12", "pfx", "ppk"];
+#[cfg(unix)]
+const SECRET_NAMES: &[&str] = &[".netrc", ".pgpass", ".git-credentials", ".pypirc"];
+#[cfg(unix)]
+const SSH_KEY_PREFIXES: &[&str] = &["id_rsa", "id_dsa", "id_ecdsa", "id_ed25519"];
+
+// Du plus grave au moins grave : l'ordre sert au tri des résultats
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+impl Severity {
+    pub const ALL: [Severity; 4] = [Severity::Critical, Severity::High, Severity::Medium, Severity::Low];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Critical => "critique",
+            Severity::High => "haute",
+            Severity::Medium => "moyenne",
+            Severity::Low => "basse",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditIssue {
+    WorldWritableDir,
+    WorldWritableFile,
+    Setuid,
+    Setgid,
+    UnknownOwner,
+    UnknownGroup,
+    ExposedSecret,
+}
+
+impl AuditIssue {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AuditIssue::WorldWritableDir => "dossier modifiable par tous",
+            AuditIssue::WorldWritableFile => "fichier modifiable par tous",
+            AuditIssue::Setuid => "exécutable setuid",
+            AuditIssue::Setgid => "exécutable setgid",
+            AuditIssue::UnknownOwner => "propriétaire inconnu",
+            AuditIssue::UnknownGroup => "groupe inconnu",
+            AuditIssue::ExposedSecret => "secret exposé",
+        }
+    }
+
+    pub fn explanation(&self) -> &'static str {
+        match self {
+            AuditIssue::WorldWritableDir => "N'importe quel utilisateur peut créer, renommer ou supprimer des fichiers ici (pas de sticky bit)",
+            AuditIssue::WorldWritableFile => "N'importe quel utilisateur peut modifier ce fichier",
+            AuditIssue::Setuid => "S'exécute avec les droits de son propriétaire, quel que soit l'utilisateur qui le lance",
+            AuditIssue::Setgid => "S'exécute avec les droits de son groupe, quel que soit l'utilisateur qui le lance",
+            AuditIssue::UnknownOwner => "L'UID du propriétaire n'a pas de compte sur ce système (utilisateur supprimé ou disque d'une autre machine)",
+            AuditIssue::UnknownGroup => "Le GID du groupe n'existe pas sur ce système",
+            AuditIssue::ExposedSecret => "Clé, identifiants ou fichier .env lisible ou modifiable par d'autres utilisateurs",
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AuditFinding {
+    pub severity: Severity,
+    pub issue: AuditIssue,
+    pub path: String,
+    // Mode octal, ex: "4755"
+    pub mode: String,
+    pub owner: String,
+}
+
+pub struct AuditReport {
+    pub findings: Vec<AuditFinding>,
+    // Entrées examinées sur le disque
+    pub checked: usize,
+}
+
+// Examine chaque chemin sur le disque (sans suivre les liens) et renvoie les
+// findings triés par gravité puis par chemin
+#[cfg(unix)]
+pub fn audit_paths(paths: Vec<String>) -> io::Result<AuditReport> {
+    use rayon::prelude::*;
+
+    let checked = paths.len();
+    let mut findings: Vec<AuditFinding> = paths.par_iter()
+        .flat_map_iter(|path| unix::check_path(path))
+        .collect();
+
+    findings.sort_by(|a, b| a.severity.cmp(&b.severity).then_with(|| a.path.cmp(&b.path)));
+    Ok(AuditReport { findings, checked })
+}
+
+#[cfg(windows)]
+pub fn audit_paths(_paths: Vec<String>) -> io::Result<AuditReport> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "L'audit de sécurité repose sur les permissions Unix"))
+}
+
+pub fn findings_to_csv(findings: &[AuditFinding]) -> String {
+    let mut csv = String::from("gravité,problème,chemin,mode,propriétaire,explication\n");
+    for finding in findings {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            finding.severity.label(),
+            finding.issue.label(),
+            csv_field(&finding.path),
+            finding.mode,
+            csv_field(&finding.owner),
+            csv_field(finding.issue.explanation())
+        ));
+    }
+    csv
+}
+
+pub fn findings_to_json(findings: &[AuditFinding]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(findings)
+}
+
+#[cfg(unix)]
+mod unix {
+    use super::*;
+    use dashmap::DashMap;
+    use once_cell::sync::Lazy;
+    use std::fs;
+    use std::os::unix::fs::MetadataExt;
+    use std::path::Path;
+
+    // Les mêmes UID/GID reviennent sur des millions de fichiers
+    static KNOWN_USERS: Lazy<DashMap<u32, bool>> = Lazy::new(DashMap::new);
+    static KNOWN_GROUPS: Lazy<DashMap<u32, bool>> = Lazy::new(DashMap::new);
+
+    pub fn check_path(path: &str) -> Vec<AuditFinding> {
+        let Ok(metadata) = fs::symlink_metadata(path) else {
+            return Vec::new();
+        };
+        // Les liens ont toujours le mode 777, seule leur cible compte
+        if metadata.file_type().is_symlink() {
+            return Vec::new();
+        }
+
+        let mode = metadata.mode();
+        let mut issues: Vec<(Severity, AuditIssue)> = Vec::new();
+
+        if metadata.is_dir() {
+            if mode & 0o002 != 0 && mode & 0o1000 == 0 {
+                issues.push((Severity::High, AuditIssue::WorldWritableDir));
+            }
+        } else if metadata.is_file() {
+            if mode & 0o002 != 0 {
+                // Un programme modifiable par tous peut être piégé
+                let severity = if mode & 0o111 != 0 { Severity::High } else { Severity::Medium };
+                issues.push((severity, AuditIssue::WorldWritableFile));
+            }
+
+            let in_system_dir = SYSTEM_BINARY_DIRS.iter().any(|dir| Path::new(path).starts_with(dir));
+            if mode & 0o4000 != 0 {
+                issues.push((if in_system_dir { Severity::Low } else { Severity::High }, AuditIssue::Setuid));
+            }
+            if mode & 0o2000 != 0 && mode & 0o010 != 0 {
+                issues.push((if in_system_dir { Severity::Low } else { Severity::Medium }, AuditIssue::Setgid));
+            }
+
+            if is_secret_file(path) && mode & 0o077 != 0 {
+                let severity = if mode & 0o006 != 0 { Severity::Critical } else { Severity::High };
+                issues.push((severity, AuditIssue::ExposedSecret));
+            }
+        }
+
+        if !user_exists(metadata.uid()) {
+            issues.push((Severity::Medium, AuditIssue::UnknownOwner));
+        }
+        if !group_exists(metadata.gid()) {
+            issues.push((Severity::Low, AuditIssue::UnknownGroup));
+        }
+
+        issues.into_iter()
+            .map(|(severity, issue)| AuditFinding {
+                severity,
+                issue,
+                path: path.to_string(),
+                mode: format!("{:04o}", mode & 0o7777),
+                owner: format!("{}:{}", metadata.uid(), metadata.gid()),
+            })
+            .collect()
+    }
+
+    pub(super) fn is_secret_file(path: &str) -> bool {
+        let Some(name) = Path::new(path).file_name().map(|n| n.to_string_lossy().to_lowercase()) else {
+            return false;
+        };
+
+        // Les modèles (.env.example...) ne contiennent pas de vrais secrets
+        let is_env = name == ".env"
+            || (name.starts_with(".env.") && ![".example", ".sample", ".template", ".dist"].iter().any(|s| name.ends_with(s)));
+        let is_ssh_key = SSH_KEY_PREFIXES.iter().any(|prefix| name.starts_with(prefix)) && !name.ends_with(".pub");
+        let extension = name.rsplit_once('.').map(|(_, ext)| ext).unwrap_or("");
+
+        is_env || is_ssh_key || SECRET_NAMES.contains(&name.as_str()) || SECRET_EXTENSIONS.contains(&extension)
+    }
+
+    fn user_exists(uid: u32) -> bool {
+        *KNOWN_USERS.entry(uid).or_insert_with(|| {
+            let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+            let mut result = std::ptr::null_mut();
+            let mut buffer = vec![0 as libc::c_char; 1024];
+            let status = unsafe {
+                libc::getpwuid_r(uid, &mut passwd, buffer.as_mut_ptr(), buffer.len(), &mut result)
+            };
+            status == 0 && !result.is_null()
+        })
+    }
+
+    fn group_exists(gid: u32) -> bool {
+        *KNOWN_GROUPS.entry(gid).or_insert_with(|| {
+            let mut group: libc::group = unsafe { std::mem::zeroed() };
+            let mut result = std::ptr::null_mut();
+            // Les groupes listent leurs membres : tampon plus grand que pour passwd
+            let mut buffer = vec![0 as libc::c_char; 16 * 1024];
+            let status = unsafe {
+                libc::getgrgid_r(gid, &mut group, buffer.as_mut_ptr(), buffer.len(), &mut result)
+            };
+            status == 0 && !result.is_null()
+        })
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn is_secret_file_ignore_les_modeles_et_cles_publiques() {
+        for secret in ["/home/léa/.env", "/srv/.env.production", "/home/léa/.ssh/id_ed25519", "/etc/certs/serveur.PEM", "/root/.netrc"] {
+            assert!(unix::is_secret_file(secret), "{}", secret);
+        }
+        for harmless in ["/projet/.env.example", "/home/léa/.ssh/id_rsa.pub", "/projet/clé.txt", "/projet/environnement"] {
+            assert!(!unix::is_secret_file(harmless), "{}", harmless);
+        }
+    }
+
+    #[test]
+    fn audit_paths_trie_les_problemes_par_gravite() {
+        let dir = std::env::temp_dir().join(format!("suptool-audit-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("partagé")).unwrap();
+        fs::write(dir.join(".env"), "TOKEN=secret").unwrap();
+        fs::write(dir.join("notes.txt"), "").unwrap();
+        fs::write(dir.join("privé.key"), "").unwrap();
+        let chmod = |name: &str, mode: u32| fs::set_permissions(dir.join(name), fs::Permissions::from_mode(mode)).unwrap();
+        chmod("partagé", 0o777);
+        chmod(".env", 0o644);
+        chmod("notes.txt", 0o666);
+        chmod("privé.key", 0o600);
+
+        let paths = ["partagé", ".env", "notes.txt", "privé.key"].iter()
+            .map(|name| dir.join(name).to_string_lossy().to_string())
+            .collect();
+        let report = audit_paths(paths).unwrap();
+        assert_eq!(report.checked, 4);
+
+        let found: Vec<(Severity, AuditIssue, String)> = report.findings.iter()
+            .map(|f| (f.severity, f.issue, f.mode.clone()))
+            .collect();
+        assert!(found == vec![
+            (Severity::Critical, AuditIssue::ExposedSecret, "0644".to_string()),
+            (Severity::High, AuditIssue::WorldWritableDir, "0777".to_string()),
+            (Severity::Medium, AuditIssue::WorldWritableFile, "0666".to_string()),
+        ]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn findings_to_csv_ajoute_l_explication() {
+        let finding = AuditFinding {
+            severity: Severity::Low,
+            issue: AuditIssue::Setuid,
+            path: "/usr/bin/passwd".to_string(),
+            mode: "4755".to_string(),
+            owner: "0:0".to_string(),
+        };
+        assert_eq!(
+            findings_to_csv(&[finding]).lines().nth(1),
+            Some("basse,exécutable setuid,/usr/bin/passwd,4755,0:0,\"S'exécute avec les droits de son propriétaire, quel que soit l'utilisateur qui le lance\"")
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use crate::utils::{get_data_dir, list_volumes, load_json, save_json, VolumeInfo};
+use serde::{Serialize, Deserialize};
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+const CATALOG_DIR: &str = "catalogs";
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CatalogEntry {
+    pub name: String,
+    pub path: String,
+    pub size: u64,
+    pub is_dir: bool,
+    pub modified: SystemTime,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct VolumeCatalog {
+    pub volume: VolumeInfo,
+    pub indexed_at: SystemTime,
+    pub entries: Vec<CatalogEntry>,
+}
+
+// Catalogue chargé en mémoire avec les noms déjà en minuscules pour la recherche
+pub struct LoadedCatalog {
+    pub catalog: VolumeCatalog,
+    pub names_lower: Vec<String>,
+}
+
+static CATALOGS: Lazy<DashMap<String, Arc<LoadedCatalog>>> = Lazy::new(|| {
+    let catalogs = DashMap::new();
+    for uuid in list_catalog_files() {
+        if let Some(catalog) = load_json::<VolumeCatalog>(&catalog_file(&uuid)) {
+            catalogs.insert(catalog.volume.uuid.clone(), Arc::new(LoadedCatalog::from(catalog)));
+        }
+    }
+    catalogs
+});
+
+static MOUNTED_VOLUMES: Lazy<RwLock<Vec<VolumeInfo>>> = Lazy::new(|| RwLock::new(sorted_volumes()));
+
+impl From<VolumeCatalog> for LoadedCatalog {
+    fn from(catalog: VolumeCatalog) -> Self {
+        let names_lower = catalog.entries.iter()
+            .map(|e| e.name.to_lowercase())
+            .collect();
+        Self { catalog, names_lower }
+    }
+}
+
+impl VolumeCatalog {
+    pub fn save(self) -> io::Result<usize> {
+        save_json(&catalog_file(&self.volume.uuid), &self)?;
+        let count = self.entries.len();
+        CATALOGS.insert(self.volume.uuid.clone(), Arc::new(LoadedCatalog::from(self)));
+        Ok(count)
+    }
+}
+
+pub fn catalogs() -> Vec<Arc<LoadedCatalog>> {
+    CATALOGS.iter().map(|entry| entry.value().clone()).collect()
+}
+
+pub fn forget_catalog(uuid: &str) -> io::Result<()> {
+    CATALOGS.remove(uuid);
+    fs::remove_file(get_data_dir().join(catalog_file(uuid)))
+}
+
+pub fn refresh_mounted_volumes() -> Vec<VolumeInfo> {
+    let volumes = sorted_volumes();
+    *MOUNTED_VOLUMES.write() = volumes.clone();
+    volumes
+}
+
+pub fn is_mounted(uuid: &str) -> bool {
+    MOUNTED_VOLUMES.read().iter().any(|v| v.uuid == uuid)
+}
+
+// Volume monté qui contient le chemin (le point de montage le plus long gagne)
+pub fn volume_of(path: &str) -> Option<VolumeInfo> {
+    volume_containing(&MOUNTED_VOLUMES.read(), path).cloned()
+}
+
+// Comparaison par composants : "/media/usb10/x" n'est pas sous "/media/usb1"
+fn volume_containing<'a>(volumes: &'a [VolumeInfo], path: &str) -> Option<&'a VolumeInfo> {
+    volumes.iter()
+        .filter(|v| Path::new(path).starts_with(&v.mount_point))
+        .max_by_key(|v| v.mount_point.len())
+}
+
+fn sorted_volumes() -> Vec<VolumeInfo> {
+    let mut volumes = list_volumes();
+    volumes.sort_by_key(|volume| std::cmp::Reverse(volume.mount_point.len()));
+    volumes
+}
+
+fn list_catalog_files() -> Vec<String> {
+    fs::read_dir(get_data_dir().join(CATALOG_DIR))
+        .map(|entries| entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                let file_name = e.file_name().to_string_lossy().to_string();
+                file_name.strip_suffix(".json").map(|s| s.to_string())
+            })
+            .collect())
+        .unwrap_or_default()
+}
+
+fn catalog_file(uuid: &str) -> String {
+    let safe_uuid: String = uuid.chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' { c } else { '_' })
+        .collect();
+    format!("{}/{}.json", CATALOG_DIR, safe_uuid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn volume(mount_point: &str) -> VolumeInfo {
+        VolumeInfo {
+            label: String::new(),
+            uuid: mount_point.to_string(),
+            mount_point: mount_point.to_string(),
+            removable: true,
+        }
+    }
+
+    fn mount_of(volumes: &[VolumeInfo], path: &str) -> Option<String> {
+        volume_containing(volumes, path).map(|v| v.mount_point.clone())
+    }
+
+    #[test]
+    fn volume_containing_ignore_un_point_de_montage_prefixe_d_un_autre_nom() {
+        let volumes = vec![volume("/"), volume("/media/usb1")];
+        assert_eq!(mount_of(&volumes, "/media/usb10/x"), Some("/".to_string()));
+        assert_eq!(mount_of(&volumes, "/media/usb1/x"), Some("/media/usb1".to_string()));
+        assert_eq!(mount_of(&volumes, "/media/usb1"), Some("/media/usb1".to_string()));
+    }
+
+    #[test]
+    fn volume_containing_prefere_le_point_de_montage_le_plus_long() {
+        let volumes = vec![volume("/"), volume("/media"), volume("/media/usb1/inner"), volume("/media/usb1")];
+        assert_eq!(mount_of(&volumes, "/media/usb1/inner/file"), Some("/media/usb1/inner".to_string()));
+        assert_eq!(mount_of(&volumes, "/media/usb1/file"), Some("/media/usb1".to_string()));
+        assert_eq!(mount_of(&volumes, "/media/other"), Some("/media".to_string()));
+    }
+
+    #[test]
+    fn volume_containing_ne_trouve_rien_hors_des_volumes() {
+        let volumes = vec![volume("/media/usb1")];
+        assert_eq!(mount_of(&volumes, "/home/user"), None);
+    }
+}
@@ -0,0 +1,296 @@
+use md5::Md5;
+use rayon::prelude::*;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const READ_CHUNK: usize = 4 * 1024 * 1024;
+
+// Fichiers d'empreintes cherchés à côté du fichier : d'abord `<fichier>.<ext>`,
+// puis les listes du dossier
+const SIDECAR_EXTENSIONS: &[&str] = &["sha256", "sha256sum", "sha1", "sha1sum", "md5", "md5sum", "b3"];
+const SUMS_FILES: &[&str] = &[
+    "SHA256SUMS", "SHA256SUMS.txt", "sha256sums.txt", "SHA1SUMS", "MD5SUMS",
+    "B3SUMS", "CHECKSUMS", "checksums.txt",
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    Md5,
+    Sha1,
+    Sha256,
+    Blake3,
+    Crc32,
+}
+
+impl HashAlgorithm {
+    pub const ALL: [HashAlgorithm; 5] = [
+        HashAlgorithm::Md5,
+        HashAlgorithm::Sha1,
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Blake3,
+        HashAlgorithm::Crc32,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Md5 => "MD5",
+            HashAlgorithm::Sha1 => "SHA-1",
+            HashAlgorithm::Sha256 => "SHA-256",
+            HashAlgorithm::Blake3 => "BLAKE3",
+            HashAlgorithm::Crc32 => "CRC32",
+        }
+    }
+}
+
+enum Hasher {
+    Md5(Md5),
+    Sha1(Sha1),
+    Sha256(Sha256),
+    Blake3(Box<blake3::Hasher>),
+    Crc32(crc32fast::Hasher),
+}
+
+impl Hasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Md5 => Hasher::Md5(Md5::new()),
+            HashAlgorithm::Sha1 => Hasher::Sha1(Sha1::new()),
+            HashAlgorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            HashAlgorithm::Blake3 => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+            HashAlgorithm::Crc32 => Hasher::Crc32(crc32fast::Hasher::new()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Md5(hasher) => hasher.update(data),
+            Hasher::Sha1(hasher) => hasher.update(data),
+            Hasher::Sha256(hasher) => hasher.update(data),
+            Hasher::Blake3(hasher) => {
+                hasher.update(data);
+            }
+            Hasher::Crc32(hasher) => hasher.update(data),
+        }
+    }
+
+    fn finish(self) -> String {
+        match self {
+            Hasher::Md5(hasher) => format!("{:x}", hasher.finalize()),
+            Hasher::Sha1(hasher) => format!("{:x}", hasher.finalize()),
+            Hasher::Sha256(hasher) => format!("{:x}", hasher.finalize()),
+            Hasher::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+            Hasher::Crc32(hasher) => format!("{:08x}", hasher.finalize()),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Checksum {
+    pub algorithm: HashAlgorithm,
+    // Hexadécimal en minuscules
+    pub value: String,
+}
+
+// Empreinte attendue trouvée à côté du fichier
+#[derive(Clone)]
+pub struct SidecarChecksum {
+    pub source: PathBuf,
+    pub expected: String,
+}
+
+// Lit le fichier une seule fois, chaque bloc étant passé en parallèle à tous
+// les algorithmes. `progress` reçoit (octets lus, taille totale).
+pub fn compute_checksums<F>(path: &Path, cancel: &AtomicBool, progress: F) -> io::Result<Vec<Checksum>>
+where F: Fn(u64, u64) {
+    let metadata = fs::metadata(path)?;
+    if !metadata.is_file() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Les empreintes ne se calculent que sur des fichiers"));
+    }
+
+    let total = metadata.len();
+    let mut file = File::open(path)?;
+    let mut hashers: Vec<Hasher> = HashAlgorithm::ALL.iter().map(|algorithm| Hasher::new(*algorithm)).collect();
+    let mut buffer = vec![0u8; READ_CHUNK];
+    let mut done: u64 = 0;
+
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "Calcul des empreintes annulé"));
+        }
+
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        let chunk = &buffer[..read];
+        hashers.par_iter_mut().for_each(|hasher| hasher.update(chunk));
+
+        done += read as u64;
+        progress(done, total);
+    }
+
+    Ok(HashAlgorithm::ALL.iter()
+        .zip(hashers)
+        .map(|(algorithm, hasher)| Checksum { algorithm: *algorithm, value: hasher.finish() })
+        .collect())
+}
+
+// Algorithme dont l'empreinte correspond à la valeur collée, qui peut être
+// une ligne complète de fichier d'empreintes (`<hash>  <nom>`, format BSD
+// `SHA256 (<nom>) = <hash>`) ou préfixée (`sha256:<hash>`)
+pub fn matching_algorithm(checksums: &[Checksum], expected: &str) -> Option<HashAlgorithm> {
+    let expected = match parse_sum_line(expected.trim()) {
+        Some((hash, _)) => hash,
+        None => normalize_hash(expected.trim())?,
+    };
+    checksums.iter()
+        .find(|checksum| checksum.value == expected)
+        .map(|checksum| checksum.algorithm)
+}
+
+// Cherche l'empreinte attendue du fichier dans `<fichier>.sha256`, SHA256SUMS...
+pub fn find_sidecar(path: &Path) -> Option<SidecarChecksum> {
+    let name = path.file_name()?.to_string_lossy().to_string();
+    let dir = path.parent()?;
+
+    let sidecars = SIDECAR_EXTENSIONS.iter().map(|ext| (dir.join(format!("{}.{}", name, ext)), true));
+    let sums = SUMS_FILES.iter().map(|sums| (dir.join(sums), false));
+
+    sidecars.chain(sums).find_map(|(source, own_file)| {
+        // Une liste d'empreintes ne pèse que quelques Ko
+        if fs::metadata(&source).ok()?.len() > 1024 * 1024 {
+            return None;
+        }
+        let content = fs::read_to_string(&source).ok()?;
+        let expected = content.lines().find_map(|line| {
+            let (hash, listed) = parse_sum_line(line.trim())?;
+            // `<fichier>.sha256` contient parfois l'empreinte seule
+            let same_file = match listed {
+                Some(listed) => Path::new(listed.trim_start_matches('*')).file_name()
+                    .is_some_and(|listed| listed.to_string_lossy() == name),
+                None => own_file,
+            };
+            same_file.then_some(hash)
+        })?;
+        Some(SidecarChecksum { source, expected })
+    })
+}
+
+// (empreinte, nom du fichier s'il est indiqué)
+fn parse_sum_line(line: &str) -> Option<(String, Option<&str>)> {
+    // Format BSD : `SHA256 (nom) = hash`
+    if let Some((head, hash)) = line.rsplit_once(" = ") {
+        let listed = head.split_once(" (").and_then(|(_, rest)| rest.strip_suffix(')'));
+        return normalize_hash(hash).map(|hash| (hash, listed));
+    }
+
+    let mut parts = line.splitn(2, char::is_whitespace);
+    let hash = normalize_hash(parts.next()?)?;
+    let listed = parts.next().map(str::trim).filter(|listed| !listed.is_empty());
+    Some((hash, listed))
+}
+
+fn normalize_hash(value: &str) -> Option<String> {
+    let value = value.trim();
+    let value = value.rsplit_once(':').map(|(_, hash)| hash).unwrap_or(value);
+    let value = value.trim_start_matches("0x").to_lowercase();
+    // CRC32, MD5, SHA-1, SHA-256/BLAKE3
+    let valid = matches!(value.len(), 8 | 32 | 40 | 64) && value.chars().all(|c| c.is_ascii_hexdigit());
+    valid.then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHA256_ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+    const MD5_ABC: &str = "900150983cd24fb0d6963f7d28e17f72";
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("suptool-checksum-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn parse_sum_line_lit_le_format_gnu() {
+        let line = format!("{}  image été.iso", SHA256_ABC);
+        assert_eq!(parse_sum_line(&line), Some((SHA256_ABC.to_string(), Some("image été.iso"))));
+        // Mode binaire : `*` devant le nom
+        let line = format!("{} *image.iso", MD5_ABC);
+        assert_eq!(parse_sum_line(&line), Some((MD5_ABC.to_string(), Some("*image.iso"))));
+        assert_eq!(parse_sum_line(SHA256_ABC), Some((SHA256_ABC.to_string(), None)));
+    }
+
+    #[test]
+    fn parse_sum_line_lit_le_format_bsd() {
+        let line = format!("SHA256 (image été.iso) = {}", SHA256_ABC.to_uppercase());
+        assert_eq!(parse_sum_line(&line), Some((SHA256_ABC.to_string(), Some("image été.iso"))));
+    }
+
+    #[test]
+    fn parse_sum_line_refuse_une_ligne_sans_empreinte() {
+        assert_eq!(parse_sum_line("# SHA256 checksums"), None);
+        assert_eq!(parse_sum_line("image.iso"), None);
+    }
+
+    #[test]
+    fn normalize_hash_accepte_les_prefixes_et_la_casse() {
+        assert_eq!(normalize_hash(&format!("sha256:{}", SHA256_ABC)), Some(SHA256_ABC.to_string()));
+        assert_eq!(normalize_hash(&format!("  MD5:{}  ", MD5_ABC.to_uppercase())), Some(MD5_ABC.to_string()));
+        assert_eq!(normalize_hash("0x352441C2"), Some("352441c2".to_string()));
+        // Longueur inconnue ou caractère non hexadécimal
+        assert_eq!(normalize_hash("352441c"), None);
+        assert_eq!(normalize_hash(&MD5_ABC.replace('9', "g")), None);
+    }
+
+    #[test]
+    fn find_sidecar_lit_le_fichier_voisin() {
+        let dir = temp_dir("voisin");
+        let file = dir.join("données.bin");
+        fs::write(&file, b"abc").unwrap();
+        fs::write(dir.join("données.bin.sha256"), format!("{}\n", SHA256_ABC)).unwrap();
+
+        let sidecar = find_sidecar(&file).unwrap();
+        assert_eq!(sidecar.source, dir.join("données.bin.sha256"));
+        assert_eq!(sidecar.expected, SHA256_ABC);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn find_sidecar_cherche_le_fichier_dans_la_liste_du_dossier() {
+        let dir = temp_dir("liste");
+        let file = dir.join("image.iso");
+        fs::write(&file, b"abc").unwrap();
+        fs::write(dir.join("MD5SUMS"), format!(
+            "{}  autre.iso\n{} *image.iso\n",
+            "d41d8cd98f00b204e9800998ecf8427e",
+            MD5_ABC
+        )).unwrap();
+
+        let sidecar = find_sidecar(&file).unwrap();
+        assert_eq!(sidecar.source, dir.join("MD5SUMS"));
+        assert_eq!(sidecar.expected, MD5_ABC);
+        // Fichier absent de la liste
+        assert!(find_sidecar(&dir.join("absent.iso")).is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn matching_algorithm_reconnait_l_empreinte_calculee() {
+        let dir = temp_dir("calcul");
+        let file = dir.join("abc.txt");
+        fs::write(&file, b"abc").unwrap();
+        let checksums = compute_checksums(&file, &AtomicBool::new(false), |_, _| {}).unwrap();
+
+        assert!(matching_algorithm(&checksums, SHA256_ABC) == Some(HashAlgorithm::Sha256));
+        assert!(matching_algorithm(&checksums, &format!("MD5 (abc.txt) = {}", MD5_ABC)) == Some(HashAlgorithm::Md5));
+        assert!(matching_algorithm(&checksums, "352441c2") == Some(HashAlgorithm::Crc32));
+        assert!(matching_algorithm(&checksums, "d41d8cd98f00b204e9800998ecf8427e").is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::utils::{load_json, save_json};
+use serde::{Serialize, Deserialize};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::io;
+
+const COLUMNS_FILE: &str = "columns.json";
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum Column {
+    Name,
+    Path,
+    Size,
+    Type,
+    Modified,
+    Volume,
+    Extension,
+    Created,
+    Accessed,
+    Permissions,
+    Owner,
+    Inode,
+    Links,
+    Hidden,
+    LinkTarget,
+    Tags,
+}
+
+impl Column {
+    pub const ALL: &'static [Column] = &[
+        Column::Name, Column::Path, Column::Size, Column::Type, Column::Modified,
+        Column::Volume, Column::Extension, Column::Created, Column::Accessed,
+        Column::Permissions, Column::Owner, Column::Inode, Column::Links,
+        Column::Hidden, Column::LinkTarget, Column::Tags,
+    ];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            Column::Name => "name",
+            Column::Path => "path",
+            Column::Size => "size",
+            Column::Type => "type",
+            Column::Modified => "modified",
+            Column::Volume => "volume",
+            Column::Extension => "extension",
+            Column::Created => "created",
+            Column::Accessed => "accessed",
+            Column::Permissions => "permissions",
+            Column::Owner => "owner",
+            Column::Inode => "inode",
+            Column::Links => "links",
+            Column::Hidden => "hidden",
+            Column::LinkTarget => "link-target",
+            Column::Tags => "tags",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Column::Name => "Nom",
+            Column::Path => "Chemin",
+            Column::Size => "Taille",
+            Column::Type => "Type",
+            Column::Modified => "Modifié",
+            Column::Volume => "Volume",
+            Column::Extension => "Extension",
+            Column::Created => "Créé",
+            Column::Accessed => "Dernier accès",
+            Column::Permissions => "Droits",
+            Column::Owner => "Propriétaire",
+            Column::Inode => "Inode",
+            Column::Links => "Liens",
+            Column::Hidden => "Caché",
+            Column::LinkTarget => "Cible du lien",
+            Column::Tags => "Tags",
+        }
+    }
+
+    pub fn default_width(&self) -> f32 {
+        match self {
+            Column::Name => 220.0,
+            Column::Path | Column::LinkTarget => 360.0,
+            Column::Modified | Column::Created | Column::Accessed => 120.0,
+            Column::Volume | Column::Permissions | Column::Owner | Column::Inode => 100.0,
+            Column::Tags => 140.0,
+            _ => 70.0,
+        }
+    }
+
+    // Colonnes dont la valeur doit être relevée pendant l'indexation
+    pub fn needs_attributes(&self) -> bool {
+        !matches!(
+            self,
+            Column::Name | Column::Path | Column::Size | Column::Type
+                | Column::Modified | Column::Volume | Column::Extension | Column::LinkTarget
+                | Column::Tags
+        )
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ColumnSettings {
+    pub visible: Vec<Column>,
+    pub widths: HashMap<Column, f32>,
+}
+
+impl Default for ColumnSettings {
+    fn default() -> Self {
+        Self {
+            visible: vec![Column::Name, Column::Path, Column::Size, Column::Type, Column::Modified, Column::Volume],
+            widths: HashMap::new(),
+        }
+    }
+}
+
+impl ColumnSettings {
+    pub fn width(&self, column: Column) -> f32 {
+        self.widths.get(&column).copied().unwrap_or_else(|| column.default_width())
+    }
+
+    pub fn is_visible(&self, column: Column) -> bool {
+        self.visible.contains(&column)
+    }
+
+    pub fn wants_attributes(&self) -> bool {
+        self.visible.iter().any(|column| column.needs_attributes())
+    }
+
+    // Les colonnes restent dans l'ordre de `Column::ALL`, le nom est toujours affiché
+    pub fn toggle(&mut self, column: Column) {
+        if column == Column::Name {
+            return;
+        }
+        if self.is_visible(column) {
+            self.visible.retain(|c| *c != column);
+        } else {
+            self.visible = Column::ALL.iter()
+                .copied()
+                .filter(|c| *c == column || self.visible.contains(c))
+                .collect();
+        }
+    }
+}
+
+static COLUMNS: Lazy<RwLock<ColumnSettings>> = Lazy::new(|| {
+    RwLock::new(load_json(COLUMNS_FILE).unwrap_or_default())
+});
+
+pub fn column_settings() -> ColumnSettings {
+    COLUMNS.read().clone()
+}
+
+pub fn update_column_settings(update: impl FnOnce(&mut ColumnSettings)) -> io::Result<ColumnSettings> {
+    let mut settings = COLUMNS.write();
+    update(&mut settings);
+    save_json(COLUMNS_FILE, &*settings)?;
+    Ok(settings.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggle_garde_l_ordre_des_colonnes_et_le_nom() {
+        let mut settings = ColumnSettings::default();
+        settings.toggle(Column::Name);
+        assert!(settings.is_visible(Column::Name));
+
+        settings.toggle(Column::Tags);
+        settings.toggle(Column::Extension);
+        settings.toggle(Column::Size);
+        assert_eq!(settings.visible, vec![
+            Column::Name, Column::Path, Column::Type, Column::Modified,
+            Column::Volume, Column::Extension, Column::Tags,
+        ]);
+    }
+
+    #[test]
+    fn wants_attributes_seulement_pour_les_colonnes_optionnelles() {
+        let mut settings = ColumnSettings::default();
+        settings.toggle(Column::Tags);
+        assert!(!settings.wants_attributes());
+        settings.toggle(Column::Owner);
+        assert!(settings.wants_attributes());
+    }
+
+    #[test]
+    fn width_utilise_la_largeur_enregistree_sinon_celle_par_defaut() {
+        let mut settings = ColumnSettings::default();
+        settings.widths.insert(Column::Path, 500.0);
+        assert_eq!(settings.width(Column::Path), 500.0);
+        assert_eq!(settings.width(Column::Name), 220.0);
+    }
+}
@@ -0,0 +1,187 @@
+use super::query::extension_of;
+use super::searcher::SearchMatch;
+use chrono::{DateTime, Datelike, Local};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    None,
+    Folder,
+    Extension,
+    Date,
+}
+
+impl GroupBy {
+    // Même ordre que la liste déroulante de la page EverySup
+    pub fn from_index(index: i32) -> Self {
+        match index {
+            1 => GroupBy::Folder,
+            2 => GroupBy::Extension,
+            3 => GroupBy::Date,
+            _ => GroupBy::None,
+        }
+    }
+}
+
+pub struct ResultGroup {
+    pub label: String,
+    pub total_size: u64,
+    pub items: Vec<SearchMatch>,
+    // Liens durs déjà comptés dans `total_size`
+    counted_links: HashSet<(u64, u64)>,
+}
+
+impl ResultGroup {
+    fn new(label: String) -> Self {
+        Self {
+            label,
+            total_size: 0,
+            items: Vec::new(),
+            counted_links: HashSet::new(),
+        }
+    }
+
+    fn push(&mut self, hit: SearchMatch) {
+        let counted = match hit.result.hard_link {
+            Some(id) => self.counted_links.insert(id),
+            None => true,
+        };
+        if counted {
+            self.total_size += hit.result.size;
+        }
+        self.items.push(hit);
+    }
+}
+
+const DATE_BUCKETS: &[&str] = &["Aujourd'hui", "Cette semaine", "Ce mois-ci", "Plus ancien"];
+
+pub fn group_results(matches: Vec<SearchMatch>, group_by: GroupBy) -> Vec<ResultGroup> {
+    if group_by == GroupBy::None {
+        let mut group = ResultGroup::new(String::new());
+        matches.into_iter().for_each(|hit| group.push(hit));
+        return vec![group];
+    }
+
+    let now = Local::now();
+    let mut groups: HashMap<String, ResultGroup> = HashMap::new();
+
+    for hit in matches {
+        let label = match group_by {
+            GroupBy::Folder => Path::new(&hit.result.path).parent()
+                .map(|parent| parent.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            GroupBy::Extension if hit.result.is_dir => "(dossiers)".to_string(),
+            GroupBy::Extension => extension_of(&hit.result.name)
+                .map(|ext| format!(".{}", ext))
+                .unwrap_or_else(|| "(sans extension)".to_string()),
+            GroupBy::Date => date_bucket(DateTime::<Local>::from(hit.result.modified), now).to_string(),
+            GroupBy::None => unreachable!(),
+        };
+
+        groups.entry(label.clone())
+            .or_insert_with(|| ResultGroup::new(label))
+            .push(hit);
+    }
+
+    let mut groups: Vec<ResultGroup> = groups.into_values().collect();
+    match group_by {
+        GroupBy::Date => groups.sort_by_key(|g| DATE_BUCKETS.iter().position(|b| *b == g.label)),
+        // Les groupes les plus fournis d'abord
+        _ => groups.sort_by(|a, b| b.items.len().cmp(&a.items.len()).then(a.label.cmp(&b.label))),
+    }
+    groups
+}
+
+fn date_bucket(date: DateTime<Local>, now: DateTime<Local>) -> &'static str {
+    let (date, today) = (date.date_naive(), now.date_naive());
+
+    if date >= today {
+        DATE_BUCKETS[0]
+    } else if date.iso_week() == today.iso_week() {
+        DATE_BUCKETS[1]
+    } else if date.year() == today.year() && date.month() == today.month() {
+        DATE_BUCKETS[2]
+    } else {
+        DATE_BUCKETS[3]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::searcher::SearchResult;
+    use chrono::{Duration, TimeZone};
+    use std::time::SystemTime;
+
+    fn hit(path: &str, size: u64, is_dir: bool, hard_link: Option<(u64, u64)>) -> SearchMatch {
+        let name = Path::new(path).file_name().unwrap().to_string_lossy().to_string();
+        SearchMatch {
+            result: SearchResult {
+                id: 0,
+                name,
+                path: path.to_string(),
+                size,
+                is_dir,
+                modified: SystemTime::now(),
+                volume: String::new(),
+                offline: false,
+                attributes: None,
+                link_target: None,
+                hard_link,
+                archive: None,
+            },
+            name_spans: Vec::new(),
+            path_spans: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn group_results_par_dossier_trie_les_groupes_les_plus_fournis_d_abord() {
+        let groups = group_results(vec![
+            hit("/docs/été.txt", 10, false, None),
+            hit("/photos/a.jpg", 20, false, None),
+            hit("/photos/b.jpg", 30, false, None),
+        ], GroupBy::Folder);
+
+        let labels: Vec<&str> = groups.iter().map(|g| g.label.as_str()).collect();
+        assert_eq!(labels, vec!["/photos", "/docs"]);
+        assert_eq!(groups[0].total_size, 50);
+    }
+
+    #[test]
+    fn group_results_par_extension_separe_dossiers_et_fichiers_sans_extension() {
+        let groups = group_results(vec![
+            hit("/docs/rapport.PDF", 1, false, None),
+            hit("/docs/Makefile", 1, false, None),
+            hit("/docs/archives.d", 0, true, None),
+        ], GroupBy::Extension);
+
+        let mut labels: Vec<&str> = groups.iter().map(|g| g.label.as_str()).collect();
+        labels.sort();
+        assert_eq!(labels, vec!["(dossiers)", "(sans extension)", ".pdf"]);
+    }
+
+    #[test]
+    fn group_results_ne_compte_qu_une_fois_les_liens_durs() {
+        let groups = group_results(vec![
+            hit("/a/données", 100, false, Some((1, 42))),
+            hit("/b/données", 100, false, Some((1, 42))),
+            hit("/c/autre", 5, false, None),
+        ], GroupBy::None);
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].items.len(), 3);
+        assert_eq!(groups[0].total_size, 105);
+    }
+
+    #[test]
+    fn date_bucket_classe_par_jour_semaine_et_mois() {
+        // Mercredi 15 mai 2024
+        let now = Local.with_ymd_and_hms(2024, 5, 15, 12, 0, 0).unwrap();
+        assert_eq!(date_bucket(now - Duration::hours(2), now), "Aujourd'hui");
+        assert_eq!(date_bucket(now - Duration::days(2), now), "Cette semaine");
+        assert_eq!(date_bucket(now - Duration::days(10), now), "Ce mois-ci");
+        assert_eq!(date_bucket(now - Duration::days(20), now), "Plus ancien");
+    }
+}
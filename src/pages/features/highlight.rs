@@ -0,0 +1,221 @@
+// Coloration syntaxique volontairement simple : commentaires, chaînes,
+// nombres et mots-clés, sans analyse de la grammaire du langage
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TokenKind {
+    Plain,
+    Keyword,
+    String,
+    Number,
+    Comment,
+}
+
+struct Language {
+    line_comment: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    keywords: &'static [&'static str],
+}
+
+const C_LIKE_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "case", "catch", "class", "const", "continue", "default",
+    "do", "else", "enum", "export", "extends", "false", "fn", "for", "func", "function", "if",
+    "impl", "import", "in", "interface", "let", "match", "mod", "mut", "namespace", "new",
+    "null", "package", "private", "protected", "pub", "public", "return", "self", "static",
+    "struct", "super", "switch", "this", "throw", "trait", "true", "try", "type", "use", "var",
+    "void", "where", "while",
+];
+
+const SCRIPT_KEYWORDS: &[&str] = &[
+    "and", "as", "case", "class", "def", "do", "done", "elif", "else", "end", "esac", "except",
+    "export", "false", "fi", "for", "from", "function", "if", "import", "in", "lambda", "local",
+    "module", "not", "or", "pass", "return", "then", "true", "try", "while", "with", "yield",
+    "False", "None", "True",
+];
+
+const SQL_KEYWORDS: &[&str] = &[
+    "and", "by", "create", "delete", "do", "else", "end", "for", "from", "function", "group",
+    "if", "in", "insert", "into", "join", "local", "nil", "not", "null", "or", "order",
+    "return", "select", "set", "table", "then", "update", "values", "where", "while",
+];
+
+const DATA_KEYWORDS: &[&str] = &["true", "false", "null", "yes", "no"];
+
+fn language_for(extension: &str) -> Option<Language> {
+    let language = match extension {
+        "rs" | "c" | "h" | "cpp" | "hpp" | "cc" | "cs" | "java" | "kt" | "js" | "jsx" | "ts"
+        | "tsx" | "go" | "swift" | "php" | "slint" | "css" | "scss" => Language {
+            line_comment: &["//"],
+            block_comment: Some(("/*", "*/")),
+            keywords: C_LIKE_KEYWORDS,
+        },
+        "py" | "sh" | "bash" | "zsh" | "rb" | "pl" | "r" | "ps1" => Language {
+            line_comment: &["#"],
+            block_comment: None,
+            keywords: SCRIPT_KEYWORDS,
+        },
+        "toml" | "yaml" | "yml" | "conf" | "cfg" | "ini" | "properties" => Language {
+            line_comment: &["#", ";"],
+            block_comment: None,
+            keywords: DATA_KEYWORDS,
+        },
+        "sql" | "lua" => Language {
+            line_comment: &["--"],
+            block_comment: Some(("/*", "*/")),
+            keywords: SQL_KEYWORDS,
+        },
+        "json" => Language {
+            line_comment: &[],
+            block_comment: None,
+            keywords: DATA_KEYWORDS,
+        },
+        _ => return None,
+    };
+    Some(language)
+}
+
+pub type HighlightedLine = Vec<(String, TokenKind)>;
+
+pub fn highlight(text: &str, extension: &str) -> Vec<HighlightedLine> {
+    let Some(language) = language_for(extension) else {
+        return text.lines()
+            .map(|line| vec![(line.to_string(), TokenKind::Plain)])
+            .collect();
+    };
+
+    // Un commentaire bloc peut s'étendre sur plusieurs lignes
+    let mut in_block = false;
+    text.lines()
+        .map(|line| highlight_line(line, &language, &mut in_block))
+        .collect()
+}
+
+fn highlight_line(line: &str, language: &Language, in_block: &mut bool) -> HighlightedLine {
+    let mut tokens: HighlightedLine = Vec::new();
+    let mut push = |text: &str, kind: TokenKind| {
+        if text.is_empty() {
+            return;
+        }
+        match tokens.last_mut() {
+            Some((last, last_kind)) if *last_kind == kind => last.push_str(text),
+            _ => tokens.push((text.to_string(), kind)),
+        }
+    };
+
+    let mut rest = line;
+    while !rest.is_empty() {
+        if *in_block {
+            let end = language.block_comment.map(|(_, end)| end).unwrap_or_default();
+            match rest.find(end) {
+                Some(i) => {
+                    push(&rest[..i + end.len()], TokenKind::Comment);
+                    rest = &rest[i + end.len()..];
+                    *in_block = false;
+                }
+                None => {
+                    push(rest, TokenKind::Comment);
+                    rest = "";
+                }
+            }
+            continue;
+        }
+
+        if language.line_comment.iter().any(|prefix| rest.starts_with(prefix)) {
+            push(rest, TokenKind::Comment);
+            break;
+        }
+
+        if let Some((start, _)) = language.block_comment {
+            if rest.starts_with(start) {
+                push(start, TokenKind::Comment);
+                rest = &rest[start.len()..];
+                *in_block = true;
+                continue;
+            }
+        }
+
+        let first = rest.chars().next().unwrap_or_default();
+
+        if first == '"' || first == '\'' || first == '`' {
+            let end = string_end(rest, first);
+            push(&rest[..end], TokenKind::String);
+            rest = &rest[end..];
+        } else if first.is_ascii_digit() {
+            let end = rest.find(|c: char| !c.is_ascii_alphanumeric() && c != '.' && c != '_')
+                .unwrap_or(rest.len());
+            push(&rest[..end], TokenKind::Number);
+            rest = &rest[end..];
+        } else if first.is_alphabetic() || first == '_' {
+            let end = rest.find(|c: char| !c.is_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            let word = &rest[..end];
+            let kind = if language.keywords.contains(&word) { TokenKind::Keyword } else { TokenKind::Plain };
+            push(word, kind);
+            rest = &rest[end..];
+        } else {
+            let len = first.len_utf8();
+            push(&rest[..len], TokenKind::Plain);
+            rest = &rest[len..];
+        }
+    }
+
+    tokens
+}
+
+// Position juste après le guillemet fermant (ou fin de ligne si la chaîne n'est pas fermée)
+fn string_end(text: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (i, c) in text.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == quote {
+            return i + c.len_utf8();
+        }
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(line: &HighlightedLine) -> Vec<(&str, TokenKind)> {
+        line.iter().map(|(text, kind)| (text.as_str(), *kind)).collect()
+    }
+
+    #[test]
+    fn highlight_reconnait_mots_cles_chaines_et_nombres() {
+        let lines = highlight("let été = \"ça\" + 42; // fin", "rs");
+        assert_eq!(kinds(&lines[0]), vec![
+            ("let", TokenKind::Keyword),
+            (" été = ", TokenKind::Plain),
+            ("\"ça\"", TokenKind::String),
+            (" + ", TokenKind::Plain),
+            ("42", TokenKind::Number),
+            ("; ", TokenKind::Plain),
+            ("// fin", TokenKind::Comment),
+        ]);
+    }
+
+    #[test]
+    fn highlight_suit_un_commentaire_bloc_sur_plusieurs_lignes() {
+        let lines = highlight("a /* début\nsuite */ b", "c");
+        assert_eq!(kinds(&lines[0]), vec![("a ", TokenKind::Plain), ("/* début", TokenKind::Comment)]);
+        assert_eq!(kinds(&lines[1]), vec![("suite */", TokenKind::Comment), (" b", TokenKind::Plain)]);
+    }
+
+    #[test]
+    fn highlight_gere_les_guillemets_echappes_et_non_fermes() {
+        let lines = highlight("x = 'l\\'été'\ny = \"ouvert", "py");
+        assert_eq!(lines[0][1], ("'l\\'été'".to_string(), TokenKind::String));
+        assert_eq!(lines[1][1], ("\"ouvert".to_string(), TokenKind::String));
+    }
+
+    #[test]
+    fn highlight_laisse_le_texte_brut_sans_langage_connu() {
+        let lines = highlight("let x = 1\n// rien", "txt");
+        assert_eq!(kinds(&lines[1]), vec![("// rien", TokenKind::Plain)]);
+        assert_eq!(lines.len(), 2);
+    }
+}
@@ -0,0 +1,169 @@
+use crate::utils::{load_json, save_json};
+use serde::{Serialize, Deserialize};
+use std::io;
+use std::time::SystemTime;
+
+const HISTORY_FILE: &str = "search_history.json";
+const MAX_HISTORY_ENTRIES: usize = 200;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub query: String,
+    pub timestamp: SystemTime,
+    pub hits: usize,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub name: String,
+    pub query: String,
+    // "file", "folder" ou vide pour tout
+    pub kind: String,
+    pub extensions: Vec<String>,
+    pub pinned: bool,
+    pub created: SystemTime,
+}
+
+impl SavedSearch {
+    // Requête complète avec les filtres, dans la syntaxe de la barre de recherche
+    pub fn to_query(&self) -> String {
+        let mut parts = vec![self.query.trim().to_string()];
+        if !self.kind.is_empty() {
+            parts.push(format!("type:{}", self.kind));
+        }
+        if !self.extensions.is_empty() {
+            parts.push(format!("ext:{}", self.extensions.join(",")));
+        }
+        parts.retain(|p| !p.is_empty());
+        parts.join(" ")
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct SearchHistory {
+    pub entries: Vec<HistoryEntry>,
+    pub saved: Vec<SavedSearch>,
+}
+
+impl SearchHistory {
+    pub fn load() -> Self {
+        load_json(HISTORY_FILE).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        save_json(HISTORY_FILE, self)
+    }
+
+    // La même requête relancée remonte en tête au lieu d'être dupliquée
+    pub fn record(&mut self, query: &str, hits: usize) {
+        let query = query.trim();
+        if query.is_empty() {
+            return;
+        }
+
+        self.entries.retain(|e| e.query != query);
+        self.entries.insert(0, HistoryEntry {
+            query: query.to_string(),
+            timestamp: SystemTime::now(),
+            hits,
+        });
+        self.entries.truncate(MAX_HISTORY_ENTRIES);
+    }
+
+    pub fn clear_entries(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn add_saved(&mut self, search: SavedSearch) {
+        // Un nom déjà utilisé remplace la recherche existante
+        match self.saved.iter_mut().find(|s| s.name == search.name) {
+            Some(existing) => *existing = search,
+            None => self.saved.push(search),
+        }
+    }
+
+    pub fn remove_saved(&mut self, index: usize) {
+        if index < self.saved.len() {
+            self.saved.remove(index);
+        }
+    }
+
+    pub fn toggle_pin(&mut self, index: usize) {
+        if let Some(search) = self.saved.get_mut(index) {
+            search.pinned = !search.pinned;
+        }
+    }
+
+    pub fn pinned(&self) -> Vec<&SavedSearch> {
+        self.saved.iter().filter(|s| s.pinned).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn saved(name: &str, query: &str) -> SavedSearch {
+        SavedSearch {
+            name: name.to_string(),
+            query: query.to_string(),
+            kind: String::new(),
+            extensions: Vec::new(),
+            pinned: false,
+            created: SystemTime::now(),
+        }
+    }
+
+    #[test]
+    fn record_remonte_une_requete_relancee_sans_la_dupliquer() {
+        let mut history = SearchHistory::default();
+        history.record("rapport", 3);
+        history.record("  ", 0);
+        history.record("été", 1);
+        history.record(" rapport ", 5);
+
+        let queries: Vec<&str> = history.entries.iter().map(|e| e.query.as_str()).collect();
+        assert_eq!(queries, vec!["rapport", "été"]);
+        assert_eq!(history.entries[0].hits, 5);
+    }
+
+    #[test]
+    fn record_limite_la_taille_de_l_historique() {
+        let mut history = SearchHistory::default();
+        for i in 0..MAX_HISTORY_ENTRIES + 10 {
+            history.record(&format!("requête {}", i), i);
+        }
+        assert_eq!(history.entries.len(), MAX_HISTORY_ENTRIES);
+        assert_eq!(history.entries[0].query, format!("requête {}", MAX_HISTORY_ENTRIES + 9));
+    }
+
+    #[test]
+    fn to_query_ajoute_les_filtres() {
+        let mut search = saved("Factures", " facture ");
+        assert_eq!(search.to_query(), "facture");
+        search.kind = "file".to_string();
+        search.extensions = vec!["pdf".to_string(), "odt".to_string()];
+        assert_eq!(search.to_query(), "facture type:file ext:pdf,odt");
+        search.query.clear();
+        assert_eq!(search.to_query(), "type:file ext:pdf,odt");
+    }
+
+    #[test]
+    fn add_saved_remplace_une_recherche_du_meme_nom() {
+        let mut history = SearchHistory::default();
+        history.add_saved(saved("Photos", "vacances"));
+        history.add_saved(saved("Documents", "contrat"));
+        history.add_saved(saved("Photos", "été"));
+        assert_eq!(history.saved.len(), 2);
+        assert_eq!(history.saved[0].query, "été");
+
+        history.toggle_pin(1);
+        history.toggle_pin(5);
+        let pinned: Vec<&str> = history.pinned().iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(pinned, vec!["Documents"]);
+
+        history.remove_saved(0);
+        history.remove_saved(5);
+        assert_eq!(history.saved.len(), 1);
+    }
+}
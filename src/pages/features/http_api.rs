@@ -0,0 +1,363 @@
+use super::searcher::{FileSearcher, SearchResult};
+use crate::utils::{load_json, save_json};
+use serde::{Serialize, Deserialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::io;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+const API_SETTINGS_FILE: &str = "http_api.json";
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 1_000;
+// Au-delà, mieux vaut affiner la recherche que paginer
+const MAX_OFFSET: usize = 10_000;
+// Un tri ne porte que sur les premiers résultats trouvés
+const MAX_SORTED: usize = 2_000;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ApiSettings {
+    pub enabled: bool,
+    pub port: u16,
+    // Vide = pas d'authentification
+    pub token: String,
+}
+
+impl Default for ApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 8765,
+            token: generate_token(),
+        }
+    }
+}
+
+// Au premier lancement, le jeton généré est enregistré pour rester le même
+pub fn api_settings() -> ApiSettings {
+    load_json(API_SETTINGS_FILE).unwrap_or_else(|| {
+        let settings = ApiSettings::default();
+        if let Err(e) = set_api_settings(&settings) {
+            eprintln!("Erreur lors de la sauvegarde des réglages de l'API: {}", e);
+        }
+        settings
+    })
+}
+
+pub fn set_api_settings(settings: &ApiSettings) -> io::Result<()> {
+    save_json(API_SETTINGS_FILE, settings)
+}
+
+// Résultat tel qu'exposé par l'API : dates en secondes Unix
+#[derive(Serialize)]
+struct ApiResult<'a> {
+    name: &'a str,
+    path: &'a str,
+    size: u64,
+    is_dir: bool,
+    modified: u64,
+    volume: &'a str,
+    offline: bool,
+    link_target: Option<&'a str>,
+    archive: Option<&'a str>,
+}
+
+impl<'a> From<&'a SearchResult> for ApiResult<'a> {
+    fn from(result: &'a SearchResult) -> Self {
+        Self {
+            name: &result.name,
+            path: &result.path,
+            size: result.size,
+            is_dir: result.is_dir,
+            modified: unix_seconds(result.modified),
+            volume: &result.volume,
+            offline: result.offline,
+            link_target: result.link_target.as_deref(),
+            archive: result.archive.as_deref(),
+        }
+    }
+}
+
+// Serveur HTTP local (127.0.0.1 uniquement), arrêté par `stop`
+pub struct HttpApi {
+    server: Arc<Server>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl HttpApi {
+    // `on_reindex` reçoit le profil demandé (None = tous les profils)
+    pub fn start<F>(
+        settings: &ApiSettings,
+        searcher: Arc<FileSearcher>,
+        is_indexing: Arc<AtomicBool>,
+        on_reindex: F
+    ) -> io::Result<Self>
+    where F: Fn(Option<String>) + Send + 'static {
+        let server = Server::http(("127.0.0.1", settings.port))
+            .map_err(|e| io::Error::new(io::ErrorKind::AddrInUse, e.to_string()))?;
+        let server = Arc::new(server);
+        println!("🌐 API HTTP sur http://127.0.0.1:{}", settings.port);
+
+        let token = settings.token.clone();
+        let port = settings.port;
+        let thread = thread::spawn({
+            let server = server.clone();
+            move || {
+                for request in server.incoming_requests() {
+                    handle_request(request, port, &token, &searcher, &is_indexing, &on_reindex);
+                }
+            }
+        });
+
+        Ok(Self { server, thread: Some(thread) })
+    }
+
+    pub fn stop(mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        println!("🌐 API HTTP arrêtée");
+    }
+}
+
+fn handle_request<F>(
+    request: Request,
+    port: u16,
+    token: &str,
+    searcher: &FileSearcher,
+    is_indexing: &AtomicBool,
+    on_reindex: &F
+) where F: Fn(Option<String>) {
+    // Une page web ne doit pas pouvoir interroger l'API, ni directement (Origin)
+    // ni via un nom de domaine qui pointe vers 127.0.0.1 (DNS rebinding)
+    if !header(&request, "Host").is_some_and(|host| is_local_host(host, port)) {
+        respond(request, 403, json!({ "error": "invalid host" }));
+        return;
+    }
+    if header(&request, "Origin").is_some() {
+        respond(request, 403, json!({ "error": "browser requests are not allowed" }));
+        return;
+    }
+
+    let (path, params) = parse_url(request.url());
+
+    if !token.is_empty() && !is_authorized(header(&request, "Authorization"), token) {
+        respond(request, 401, json!({ "error": "missing or invalid token" }));
+        return;
+    }
+
+    let method = request.method().clone();
+    match (method, path.as_str()) {
+        (Method::Get, "/search") => {
+            if let Some(profile) = params.get("profile") {
+                if !searcher.profiles().iter().any(|p| &p.name == profile) {
+                    respond(request, 404, json!({ "error": format!("unknown profile: {}", profile) }));
+                    return;
+                }
+            }
+            let body = search(searcher, &params);
+            respond(request, 200, body);
+        }
+        (Method::Get, "/stats") => {
+            let body = stats(searcher, is_indexing);
+            respond(request, 200, body);
+        }
+        (Method::Post, "/reindex") => {
+            let profile = params.get("profile").cloned();
+            if let Some(profile) = &profile {
+                if !searcher.profiles().iter().any(|p| &p.name == profile) {
+                    respond(request, 404, json!({ "error": format!("unknown profile: {}", profile) }));
+                    return;
+                }
+            }
+            on_reindex(profile.clone());
+            respond(request, 202, json!({ "queued": profile.map_or("all".to_string(), |p| p) }));
+        }
+        (_, "/search" | "/stats" | "/reindex") => {
+            respond(request, 405, json!({ "error": "method not allowed" }));
+        }
+        _ => respond(request, 404, json!({ "error": "not found" })),
+    }
+}
+
+// GET /search?q=&offset=&limit=&sort=name|path|size|modified&order=asc|desc&profile=
+fn search(searcher: &FileSearcher, params: &HashMap<String, String>) -> serde_json::Value {
+    let query = params.get("q").map(String::as_str).unwrap_or("");
+    let offset = param(params, "offset", 0).min(MAX_OFFSET);
+    let limit = param(params, "limit", DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let profile = params.get("profile").map(String::as_str);
+
+    // Un tri porte sur les MAX_SORTED premiers résultats, pas seulement sur la page demandée
+    let sorted = params.contains_key("sort");
+    let fetch = if sorted { MAX_SORTED } else { offset + limit };
+    let mut results: Vec<SearchResult> = searcher.search_in(profile, query, fetch)
+        .into_iter()
+        .map(|hit| hit.result)
+        .collect();
+
+    // Sans tri demandé, l'ordre est celui de la recherche (pertinence)
+    if let Some(sort) = params.get("sort") {
+        match sort.as_str() {
+            "name" => results.sort_by_key(|r| r.name.to_lowercase()),
+            "path" => results.sort_by(|a, b| a.path.cmp(&b.path)),
+            "size" => results.sort_by_key(|r| r.size),
+            "modified" => results.sort_by_key(|r| r.modified),
+            _ => {}
+        }
+        if params.get("order").is_some_and(|order| order == "desc") {
+            results.reverse();
+        }
+    }
+
+    let page: Vec<ApiResult> = results.iter().skip(offset).take(limit).map(ApiResult::from).collect();
+    json!({
+        "query": query,
+        "offset": offset,
+        "limit": limit,
+        "count": page.len(),
+        // Vrai si d'autres résultats existent au-delà de cette page
+        "more": results.len() > offset + limit,
+        // Vrai si le tri n'a porté que sur une partie des résultats
+        "truncated": sorted && results.len() >= MAX_SORTED,
+        "results": page,
+    })
+}
+
+// GET /stats
+fn stats(searcher: &FileSearcher, is_indexing: &AtomicBool) -> serde_json::Value {
+    let profiles: Vec<serde_json::Value> = searcher.profiles()
+        .iter()
+        .map(|profile| json!({
+            "name": profile.name,
+            "roots": profile.roots,
+            "files": searcher.profile_file_count(&profile.name),
+            "last_indexed": searcher.profile_last_indexed(&profile.name).map(unix_seconds),
+        }))
+        .collect();
+
+    json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "indexing": is_indexing.load(Ordering::SeqCst),
+        "index_errors": searcher.index_errors().len(),
+        "profiles": profiles,
+    })
+}
+
+// Le jeton n'est accepté que dans l'en-tête : dans l'URL il finirait dans
+// l'historique du shell et la liste des processus
+fn is_authorized(authorization: Option<&str>, token: &str) -> bool {
+    authorization
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|given| tokens_match(given.trim(), token))
+}
+
+// Comparaison en temps constant : la durée ne dit pas combien de caractères
+// sont justes
+fn tokens_match(given: &str, expected: &str) -> bool {
+    let (given, expected) = (given.as_bytes(), expected.as_bytes());
+    let mut difference = given.len() ^ expected.len();
+    for (i, byte) in expected.iter().enumerate() {
+        difference |= (byte ^ given.get(i).copied().unwrap_or(0)) as usize;
+    }
+    difference == 0
+}
+
+fn header<'a>(request: &'a Request, name: &'static str) -> Option<&'a str> {
+    request.headers()
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.as_str())
+}
+
+// Seuls `127.0.0.1:<port>` et `localhost:<port>` sont acceptés
+fn is_local_host(host: &str, port: u16) -> bool {
+    let Some((name, host_port)) = host.trim().rsplit_once(':') else {
+        return false;
+    };
+    host_port.parse() == Ok(port) && (name == "127.0.0.1" || name.eq_ignore_ascii_case("localhost"))
+}
+
+// 128 bits aléatoires en hexadécimal
+fn generate_token() -> String {
+    let mut bytes = [0u8; 16];
+    if let Err(e) = getrandom::getrandom(&mut bytes) {
+        eprintln!("Jeton de l'API non généré: {}", e);
+        return String::new();
+    }
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn parse_url(url: &str) -> (String, HashMap<String, String>) {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let params = form_urlencoded::parse(query.as_bytes())
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    (path.trim_end_matches('/').to_string(), params)
+}
+
+fn param(params: &HashMap<String, String>, name: &str, default: usize) -> usize {
+    params.get(name).and_then(|value| value.parse().ok()).unwrap_or(default)
+}
+
+fn respond(request: Request, status: u16, body: serde_json::Value) {
+    let header = Header::from_bytes("Content-Type", "application/json; charset=utf-8").unwrap();
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(header);
+    if let Err(e) = request.respond(response) {
+        eprintln!("Réponse HTTP non envoyée: {}", e);
+    }
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_local_host_exige_l_adresse_et_le_port() {
+        assert!(is_local_host("127.0.0.1:8765", 8765));
+        assert!(is_local_host("localhost:8765", 8765));
+        assert!(is_local_host("LocalHost:8765", 8765));
+        assert!(!is_local_host("127.0.0.1:8766", 8765));
+        assert!(!is_local_host("127.0.0.1", 8765));
+        assert!(!is_local_host("localhost", 8765));
+        assert!(!is_local_host("evil.example:8765", 8765));
+        assert!(!is_local_host("localhost.evil.example:8765", 8765));
+        assert!(!is_local_host("", 8765));
+    }
+
+    #[test]
+    fn generate_token_est_aleatoire() {
+        let token = generate_token();
+        assert_eq!(token.len(), 32);
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(token, generate_token());
+    }
+
+    #[test]
+    fn parse_url_decode_les_parametres() {
+        let (path, params) = parse_url("/search/?q=r%C3%A9sum%C3%A9+2024&profile=Projets");
+        assert_eq!(path, "/search");
+        assert_eq!(params["q"], "résumé 2024");
+        assert_eq!(params["profile"], "Projets");
+    }
+
+    #[test]
+    fn is_authorized_n_accepte_que_l_en_tete_bearer() {
+        let token = "0123456789abcdef";
+        assert!(is_authorized(Some("Bearer 0123456789abcdef"), token));
+        assert!(!is_authorized(Some("Bearer 0123456789abcdeF"), token));
+        assert!(!is_authorized(Some("Bearer 0123456789abcde"), token));
+        assert!(!is_authorized(Some("Bearer 0123456789abcdef0"), token));
+        assert!(!is_authorized(Some("0123456789abcdef"), token));
+        assert!(!is_authorized(None, token));
+    }
+}
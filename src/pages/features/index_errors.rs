@@ -0,0 +1,177 @@
+use crate::utils::csv_field;
+use serde::{Serialize, Deserialize};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::io;
+use std::path::Path;
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IndexErrorKind {
+    PermissionDenied,
+    NotFound,
+    Loop,
+    Other,
+}
+
+impl IndexErrorKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            IndexErrorKind::PermissionDenied => "accès refusé",
+            IndexErrorKind::NotFound => "introuvable",
+            IndexErrorKind::Loop => "boucle de liens",
+            IndexErrorKind::Other => "erreur d'E/S",
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct IndexError {
+    pub path: String,
+    pub kind: IndexErrorKind,
+    pub reason: String,
+}
+
+impl IndexError {
+    pub fn from_walk_error(error: &ignore::Error) -> Self {
+        let path = walk_error_path(error).unwrap_or_default();
+
+        let kind = match error.io_error().map(|e| e.kind()) {
+            _ if matches!(unwrap_walk_error(error), ignore::Error::Loop { .. }) => IndexErrorKind::Loop,
+            Some(io::ErrorKind::PermissionDenied) => IndexErrorKind::PermissionDenied,
+            Some(io::ErrorKind::NotFound) => IndexErrorKind::NotFound,
+            _ => IndexErrorKind::Other,
+        };
+
+        // Le message d'ignore répète le chemin, on ne garde que la cause
+        let reason = match unwrap_walk_error(error) {
+            ignore::Error::Io(e) => e.to_string(),
+            other => other.to_string(),
+        };
+
+        Self { path, kind, reason }
+    }
+}
+
+fn unwrap_walk_error(error: &ignore::Error) -> &ignore::Error {
+    match error {
+        ignore::Error::WithPath { err, .. }
+        | ignore::Error::WithDepth { err, .. }
+        | ignore::Error::WithLineNumber { err, .. } => unwrap_walk_error(err),
+        other => other,
+    }
+}
+
+fn walk_error_path(error: &ignore::Error) -> Option<String> {
+    match error {
+        ignore::Error::WithPath { path, .. } => Some(path.to_string_lossy().to_string()),
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => walk_error_path(err),
+        ignore::Error::Loop { child, .. } => Some(child.to_string_lossy().to_string()),
+        ignore::Error::Partial(errors) => errors.first().and_then(walk_error_path),
+        _ => None,
+    }
+}
+
+static INDEX_ERRORS: Lazy<Mutex<Vec<IndexError>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+pub fn record_index_error(error: IndexError) {
+    INDEX_ERRORS.lock().push(error);
+}
+
+pub fn index_errors() -> Vec<IndexError> {
+    INDEX_ERRORS.lock().clone()
+}
+
+pub fn clear_index_errors() {
+    INDEX_ERRORS.lock().clear();
+}
+
+// Retire les erreurs situées sous une des racines (réindexées ou réessayées)
+pub fn forget_index_errors_under(roots: &[String]) {
+    INDEX_ERRORS.lock().retain(|error| !roots.iter().any(|root| Path::new(&error.path).starts_with(root)));
+}
+
+// Ex: "12 chemins n'ont pas pu être indexés (10 accès refusé, 2 erreur d'E/S)"
+pub fn summarize(errors: &[IndexError]) -> String {
+    let kinds = [
+        IndexErrorKind::PermissionDenied,
+        IndexErrorKind::NotFound,
+        IndexErrorKind::Loop,
+        IndexErrorKind::Other,
+    ];
+    let details: Vec<String> = kinds.iter()
+        .map(|kind| (kind, errors.iter().filter(|e| e.kind == *kind).count()))
+        .filter(|(_, count)| *count > 0)
+        .map(|(kind, count)| format!("{} {}", count, kind.label()))
+        .collect();
+
+    format!("{} chemins n'ont pas pu être indexés ({})", errors.len(), details.join(", "))
+}
+
+pub fn errors_to_csv(errors: &[IndexError]) -> String {
+    let mut csv = String::from("chemin,type,raison\n");
+    for error in errors {
+        csv.push_str(&format!(
+            "{},{},{}\n",
+            csv_field(&error.path),
+            error.kind.label(),
+            csv_field(&error.reason)
+        ));
+    }
+    csv
+}
+
+pub fn errors_to_json(errors: &[IndexError]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn error(path: &str, kind: IndexErrorKind, reason: &str) -> IndexError {
+        IndexError { path: path.to_string(), kind, reason: reason.to_string() }
+    }
+
+    #[test]
+    fn from_walk_error_garde_le_chemin_et_la_cause() {
+        let walk_error = ignore::Error::WithDepth {
+            depth: 2,
+            err: Box::new(ignore::Error::WithPath {
+                path: PathBuf::from("/home/léa/privé"),
+                err: Box::new(ignore::Error::Io(io::Error::new(io::ErrorKind::PermissionDenied, "accès refusé"))),
+            }),
+        };
+        let error = IndexError::from_walk_error(&walk_error);
+        assert_eq!(error.path, "/home/léa/privé");
+        assert!(error.kind == IndexErrorKind::PermissionDenied);
+        assert_eq!(error.reason, "accès refusé");
+
+        let loop_error = ignore::Error::Loop {
+            ancestor: PathBuf::from("/données"),
+            child: PathBuf::from("/données/boucle"),
+        };
+        let error = IndexError::from_walk_error(&loop_error);
+        assert_eq!(error.path, "/données/boucle");
+        assert!(error.kind == IndexErrorKind::Loop);
+    }
+
+    #[test]
+    fn summarize_compte_les_erreurs_par_type() {
+        let errors = vec![
+            error("/a", IndexErrorKind::Other, "e"),
+            error("/b", IndexErrorKind::PermissionDenied, "e"),
+            error("/c", IndexErrorKind::PermissionDenied, "e"),
+        ];
+        assert_eq!(summarize(&errors), "3 chemins n'ont pas pu être indexés (2 accès refusé, 1 erreur d'E/S)");
+    }
+
+    #[test]
+    fn errors_to_csv_echappe_les_champs() {
+        let errors = vec![error("/dossier, été", IndexErrorKind::NotFound, "fichier \"absent\"")];
+        assert_eq!(
+            errors_to_csv(&errors),
+            "chemin,type,raison\n\"/dossier, été\",introuvable,\"fichier \"\"absent\"\"\"\n"
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use crate::utils::{load_json, save_json};
+use serde::{Serialize, Deserialize};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use std::io;
+
+const INDEX_OPTIONS_FILE: &str = "index_options.json";
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum IndexProfile {
+    // Tous les cœurs, disques parcourus en parallèle
+    Fast,
+    #[default]
+    Balanced,
+    // Un seul thread, débit limité : la machine reste utilisable
+    Background,
+}
+
+impl IndexProfile {
+    // Même ordre que la liste déroulante des paramètres
+    pub fn from_index(index: i32) -> Self {
+        match index {
+            0 => IndexProfile::Fast,
+            2 => IndexProfile::Background,
+            _ => IndexProfile::Balanced,
+        }
+    }
+
+    pub fn index(&self) -> i32 {
+        match self {
+            IndexProfile::Fast => 0,
+            IndexProfile::Balanced => 1,
+            IndexProfile::Background => 2,
+        }
+    }
+
+    // Threads du walker pour chaque disque
+    pub fn threads(&self) -> usize {
+        match self {
+            IndexProfile::Fast => num_cpus::get(),
+            IndexProfile::Balanced => (num_cpus::get() / 2).max(1),
+            IndexProfile::Background => 1,
+        }
+    }
+
+    pub fn files_per_second(&self) -> Option<u64> {
+        match self {
+            IndexProfile::Background => Some(1_000),
+            _ => None,
+        }
+    }
+
+    // Priorité CPU et E/S abaissée pour les threads d'indexation
+    pub fn low_priority(&self) -> bool {
+        *self != IndexProfile::Fast
+    }
+
+    pub fn parallel_drives(&self) -> bool {
+        *self == IndexProfile::Fast
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct IndexOptions {
+    // Suivre les liens symboliques (les boucles sont détectées par le walker)
+    pub follow_links: bool,
+    // Descendre dans les points de montage d'autres systèmes de fichiers
+    pub cross_filesystems: bool,
+    #[serde(default)]
+    pub profile: IndexProfile,
+    // Mettre l'indexation en pause tant que le système est utilisé
+    #[serde(default)]
+    pub only_when_idle: bool,
+    // Vérifier les résultats sur le disque avant de les afficher
+    #[serde(default)]
+    pub verify_results: bool,
+    // Indexer le contenu des archives zip, tar, tar.gz et 7z
+    #[serde(default)]
+    pub index_archives: bool,
+}
+
+static INDEX_OPTIONS: Lazy<RwLock<IndexOptions>> = Lazy::new(|| {
+    RwLock::new(load_json(INDEX_OPTIONS_FILE).unwrap_or_default())
+});
+
+pub fn index_options() -> IndexOptions {
+    INDEX_OPTIONS.read().clone()
+}
+
+pub fn set_index_options(options: IndexOptions) -> io::Result<()> {
+    save_json(INDEX_OPTIONS_FILE, &options)?;
+    *INDEX_OPTIONS.write() = options;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_profile_suit_l_ordre_de_la_liste() {
+        for profile in [IndexProfile::Fast, IndexProfile::Balanced, IndexProfile::Background] {
+            assert!(IndexProfile::from_index(profile.index()) == profile);
+        }
+        assert!(IndexProfile::from_index(-1) == IndexProfile::Balanced);
+    }
+
+    #[test]
+    fn index_options_anciens_fichiers_gardent_les_valeurs_par_defaut() {
+        let options: IndexOptions = serde_json::from_str(r#"{"follow_links":true,"cross_filesystems":false}"#).unwrap();
+        assert!(options.follow_links);
+        assert!(options.profile == IndexProfile::Balanced);
+        assert!(!options.only_when_idle && !options.index_archives);
+    }
+}
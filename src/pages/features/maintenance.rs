@@ -0,0 +1,296 @@
+use crate::utils::{get_data_dir, load_json, save_json};
+use serde::{Serialize, Deserialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const PROTECTED_PATHS_FILE: &str = "protected_paths.json";
+
+// Arborescences système jamais proposées au nettoyage
+#[cfg(windows)]
+const SYSTEM_PATHS: &[&str] = &[
+    r"C:\Windows",
+    r"C:\Program Files",
+    r"C:\Program Files (x86)",
+    r"C:\ProgramData",
+    r"C:\$Recycle.Bin",
+    r"C:\System Volume Information",
+];
+#[cfg(target_os = "macos")]
+const SYSTEM_PATHS: &[&str] = &["/System", "/Library", "/Applications", "/bin", "/sbin", "/usr", "/private", "/dev"];
+#[cfg(all(unix, not(target_os = "macos")))]
+const SYSTEM_PATHS: &[&str] = &[
+    "/bin", "/boot", "/dev", "/etc", "/lib", "/lib32", "/lib64", "/opt",
+    "/proc", "/run", "/sbin", "/snap", "/sys", "/usr", "/var",
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CleanupKind {
+    // Dossier vide ou ne contenant que des dossiers vides
+    EmptyFolder,
+    EmptyFile,
+    BrokenLink,
+}
+
+impl CleanupKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CleanupKind::EmptyFolder => "dossier vide",
+            CleanupKind::EmptyFile => "fichier vide",
+            CleanupKind::BrokenLink => "lien cassé",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CleanupCandidate {
+    pub path: String,
+    pub kind: CleanupKind,
+}
+
+pub struct CleanupScan {
+    pub candidates: Vec<CleanupCandidate>,
+    // Entrées écartées parce que protégées
+    pub protected: usize,
+}
+
+#[derive(Default)]
+pub struct CleanupReport {
+    pub removed: Vec<String>,
+    pub failed: Vec<(String, String)>,
+}
+
+// Chemins protégés ajoutés par l'utilisateur, en plus des dossiers système
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct ProtectedPaths {
+    pub paths: Vec<String>,
+}
+
+impl ProtectedPaths {
+    pub fn load() -> Self {
+        load_json(PROTECTED_PATHS_FILE).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        save_json(PROTECTED_PATHS_FILE, self)
+    }
+
+    pub fn is_protected(&self, path: &str) -> bool {
+        let path = Path::new(path);
+
+        // Racines de volumes, dossier personnel et données de SupTool
+        if path.parent().is_none()
+            || dirs::home_dir().is_some_and(|home| home == path)
+            || path.starts_with(get_data_dir())
+        {
+            return true;
+        }
+
+        SYSTEM_PATHS.iter()
+            .map(Path::new)
+            .chain(self.paths.iter().map(Path::new))
+            .any(|protected| path.starts_with(protected))
+    }
+}
+
+// `entries` : (chemin, dossier, taille, lien symbolique) pour chaque entrée de l'index.
+// Les candidats sont revérifiés sur le disque : l'index peut dater, et il ne
+// contient pas les fichiers exclus de l'indexation.
+pub fn find_candidates<I>(entries: I, protected: &ProtectedPaths) -> CleanupScan
+where I: IntoIterator<Item = (String, bool, u64, bool)> {
+    let entries: Vec<(String, bool, u64, bool)> = entries.into_iter().collect();
+    let dirs: HashSet<&str> = entries.iter()
+        .filter(|(_, is_dir, _, _)| *is_dir)
+        .map(|(path, _, _, _)| path.as_str())
+        .collect();
+
+    // Un dossier qui contient un fichier (même vide) n'est pas vide, ses parents non plus
+    let mut non_empty: HashSet<&str> = HashSet::new();
+    for (path, is_dir, _, _) in &entries {
+        if *is_dir {
+            continue;
+        }
+        for ancestor in Path::new(path).ancestors().skip(1) {
+            let Some(ancestor) = ancestor.to_str().and_then(|a| dirs.get(a)) else { break };
+            if !non_empty.insert(*ancestor) {
+                break;
+            }
+        }
+    }
+
+    let is_empty_dir = |path: &str| dirs.contains(path) && !non_empty.contains(path);
+    let mut candidates = Vec::new();
+
+    for (path, is_dir, size, is_link) in &entries {
+        let kind = if *is_dir {
+            // Seul le dossier vide le plus haut est proposé, son contenu part avec lui.
+            // Les racines indexées (sans parent dans l'index) ne sont jamais proposées.
+            let parent = Path::new(path).parent().and_then(Path::to_str);
+            match parent {
+                Some(parent) if dirs.contains(parent) && !is_empty_dir(parent) && is_empty_dir(path) => CleanupKind::EmptyFolder,
+                _ => continue,
+            }
+        } else if *is_link {
+            CleanupKind::BrokenLink
+        } else if *size == 0 {
+            CleanupKind::EmptyFile
+        } else {
+            continue;
+        };
+
+        if still_matches(path, kind) {
+            candidates.push(CleanupCandidate { path: path.clone(), kind });
+        }
+    }
+
+    let before = candidates.len();
+    candidates.retain(|candidate| !protected.is_protected(&candidate.path));
+    let protected = before - candidates.len();
+
+    candidates.sort_by(|a, b| a.path.cmp(&b.path));
+    CleanupScan { candidates, protected }
+}
+
+// Supprime (ou met à la corbeille) les candidats encore valides sur le disque
+pub fn remove_candidates(candidates: &[CleanupCandidate], to_trash: bool, protected: &ProtectedPaths) -> CleanupReport {
+    let mut report = CleanupReport::default();
+
+    for candidate in candidates {
+        let result = if protected.is_protected(&candidate.path) {
+            Err(io::Error::new(io::ErrorKind::PermissionDenied, "Chemin protégé"))
+        } else if !still_matches(&candidate.path, candidate.kind) {
+            Err(io::Error::other(format!("Ne correspond plus : {}", candidate.kind.label())))
+        } else if to_trash {
+            trash::delete(&candidate.path).map_err(io::Error::other)
+        } else if candidate.kind == CleanupKind::EmptyFolder {
+            remove_empty_dir(Path::new(&candidate.path))
+        } else {
+            fs::remove_file(&candidate.path)
+        };
+
+        match result {
+            Ok(()) => report.removed.push(candidate.path.clone()),
+            Err(e) => report.failed.push((candidate.path.clone(), e.to_string())),
+        }
+    }
+
+    report
+}
+
+fn still_matches(path: &str, kind: CleanupKind) -> bool {
+    let path = Path::new(path);
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return false;
+    };
+
+    match kind {
+        CleanupKind::EmptyFolder => metadata.is_dir() && dir_is_empty(path),
+        CleanupKind::EmptyFile => metadata.is_file() && metadata.len() == 0,
+        // Lien dont la cible n'existe plus
+        CleanupKind::BrokenLink => metadata.file_type().is_symlink() && fs::metadata(path).is_err(),
+    }
+}
+
+// Vrai si le dossier ne contient que des dossiers vides (liens non suivis)
+fn dir_is_empty(path: &Path) -> bool {
+    let Ok(entries) = fs::read_dir(path) else {
+        return false;
+    };
+
+    entries.into_iter().all(|entry| {
+        entry.is_ok_and(|entry| {
+            entry.file_type().is_ok_and(|file_type| file_type.is_dir()) && dir_is_empty(&entry.path())
+        })
+    })
+}
+
+// remove_dir échoue sur un dossier non vide : rien d'autre ne peut partir
+fn remove_empty_dir(path: &Path) -> io::Result<()> {
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            remove_empty_dir(&entry.path())?;
+        }
+    }
+    fs::remove_dir(path)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn sample_tree(name: &str) -> (PathBuf, Vec<(String, bool, u64, bool)>) {
+        let root = std::env::temp_dir().join(format!("suptool-maintenance-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("vide").join("sous")).unwrap();
+        fs::create_dir_all(root.join("plein")).unwrap();
+        fs::write(root.join("plein").join("été.txt"), "contenu").unwrap();
+        fs::write(root.join("zéro.txt"), "").unwrap();
+        std::os::unix::fs::symlink(root.join("absent"), root.join("lien")).unwrap();
+
+        let path = |relative: &str| root.join(relative).to_string_lossy().to_string();
+        let entries = vec![
+            (root.to_string_lossy().to_string(), true, 0, false),
+            (path("vide"), true, 0, false),
+            (path("vide/sous"), true, 0, false),
+            (path("plein"), true, 0, false),
+            (path("plein/été.txt"), false, 7, false),
+            (path("zéro.txt"), false, 0, false),
+            (path("lien"), false, 0, true),
+        ];
+        (root, entries)
+    }
+
+    #[test]
+    fn find_candidates_propose_le_dossier_vide_le_plus_haut() {
+        let (root, entries) = sample_tree("scan");
+        let scan = find_candidates(entries, &ProtectedPaths::default());
+
+        let found: Vec<(PathBuf, CleanupKind)> = scan.candidates.iter()
+            .map(|c| (PathBuf::from(&c.path), c.kind))
+            .collect();
+        assert!(found == vec![
+            (root.join("lien"), CleanupKind::BrokenLink),
+            (root.join("vide"), CleanupKind::EmptyFolder),
+            (root.join("zéro.txt"), CleanupKind::EmptyFile),
+        ]);
+        assert_eq!(scan.protected, 0);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn find_candidates_ecarte_les_chemins_proteges() {
+        let (root, entries) = sample_tree("protege");
+        let protected = ProtectedPaths { paths: vec![root.join("vide").to_string_lossy().to_string()] };
+        let scan = find_candidates(entries, &protected);
+
+        assert_eq!(scan.candidates.len(), 2);
+        assert_eq!(scan.protected, 1);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn remove_candidates_reverifie_avant_de_supprimer() {
+        let (root, entries) = sample_tree("suppression");
+        let candidates = find_candidates(entries, &ProtectedPaths::default()).candidates;
+        // Le fichier n'est plus vide depuis le scan
+        fs::write(root.join("zéro.txt"), "modifié").unwrap();
+
+        let report = remove_candidates(&candidates, false, &ProtectedPaths::default());
+        assert_eq!(report.removed.len(), 2);
+        assert_eq!(report.failed.len(), 1);
+        assert!(!root.join("vide").exists() && root.join("zéro.txt").exists());
+        assert!(fs::symlink_metadata(root.join("lien")).is_err());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn is_protected_couvre_les_dossiers_systeme_et_les_racines() {
+        let protected = ProtectedPaths::default();
+        assert!(protected.is_protected("/"));
+        assert!(protected.is_protected("/usr/share/vide"));
+        assert!(!protected.is_protected("/usrlocal/vide"));
+    }
+}
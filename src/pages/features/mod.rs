@@ -1,2 +1,47 @@
 mod searcher;
-pub use searcher::FileSearcher;
\ No newline at end of file
+mod catalog;
+mod mounts;
+mod query;
+mod suggest;
+mod snapshot;
+mod history;
+mod archive;
+mod highlight;
+mod preview;
+mod columns;
+mod grouping;
+mod index_options;
+mod index_errors;
+mod pacing;
+mod schedule;
+mod profiles;
+mod http_api;
+mod maintenance;
+mod audit;
+mod tags;
+mod timeline;
+mod checksum;
+#[cfg(target_os = "linux")]
+mod search_provider;
+pub use searcher::{run_index_helper, FileSearcher, SearchMatch, MAX_RESULTS};
+pub use mounts::{spawn_mount_monitor, MountEvent};
+pub use snapshot::SnapshotDiff;
+pub use history::{SearchHistory, SavedSearch};
+pub use highlight::TokenKind;
+pub use preview::{load_preview, FilePreview, PreviewContent};
+pub use archive::{extract_member, seven_zip_missing, split_member_path};
+pub use columns::{column_settings, update_column_settings, Column, ColumnSettings};
+pub use query::extension_of;
+pub use grouping::{group_results, GroupBy};
+pub use index_options::{index_options, set_index_options, IndexOptions, IndexProfile};
+pub use index_errors::{summarize, errors_to_csv, errors_to_json, IndexError};
+pub use schedule::{spawn_index_scheduler, IndexSchedule};
+pub use profiles::NamedProfile;
+pub use http_api::{api_settings, set_api_settings, ApiSettings, HttpApi};
+pub use tags::{file_tags, set_file_tags};
+pub use audit::{findings_to_csv, findings_to_json, AuditFinding, Severity};
+pub use checksum::{compute_checksums, find_sidecar, matching_algorithm, Checksum, HashAlgorithm};
+pub use timeline::{merge_entries, timeline_roots, watch_roots, TimeWindow, TimelineEntry, TimelineFilter};
+pub use maintenance::{remove_candidates, CleanupCandidate, CleanupKind, ProtectedPaths};
+#[cfg(target_os = "linux")]
+pub use search_provider::{run_search_provider, spawn_search_provider};
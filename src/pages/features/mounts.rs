@@ -0,0 +1,95 @@
+use super::catalog;
+use crate::utils::VolumeInfo;
+use std::thread;
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+pub enum MountEvent {
+    Mounted(VolumeInfo),
+    Unmounted(VolumeInfo),
+}
+
+// Surveille les volumes montés (/proc/self/mountinfo sous Linux, lettres de
+// lecteur sous Windows) et appelle `on_event` pour chaque changement
+pub fn spawn_mount_monitor<F>(on_event: F)
+where F: Fn(MountEvent) + Send + 'static {
+    thread::spawn(move || {
+        let mut known = catalog::refresh_mounted_volumes();
+        #[cfg(not(windows))]
+        let mut last_mountinfo = read_mountinfo();
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            // Relire le fichier brut est bien moins coûteux que de résoudre les labels
+            #[cfg(not(windows))]
+            {
+                let mountinfo = read_mountinfo();
+                if mountinfo == last_mountinfo {
+                    continue;
+                }
+                last_mountinfo = mountinfo;
+            }
+
+            let current = catalog::refresh_mounted_volumes();
+            volume_changes(&known, &current).into_iter().for_each(&on_event);
+            known = current;
+        }
+    });
+}
+
+// Démontages d'abord, puis montages
+fn volume_changes(known: &[VolumeInfo], current: &[VolumeInfo]) -> Vec<MountEvent> {
+    let mut events = Vec::new();
+    for volume in known.iter().filter(|v| !current.contains(v)) {
+        println!("⏏️ Volume démonté: {}", volume.mount_point);
+        events.push(MountEvent::Unmounted(volume.clone()));
+    }
+    for volume in current.iter().filter(|v| !known.contains(v)) {
+        println!("💽 Volume monté: {}", volume.mount_point);
+        events.push(MountEvent::Mounted(volume.clone()));
+    }
+    events
+}
+
+#[cfg(not(windows))]
+fn read_mountinfo() -> String {
+    std::fs::read_to_string("/proc/self/mountinfo").unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn volume(label: &str, mount_point: &str) -> VolumeInfo {
+        VolumeInfo {
+            label: label.to_string(),
+            uuid: format!("uuid-{}", label),
+            mount_point: mount_point.to_string(),
+            removable: true,
+        }
+    }
+
+    #[test]
+    fn volume_changes_signale_demontages_puis_montages() {
+        let cle = volume("Clé", "/media/clé");
+        let disque = volume("Sauvegardes", "/media/sauvegardes");
+        let photos = volume("Photos", "/media/photos");
+
+        let events = volume_changes(&[cle.clone(), disque.clone()], &[disque, photos.clone()]);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], MountEvent::Unmounted(v) if *v == cle));
+        assert!(matches!(&events[1], MountEvent::Mounted(v) if *v == photos));
+    }
+
+    #[test]
+    fn volume_changes_voit_un_volume_remonte_ailleurs() {
+        let avant = volume("Clé", "/media/clé");
+        let apres = volume("Clé", "/run/media/clé");
+
+        let events = volume_changes(&[avant], &[apres]);
+        assert!(matches!(events.as_slice(), [MountEvent::Unmounted(_), MountEvent::Mounted(v)] if v.mount_point == "/run/media/clé"));
+        assert!(volume_changes(&[], &[]).is_empty());
+    }
+}
@@ -0,0 +1,131 @@
+use super::index_options::IndexOptions;
+use crate::utils::system_is_idle;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Fichiers traités entre deux vérifications de l'inactivité du système
+const IDLE_CHECK_EVERY: u64 = 1_000;
+const IDLE_RECHECK_DELAY: Duration = Duration::from_secs(5);
+const PAUSE_STEP: Duration = Duration::from_millis(250);
+
+// Cadence l'indexation selon le profil : limite de débit et pause tant que le
+// système est utilisé. Partagé par tous les threads du walker.
+pub struct Pacer {
+    files_per_second: Option<u64>,
+    only_when_idle: bool,
+    threads: usize,
+    start: Instant,
+    count: AtomicU64,
+    // Temps passé en pause, exclu du calcul du débit
+    paused_ms: AtomicU64,
+    paused: AtomicBool,
+}
+
+impl Pacer {
+    pub fn new(options: &IndexOptions) -> Self {
+        Self {
+            files_per_second: options.profile.files_per_second(),
+            only_when_idle: options.only_when_idle,
+            threads: options.profile.threads(),
+            start: Instant::now(),
+            count: AtomicU64::new(0),
+            paused_ms: AtomicU64::new(0),
+            paused: AtomicBool::new(false),
+        }
+    }
+
+    // À appeler avant chaque fichier. Renvoie false si l'indexation a été
+    // annulée pendant une pause.
+    pub fn pace(&self, should_cancel: &dyn Fn() -> bool) -> bool {
+        let count = self.count.fetch_add(1, Ordering::Relaxed);
+
+        if self.only_when_idle {
+            if count.is_multiple_of(IDLE_CHECK_EVERY) && !system_is_idle(self.threads) {
+                if !self.pause_until_idle(should_cancel) {
+                    return false;
+                }
+            } else if self.paused.load(Ordering::Relaxed) && !self.wait_resume(should_cancel) {
+                return false;
+            }
+        }
+
+        if let Some(rate) = self.files_per_second {
+            let expected = Duration::from_secs_f64(count as f64 / rate as f64);
+            let elapsed = self.start.elapsed()
+                .saturating_sub(Duration::from_millis(self.paused_ms.load(Ordering::Relaxed)));
+            if expected > elapsed {
+                thread::sleep(expected - elapsed);
+            }
+        }
+
+        true
+    }
+
+    fn pause_until_idle(&self, should_cancel: &dyn Fn() -> bool) -> bool {
+        // Un autre thread surveille déjà l'inactivité
+        if self.paused.swap(true, Ordering::SeqCst) {
+            return self.wait_resume(should_cancel);
+        }
+
+        println!("💤 Indexation en pause, système occupé");
+        let pause_start = Instant::now();
+        let mut last_check = Instant::now();
+
+        let resumed = loop {
+            if should_cancel() {
+                break false;
+            }
+            if last_check.elapsed() >= IDLE_RECHECK_DELAY {
+                if system_is_idle(self.threads) {
+                    break true;
+                }
+                last_check = Instant::now();
+            }
+            thread::sleep(PAUSE_STEP);
+        };
+
+        self.paused_ms.fetch_add(pause_start.elapsed().as_millis() as u64, Ordering::Relaxed);
+        self.paused.store(false, Ordering::SeqCst);
+        if resumed {
+            println!("▶️ Reprise de l'indexation");
+        }
+        resumed
+    }
+
+    fn wait_resume(&self, should_cancel: &dyn Fn() -> bool) -> bool {
+        while self.paused.load(Ordering::SeqCst) {
+            if should_cancel() {
+                return false;
+            }
+            thread::sleep(PAUSE_STEP);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::index_options::IndexProfile;
+
+    fn pace_files(profile: IndexProfile, files: u64) -> Duration {
+        let pacer = Pacer::new(&IndexOptions { profile, ..IndexOptions::default() });
+        let start = Instant::now();
+        for _ in 0..files {
+            assert!(pacer.pace(&|| false));
+        }
+        start.elapsed()
+    }
+
+    #[test]
+    fn pace_limite_le_debit_en_arriere_plan() {
+        // 1 000 fichiers par seconde : le 201e arrive après 200 ms
+        assert!(pace_files(IndexProfile::Background, 201) >= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn pace_ne_ralentit_pas_les_autres_profils() {
+        assert!(pace_files(IndexProfile::Fast, 10_000) < Duration::from_millis(200));
+    }
+}
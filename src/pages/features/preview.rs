@@ -0,0 +1,190 @@
+use super::archive::{find_member, list_archive, split_member_path, ArchiveFormat, ArchiveMember};
+use super::highlight::{highlight, HighlightedLine};
+use super::query::extension_of;
+use crate::utils::{file_details, FileDetails};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+const TEXT_PREVIEW_BYTES: u64 = 64 * 1024;
+const ARCHIVE_PREVIEW_ENTRIES: usize = 500;
+const IMAGE_PREVIEW_SIZE: u32 = 1024;
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "bmp", "webp", "ico"];
+
+pub enum PreviewContent {
+    Text {
+        lines: Vec<HighlightedLine>,
+        truncated: bool,
+    },
+    // Pixels RGBA déjà réduits, pour ne pas décoder l'image sur le thread UI
+    Image {
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    },
+    Archive(Vec<ArchiveMember>),
+    // Membre d'une archive indexée : pas de contenu, seulement l'extraction
+    Member {
+        archive: String,
+    },
+    Unavailable(String),
+}
+
+pub struct FilePreview {
+    pub details: FileDetails,
+    pub content: PreviewContent,
+}
+
+pub fn load_preview(path: &Path) -> io::Result<FilePreview> {
+    if let Some((archive, member)) = split_member_path(path) {
+        return load_member_preview(path, &archive, &member);
+    }
+
+    let details = file_details(path)?;
+
+    let content = if details.is_dir {
+        PreviewContent::Unavailable("Dossier".to_string())
+    } else {
+        preview_content(path).unwrap_or_else(|e| PreviewContent::Unavailable(e.to_string()))
+    };
+
+    Ok(FilePreview { details, content })
+}
+
+// Détails du membre, droits et propriétaire étant ceux de l'archive
+fn load_member_preview(path: &Path, archive: &Path, member: &str) -> io::Result<FilePreview> {
+    let entry = find_member(archive, member)?;
+    let archive_details = file_details(archive)?;
+
+    let details = FileDetails {
+        path: path.to_path_buf(),
+        size: entry.size,
+        is_dir: entry.is_dir,
+        created: None,
+        modified: entry.modified.or(archive_details.modified),
+        accessed: None,
+        ..archive_details
+    };
+
+    Ok(FilePreview {
+        details,
+        content: PreviewContent::Member {
+            archive: archive.to_string_lossy().to_string(),
+        },
+    })
+}
+
+fn preview_content(path: &Path) -> io::Result<PreviewContent> {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let extension = extension_of(&name).unwrap_or_default();
+
+    if ArchiveFormat::detect(path).is_some() {
+        return list_archive(path, ARCHIVE_PREVIEW_ENTRIES).map(PreviewContent::Archive);
+    }
+
+    if IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+        return load_image(path);
+    }
+
+    let mut buffer = Vec::new();
+    File::open(path)?.take(TEXT_PREVIEW_BYTES + 1).read_to_end(&mut buffer)?;
+
+    let truncated = buffer.len() as u64 > TEXT_PREVIEW_BYTES;
+    buffer.truncate(TEXT_PREVIEW_BYTES as usize);
+
+    if buffer.contains(&0) {
+        return Ok(PreviewContent::Unavailable("Fichier binaire".to_string()));
+    }
+
+    let text = String::from_utf8_lossy(&buffer).replace('\t', "    ");
+    Ok(PreviewContent::Text {
+        lines: highlight(&text, &extension),
+        truncated,
+    })
+}
+
+fn load_image(path: &Path) -> io::Result<PreviewContent> {
+    let image = image::open(path).map_err(io::Error::other)?;
+
+    let image = if image.width() > IMAGE_PREVIEW_SIZE || image.height() > IMAGE_PREVIEW_SIZE {
+        image.thumbnail(IMAGE_PREVIEW_SIZE, IMAGE_PREVIEW_SIZE)
+    } else {
+        image
+    };
+
+    let rgba = image.to_rgba8();
+    Ok(PreviewContent::Image {
+        width: rgba.width(),
+        height: rgba.height(),
+        rgba: rgba.into_raw(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("suptool-preview-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn preview_content_tronque_les_gros_fichiers_texte() {
+        let dir = temp_dir("texte");
+        let small = dir.join("notes.txt");
+        fs::write(&small, "première ligne\n\tindentée").unwrap();
+        let big = dir.join("journal.log");
+        fs::write(&big, "x".repeat(TEXT_PREVIEW_BYTES as usize + 10)).unwrap();
+
+        match preview_content(&small).unwrap() {
+            PreviewContent::Text { lines, truncated } => {
+                assert!(!truncated);
+                assert_eq!(lines.len(), 2);
+                assert_eq!(lines[1][0].0, "    indentée");
+            }
+            _ => panic!("aperçu texte attendu"),
+        }
+        assert!(matches!(preview_content(&big).unwrap(), PreviewContent::Text { truncated: true, .. }));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn preview_content_ne_montre_pas_les_fichiers_binaires() {
+        let dir = temp_dir("binaire");
+        let file = dir.join("données.dat");
+        fs::write(&file, [0x7f, b'E', b'L', b'F', 0, 1]).unwrap();
+
+        assert!(matches!(preview_content(&file).unwrap(), PreviewContent::Unavailable(message) if message == "Fichier binaire"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn load_preview_liste_une_archive_et_decrit_ses_membres() {
+        let dir = temp_dir("archive");
+        let archive = dir.join("sauvegarde.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&archive).unwrap());
+        zip.start_file("docs/été.txt", zip::write::FileOptions::default()).unwrap();
+        zip.write_all(b"contenu").unwrap();
+        zip.finish().unwrap();
+
+        let preview = load_preview(&archive).unwrap();
+        match preview.content {
+            PreviewContent::Archive(members) => {
+                assert_eq!(members.len(), 1);
+                assert_eq!(members[0].path, "docs/été.txt");
+            }
+            _ => panic!("liste de l'archive attendue"),
+        }
+
+        // Chemin virtuel d'un membre indexé
+        let preview = load_preview(&archive.join("docs").join("été.txt")).unwrap();
+        assert_eq!(preview.details.size, 7);
+        assert!(matches!(preview.content, PreviewContent::Member { archive: path } if path == archive.to_string_lossy()));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
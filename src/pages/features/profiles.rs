@@ -0,0 +1,97 @@
+use super::schedule::IndexSchedule;
+use crate::utils::{load_json, save_json};
+use serde::{Serialize, Deserialize};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use std::io;
+use std::path::Path;
+
+const PROFILES_FILE: &str = "index_profiles.json";
+// Planification globale des versions précédentes, reprise par le profil par défaut
+const LEGACY_SCHEDULE_FILE: &str = "index_schedule.json";
+
+// Index nommé ("Tout le système", "Projets"...), avec ses propres racines,
+// exclusions et planification
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NamedProfile {
+    pub name: String,
+    // Vide = tous les disques
+    pub roots: Vec<String>,
+    // Chemins absolus (dossier exclu avec son contenu) ou motifs glob ("node_modules", "*.tmp")
+    pub exclusions: Vec<String>,
+    #[serde(default)]
+    pub schedule: IndexSchedule,
+}
+
+impl NamedProfile {
+    pub fn whole_system() -> Self {
+        Self {
+            name: "Tout le système".to_string(),
+            roots: Vec::new(),
+            exclusions: Vec::new(),
+            schedule: load_json(LEGACY_SCHEDULE_FILE).unwrap_or_default(),
+        }
+    }
+
+    // Compare par composants : "/data/Projets" ne couvre pas "/data/Projets2"
+    pub fn covers(&self, path: &str) -> bool {
+        self.roots.is_empty() || self.roots.iter().any(|root| Path::new(path).starts_with(root))
+    }
+}
+
+static PROFILES: Lazy<RwLock<Vec<NamedProfile>>> = Lazy::new(|| {
+    let profiles: Vec<NamedProfile> = load_json(PROFILES_FILE).unwrap_or_default();
+    RwLock::new(if profiles.is_empty() { vec![NamedProfile::whole_system()] } else { profiles })
+});
+
+pub fn index_profiles() -> Vec<NamedProfile> {
+    PROFILES.read().clone()
+}
+
+pub fn index_profile(name: &str) -> Option<NamedProfile> {
+    PROFILES.read().iter().find(|profile| profile.name == name).cloned()
+}
+
+pub fn update_index_profiles(update: impl FnOnce(&mut Vec<NamedProfile>) -> io::Result<()>) -> io::Result<Vec<NamedProfile>> {
+    let mut profiles = PROFILES.write();
+    let mut updated = profiles.clone();
+    update(&mut updated)?;
+    save_json(PROFILES_FILE, &updated)?;
+    *profiles = updated;
+    Ok(profiles.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(roots: &[&str]) -> NamedProfile {
+        NamedProfile {
+            name: "Projets".to_string(),
+            roots: roots.iter().map(|root| root.to_string()).collect(),
+            exclusions: Vec::new(),
+            schedule: IndexSchedule::default(),
+        }
+    }
+
+    #[test]
+    fn covers_compare_les_composants() {
+        let profile = profile(&["/data/Projets"]);
+        assert!(profile.covers("/data/Projets"));
+        assert!(profile.covers("/data/Projets/été/notes.txt"));
+        assert!(!profile.covers("/data/Projets2/notes.txt"));
+        assert!(!profile.covers("/data/Proj"));
+        assert!(!profile.covers("/autre/data/Projets"));
+    }
+
+    #[test]
+    fn covers_accepte_une_racine_avec_separateur_final() {
+        assert!(profile(&["/data/Projets/"]).covers("/data/Projets/notes.txt"));
+        assert!(profile(&["/"]).covers("/data/notes.txt"));
+    }
+
+    #[test]
+    fn covers_tout_sans_racine() {
+        assert!(profile(&[]).covers("/n'importe/où"));
+    }
+}
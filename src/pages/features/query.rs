@@ -0,0 +1,193 @@
+use super::searcher::SearchResult;
+use super::tags::has_tag;
+use std::ops::Range;
+
+// Champs reconnus dans la barre de recherche, ex: "facture ext:pdf type:file tag:compta"
+pub const QUERY_FIELDS: &[&str] = &["ext:", "type:", "path:", "volume:", "tag:"];
+pub const TYPE_VALUES: &[&str] = &["file", "folder", "archive"];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    File,
+    Folder,
+    // Membre d'une archive indexée
+    InArchive,
+}
+
+#[derive(Clone, Default)]
+pub struct SearchQuery {
+    pub text: String,
+    pub extensions: Vec<String>,
+    pub kind: Option<EntryKind>,
+    pub path: Option<String>,
+    pub volume: Option<String>,
+    // Tags posés sur le fichier (tous requis)
+    pub tags: Vec<String>,
+}
+
+impl SearchQuery {
+    pub fn parse(input: &str) -> Self {
+        let mut query = Self::default();
+        let mut words = Vec::new();
+
+        for token in input.split_whitespace() {
+            let lower = token.to_lowercase();
+            let Some((field, value)) = lower.split_once(':') else {
+                words.push(lower);
+                continue;
+            };
+
+            match field {
+                "ext" => query.extensions.extend(
+                    value.split(',')
+                        .map(|e| e.trim_start_matches('.'))
+                        .filter(|e| !e.is_empty())
+                        .map(|e| e.to_string())
+                ),
+                "type" => query.kind = match value {
+                    "file" => Some(EntryKind::File),
+                    "folder" | "dir" => Some(EntryKind::Folder),
+                    "archive" => Some(EntryKind::InArchive),
+                    _ => None,
+                },
+                "path" if !value.is_empty() => query.path = Some(value.to_string()),
+                "volume" if !value.is_empty() => query.volume = Some(value.to_string()),
+                "tag" if !value.is_empty() => query.tags.push(value.trim_start_matches('#').to_string()),
+                // Champ inconnu : on le garde comme texte (ex: "C:" ou "12:30")
+                _ => words.push(lower),
+            }
+        }
+
+        query.text = words.join(" ");
+        query
+    }
+
+    pub fn has_filters(&self) -> bool {
+        !self.extensions.is_empty() || self.kind.is_some() || self.path.is_some() || self.volume.is_some()
+            || !self.tags.is_empty()
+    }
+
+    pub fn matches(&self, result: &SearchResult) -> bool {
+        if let Some(kind) = self.kind {
+            let kind_matches = match kind {
+                EntryKind::File => !result.is_dir,
+                EntryKind::Folder => result.is_dir,
+                EntryKind::InArchive => result.archive.is_some(),
+            };
+            if !kind_matches {
+                return false;
+            }
+        }
+
+        if !self.extensions.is_empty() {
+            let extension = extension_of(&result.name);
+            if !self.extensions.iter().any(|e| Some(e.as_str()) == extension.as_deref()) {
+                return false;
+            }
+        }
+
+        if let Some(path) = &self.path {
+            if !result.path.to_lowercase().contains(path) {
+                return false;
+            }
+        }
+
+        if let Some(volume) = &self.volume {
+            if !result.volume.to_lowercase().contains(volume) {
+                return false;
+            }
+        }
+
+        if !self.tags.iter().all(|tag| has_tag(&result.path, tag)) {
+            return false;
+        }
+
+        true
+    }
+}
+
+pub fn extension_of(name: &str) -> Option<String> {
+    let (stem, extension) = name.rsplit_once('.')?;
+    if stem.is_empty() || extension.is_empty() {
+        return None;
+    }
+    Some(extension.to_lowercase())
+}
+
+// Plages (en octets) de `text` où apparaît un des mots, fusionnées et triées.
+// La comparaison ignore la casse, les positions restent celles du texte d'origine.
+pub fn match_spans(text: &str, words: &[&str]) -> Vec<Range<usize>> {
+    let mut lower = String::with_capacity(text.len());
+    let mut origin = Vec::with_capacity(text.len() + 1);
+    for (i, c) in text.char_indices() {
+        for lc in c.to_lowercase() {
+            let start = lower.len();
+            lower.push(lc);
+            origin.extend(std::iter::repeat_n(i, lower.len() - start));
+        }
+    }
+    origin.push(text.len());
+
+    let mut spans: Vec<Range<usize>> = words.iter()
+        .filter(|word| !word.is_empty())
+        .flat_map(|word| lower.match_indices(word)
+            .map(|(pos, _)| {
+                // Fin du caractère d'origine qui contient le dernier octet trouvé
+                // (une majuscule peut donner plusieurs caractères en minuscules)
+                let last = origin[pos + word.len() - 1];
+                let end = last + text[last..].chars().next().map_or(0, char::len_utf8);
+                origin[pos]..end
+            })
+            .collect::<Vec<_>>())
+        .collect();
+    spans.sort_by_key(|span| span.start);
+
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(spans.len());
+    for span in spans {
+        match merged.last_mut() {
+            Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+            _ => merged.push(span),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn highlighted<'a>(text: &'a str, words: &[&str]) -> Vec<&'a str> {
+        match_spans(text, words).into_iter().map(|span| &text[span]).collect()
+    }
+
+    #[test]
+    fn match_spans_donne_des_plages_d_octets_du_texte_d_origine() {
+        assert_eq!(highlighted("Rapport_Annuel.pdf", &["rapport", "annuel"]), vec!["Rapport", "Annuel"]);
+        assert_eq!(highlighted("Été 2024 - été.jpg", &["été"]), vec!["Été", "été"]);
+        assert_eq!(highlighted("Données.xlsx", &["nées"]), vec!["nées"]);
+        assert_eq!(highlighted("日本語のファイル.txt", &["ファイル"]), vec!["ファイル"]);
+    }
+
+    #[test]
+    fn match_spans_fusionne_les_plages_chevauchantes_et_voisines() {
+        assert_eq!(highlighted("abcdef", &["abc", "bcd"]), vec!["abcd"]);
+        assert_eq!(highlighted("abcdef", &["ab", "cd"]), vec!["abcd"]);
+        assert_eq!(highlighted("aaa", &["a"]), vec!["aaa"]);
+    }
+
+    #[test]
+    fn match_spans_reste_valide_quand_la_minuscule_s_allonge() {
+        // "İ" donne "i̇" (deux caractères) en minuscules
+        let text = "İstanbul.png";
+        assert_eq!(highlighted(text, &["i"]), vec!["İ"]);
+        assert_eq!(highlighted(text, &["stan"]), vec!["stan"]);
+        assert_eq!(highlighted(text, &["i̇stan"]), vec!["İstan"]);
+    }
+
+    #[test]
+    fn match_spans_ignore_les_mots_vides_et_absents() {
+        assert!(match_spans("fichier.txt", &[""]).is_empty());
+        assert!(match_spans("fichier.txt", &["zip"]).is_empty());
+        assert!(match_spans("", &["a"]).is_empty());
+    }
+}
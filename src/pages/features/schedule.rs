@@ -0,0 +1,124 @@
+use super::profiles::index_profiles;
+use serde::{Serialize, Deserialize};
+use chrono::{DateTime, Local, NaiveDate};
+use std::collections::HashMap;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct IndexSchedule {
+    pub on_startup: bool,
+    // 0 = désactivé
+    pub every_hours: u32,
+    // Heure et minute de la réindexation quotidienne
+    pub daily_at: Option<(u32, u32)>,
+}
+
+// Dernier déclenchement et dernière réindexation quotidienne d'un profil
+struct ScheduleState {
+    // Une réindexation annulée ne doit pas être relancée aussitôt
+    last_triggered: SystemTime,
+    last_daily_run: Option<NaiveDate>,
+}
+
+impl ScheduleState {
+    fn new(schedule: &IndexSchedule) -> Self {
+        let now = Local::now();
+        Self {
+            last_triggered: SystemTime::now(),
+            // Une heure quotidienne déjà passée au lancement attend le lendemain
+            last_daily_run: schedule.daily_at
+                .filter(|&(hour, minute)| daily_time_passed(now, hour, minute))
+                .map(|_| now.date_naive()),
+        }
+    }
+}
+
+// Appelle `on_due` avec le nom de chaque profil dont la réindexation est
+// prévue. `last_indexed` donne la fin de la dernière indexation complète d'un
+// profil, quelle qu'en soit l'origine.
+pub fn spawn_index_scheduler<L, F>(last_indexed: L, on_due: F)
+where
+    L: Fn(&str) -> Option<SystemTime> + Send + 'static,
+    F: Fn(String) + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut states: HashMap<String, ScheduleState> = HashMap::new();
+
+        for profile in index_profiles() {
+            states.insert(profile.name.clone(), ScheduleState::new(&profile.schedule));
+            if profile.schedule.on_startup {
+                println!("⏰ Réindexation au démarrage ({})", profile.name);
+                on_due(profile.name);
+            }
+        }
+
+        loop {
+            thread::sleep(CHECK_INTERVAL);
+            let now = Local::now();
+
+            for profile in index_profiles() {
+                let schedule = &profile.schedule;
+                let state = states.entry(profile.name.clone())
+                    .or_insert_with(|| ScheduleState::new(schedule));
+                let reference = last_indexed(&profile.name)
+                    .map_or(state.last_triggered, |indexed| indexed.max(state.last_triggered));
+                let mut due = false;
+
+                if schedule.every_hours > 0 {
+                    let interval = Duration::from_secs(schedule.every_hours as u64 * 3600);
+                    due |= reference.elapsed().is_ok_and(|elapsed| elapsed >= interval);
+                }
+
+                if let Some((hour, minute)) = schedule.daily_at {
+                    let today = now.date_naive();
+                    if state.last_daily_run != Some(today) && daily_time_passed(now, hour, minute) {
+                        state.last_daily_run = Some(today);
+                        // Inutile si une indexation a eu lieu depuis l'heure prévue
+                        due |= DateTime::<Local>::from(reference).date_naive() != today
+                            || !daily_time_passed(DateTime::<Local>::from(reference), hour, minute);
+                    }
+                }
+
+                if due {
+                    state.last_triggered = SystemTime::now();
+                    println!("⏰ Réindexation planifiée ({})", profile.name);
+                    on_due(profile.name);
+                }
+            }
+        }
+    });
+}
+
+fn daily_time_passed(now: DateTime<Local>, hour: u32, minute: u32) -> bool {
+    now.date_naive()
+        .and_hms_opt(hour, minute, 0)
+        .is_some_and(|at| now.naive_local() >= at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn daily_time_passed_compare_a_l_heure_du_jour() {
+        let now = Local.with_ymd_and_hms(2024, 5, 15, 14, 30, 0).unwrap();
+        assert!(daily_time_passed(now, 14, 30));
+        assert!(daily_time_passed(now, 3, 0));
+        assert!(!daily_time_passed(now, 14, 31));
+        // Heure invalide : jamais atteinte
+        assert!(!daily_time_passed(now, 25, 0));
+    }
+
+    #[test]
+    fn schedule_state_ne_rattrape_pas_l_heure_deja_passee_au_lancement() {
+        let passed = ScheduleState::new(&IndexSchedule { daily_at: Some((0, 0)), ..IndexSchedule::default() });
+        assert_eq!(passed.last_daily_run, Some(Local::now().date_naive()));
+
+        let disabled = ScheduleState::new(&IndexSchedule::default());
+        assert_eq!(disabled.last_daily_run, None);
+    }
+}
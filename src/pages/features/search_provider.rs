@@ -0,0 +1,402 @@
+// Recherche depuis le lanceur du bureau : org.gnome.Shell.SearchProvider2
+// (GNOME Shell) et org.kde.krunner1 (KRunner), servis sur le bus de session.
+// Le bus utilisé est celui de DBUS_SESSION_BUS_ADDRESS, ce qui permet de
+// tester contre un bus privé (dbus-daemon --session --print-address).
+
+use super::query::extension_of;
+use super::searcher::{FileSearcher, SearchMatch};
+use super::archive::split_member_path;
+use crate::utils::open_path;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::env;
+use std::path::Path;
+use std::process::Command;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use zbus::blocking::connection::Builder;
+use zbus::blocking::Connection;
+use zbus::blocking::fdo::DBusProxy;
+use zbus::fdo::RequestNameFlags;
+use zbus::interface;
+use zbus::zvariant::{OwnedValue, Str};
+
+const BUS_NAME: &str = "io.github.sup2ak.SupTool.SearchProvider";
+const GNOME_OBJECT_PATH: &str = "/io/github/sup2ak/SupTool/SearchProvider";
+const KRUNNER_OBJECT_PATH: &str = "/io/github/sup2ak/SupTool/Runner";
+
+// Les lanceurs n'affichent que les premiers résultats
+const MAX_PROVIDER_RESULTS: usize = 20;
+const MIN_QUERY_LENGTH: usize = 2;
+const OPEN_FOLDER_ACTION: &str = "open-folder";
+
+// Catégories de KRunner (Plasma::QueryMatch::Type)
+const KRUNNER_EXACT_MATCH: i32 = 100;
+const KRUNNER_POSSIBLE_MATCH: i32 = 30;
+
+// Sans lanceur qui l'interroge, le fournisseur sans fenêtre s'arrête : D-Bus
+// le relancera, avec l'index sauvegardé entre-temps
+const HEADLESS_IDLE_EXIT: Duration = Duration::from_secs(5 * 60);
+
+// Les interfaces D-Bus doivent être Sync, pas les rappels vers l'interface Slint
+type LaunchSearch = Mutex<Box<dyn Fn(String) + Send>>;
+type Search = Arc<dyn Fn(&str) -> Vec<SearchMatch> + Send + Sync>;
+
+static LAST_QUERY: Mutex<Option<Instant>> = Mutex::new(None);
+
+struct GnomeSearchProvider {
+    search: Search,
+    on_launch_search: LaunchSearch,
+}
+
+#[interface(name = "org.gnome.Shell.SearchProvider2")]
+impl GnomeSearchProvider {
+    fn get_initial_result_set(&self, terms: Vec<String>) -> Vec<String> {
+        search_paths(&self.search, &terms.join(" "))
+    }
+
+    // Les termes affinent la recherche précédente, mais la relancer reste
+    // plus juste que de filtrer : le classement dépend de toute la requête
+    fn get_subsearch_result_set(&self, _previous_results: Vec<String>, terms: Vec<String>) -> Vec<String> {
+        search_paths(&self.search, &terms.join(" "))
+    }
+
+    fn get_result_metas(&self, identifiers: Vec<String>) -> Vec<HashMap<String, OwnedValue>> {
+        identifiers.into_iter()
+            .map(|path| {
+                let entry = Path::new(&path);
+                let name = entry.file_name()
+                    .map_or_else(|| path.clone(), |name| name.to_string_lossy().into_owned());
+                let parent = entry.parent()
+                    .map(|parent| parent.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let icon = icon_name(&name, entry.is_dir());
+
+                HashMap::from([
+                    ("id".to_string(), string_value(&path)),
+                    ("name".to_string(), string_value(&name)),
+                    ("description".to_string(), string_value(&parent)),
+                    ("gicon".to_string(), string_value(icon)),
+                ])
+            })
+            .collect()
+    }
+
+    fn activate_result(&self, identifier: String, _terms: Vec<String>, _timestamp: u32) {
+        open_result(&identifier);
+    }
+
+    fn launch_search(&self, terms: Vec<String>, _timestamp: u32) {
+        (self.on_launch_search.lock())(terms.join(" "));
+    }
+}
+
+struct KRunner {
+    search: Search,
+}
+
+#[interface(name = "org.kde.krunner1")]
+impl KRunner {
+    fn actions(&self) -> Vec<(String, String, String)> {
+        vec![(
+            OPEN_FOLDER_ACTION.to_string(),
+            "Ouvrir le dossier parent".to_string(),
+            "folder-open".to_string(),
+        )]
+    }
+
+    // (id, texte, icône, type, pertinence, propriétés)
+    #[allow(clippy::type_complexity)]
+    #[zbus(name = "Match")]
+    fn find_matches(&self, query: String) -> Vec<(String, String, String, i32, f64, HashMap<String, OwnedValue>)> {
+        *LAST_QUERY.lock() = Some(Instant::now());
+        if query.trim().chars().count() < MIN_QUERY_LENGTH {
+            return Vec::new();
+        }
+
+        let query_lower = query.trim().to_lowercase();
+        let results = (self.search)(&query);
+        let count = results.len().min(MAX_PROVIDER_RESULTS);
+
+        results.into_iter()
+            .take(MAX_PROVIDER_RESULTS)
+            .enumerate()
+            .map(|(rank, hit)| {
+                let result = hit.result;
+                let kind = if result.name.to_lowercase() == query_lower {
+                    KRUNNER_EXACT_MATCH
+                } else {
+                    KRUNNER_POSSIBLE_MATCH
+                };
+                // Conserve l'ordre de la recherche
+                let relevance = 1.0 - rank as f64 / count as f64;
+                let properties = HashMap::from([
+                    ("subtext".to_string(), string_value(&result.path)),
+                ]);
+                let icon = icon_name(&result.name, result.is_dir).to_string();
+                (result.path, result.name, icon, kind, relevance, properties)
+            })
+            .collect()
+    }
+
+    fn run(&self, match_id: String, action_id: String) {
+        if action_id == OPEN_FOLDER_ACTION {
+            if let Some(parent) = Path::new(&match_id).parent() {
+                open_result(&parent.to_string_lossy());
+            }
+        } else {
+            open_result(&match_id);
+        }
+    }
+}
+
+// Publie les deux interfaces sur le bus de session. `on_launch_search` est
+// appelé quand GNOME demande d'ouvrir la recherche dans l'application. Le nom
+// est repris au fournisseur sans fenêtre s'il tourne encore.
+pub fn spawn_search_provider<F>(searcher: Arc<FileSearcher>, on_launch_search: F)
+where F: Fn(String) + Send + 'static {
+    thread::spawn(move || {
+        let connection = Builder::session()
+            .and_then(|builder| builder.name(BUS_NAME))
+            .and_then(|builder| serve_providers(builder, searcher_search(searcher), on_launch_search));
+
+        match connection {
+            Ok(_connection) => {
+                println!("🔎 Fournisseur de recherche D-Bus: {}", BUS_NAME);
+                // La connexion traite les appels dans ses propres threads tant qu'elle existe
+                loop {
+                    thread::park();
+                }
+            }
+            Err(e) => eprintln!("Fournisseur de recherche D-Bus indisponible: {}", e),
+        }
+    });
+}
+
+// `suptool --search-provider`, lancé par D-Bus (voir installer/linux) quand un
+// lanceur cherche alors que l'application n'est pas ouverte : sans fenêtre,
+// dans les index sauvegardés. Il cède le nom à l'application dès qu'elle
+// démarre, et s'arrête après quelques minutes sans recherche.
+pub fn run_search_provider() -> zbus::Result<()> {
+    let searcher = Arc::new(FileSearcher::new());
+    for profile in searcher.profiles() {
+        if !searcher.load_saved_index(&profile.name) {
+            eprintln!("Aucun index sauvegardé pour le profil {}", profile.name);
+        }
+    }
+
+    let connection = serve_providers(Builder::session()?, searcher_search(searcher), |_query| {
+        // Ouvre l'application
+        if let Err(e) = env::current_exe().and_then(|exe| Command::new(exe).spawn()) {
+            eprintln!("Impossible de lancer SupTool: {}", e);
+        }
+    })?;
+
+    // Abonnement avant la demande du nom, pour ne pas manquer sa perte
+    let name_lost = DBusProxy::new(&connection)?.receive_name_lost()?;
+    connection.request_name_with_flags(BUS_NAME, RequestNameFlags::AllowReplacement | RequestNameFlags::DoNotQueue)?;
+    println!("🔎 Fournisseur de recherche D-Bus sans fenêtre: {}", BUS_NAME);
+
+    thread::spawn(move || {
+        for signal in name_lost {
+            if signal.args().is_ok_and(|args| args.name() == BUS_NAME) {
+                println!("🔎 Nom repris par l'application, arrêt du fournisseur");
+                std::process::exit(0);
+            }
+        }
+    });
+
+    *LAST_QUERY.lock() = Some(Instant::now());
+    loop {
+        thread::sleep(Duration::from_secs(10));
+        if LAST_QUERY.lock().is_some_and(|last| last.elapsed() >= HEADLESS_IDLE_EXIT) {
+            println!("🔎 Aucune recherche depuis {} s, arrêt du fournisseur", HEADLESS_IDLE_EXIT.as_secs());
+            return Ok(());
+        }
+    }
+}
+
+fn searcher_search(searcher: Arc<FileSearcher>) -> Search {
+    Arc::new(move |query| searcher.search(query))
+}
+
+fn serve_providers<F>(builder: Builder<'_>, search: Search, on_launch_search: F) -> zbus::Result<Connection>
+where F: Fn(String) + Send + 'static {
+    let gnome = GnomeSearchProvider {
+        search: search.clone(),
+        on_launch_search: Mutex::new(Box::new(on_launch_search)),
+    };
+    let krunner = KRunner { search };
+
+    builder.serve_at(GNOME_OBJECT_PATH, gnome)?
+        .serve_at(KRUNNER_OBJECT_PATH, krunner)?
+        .build()
+}
+
+fn search_paths(search: &Search, query: &str) -> Vec<String> {
+    *LAST_QUERY.lock() = Some(Instant::now());
+    if query.trim().chars().count() < MIN_QUERY_LENGTH {
+        return Vec::new();
+    }
+    search(query)
+        .into_iter()
+        .take(MAX_PROVIDER_RESULTS)
+        .map(|hit| hit.result.path)
+        .collect()
+}
+
+fn open_result(path: &str) {
+    // Un membre d'archive n'existe pas sur le disque : on ouvre l'archive
+    let target = split_member_path(Path::new(path)).map_or_else(|| Path::new(path).to_path_buf(), |(archive, _)| archive);
+    if let Err(e) = open_path(&target) {
+        eprintln!("Impossible d'ouvrir {}: {}", path, e);
+    }
+}
+
+// Nom d'icône freedesktop selon le type de fichier
+fn icon_name(name: &str, is_dir: bool) -> &'static str {
+    if is_dir {
+        return "folder";
+    }
+    match extension_of(name).as_deref() {
+        Some("png" | "jpg" | "jpeg" | "gif" | "bmp" | "webp" | "svg" | "ico" | "tiff") => "image-x-generic",
+        Some("mp3" | "flac" | "ogg" | "wav" | "m4a" | "opus" | "aac") => "audio-x-generic",
+        Some("mp4" | "mkv" | "avi" | "mov" | "webm" | "wmv") => "video-x-generic",
+        Some("zip" | "tar" | "gz" | "xz" | "bz2" | "7z" | "rar" | "zst") => "package-x-generic",
+        Some("pdf") => "application-pdf",
+        Some("doc" | "docx" | "odt" | "rtf") => "x-office-document",
+        Some("xls" | "xlsx" | "ods" | "csv") => "x-office-spreadsheet",
+        Some("ppt" | "pptx" | "odp") => "x-office-presentation",
+        Some("html" | "htm") => "text-html",
+        Some("sh" | "exe" | "msi" | "appimage" | "bin") => "application-x-executable",
+        Some("rs" | "c" | "h" | "cpp" | "py" | "js" | "ts" | "java" | "go") => "text-x-script",
+        _ => "text-x-generic",
+    }
+}
+
+fn string_value(value: &str) -> OwnedValue {
+    OwnedValue::from(Str::from(value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::searcher::{Index, SearchResult};
+    use std::io::{BufRead, BufReader};
+    use std::process::{Child, Stdio};
+    use std::time::SystemTime;
+
+    type KRunnerMatch = (String, String, String, i32, f64, HashMap<String, OwnedValue>);
+
+    // Bus de session privé, arrêté à la fin du test
+    struct PrivateBus {
+        daemon: Child,
+        address: String,
+    }
+
+    impl PrivateBus {
+        fn start() -> Option<Self> {
+            let mut daemon = Command::new("dbus-daemon")
+                .args(["--session", "--nofork", "--print-address"])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+                .ok()?;
+            let mut address = String::new();
+            BufReader::new(daemon.stdout.take()?).read_line(&mut address).ok()?;
+            Some(Self { daemon, address: address.trim().to_string() })
+        }
+    }
+
+    impl Drop for PrivateBus {
+        fn drop(&mut self) {
+            let _ = self.daemon.kill();
+            let _ = self.daemon.wait();
+        }
+    }
+
+    fn result(path: &str, is_dir: bool) -> SearchResult {
+        SearchResult {
+            id: 0,
+            name: Path::new(path).file_name().unwrap().to_string_lossy().to_string(),
+            path: path.to_string(),
+            size: 0,
+            is_dir,
+            modified: SystemTime::UNIX_EPOCH,
+            volume: String::new(),
+            offline: false,
+            attributes: None,
+            link_target: None,
+            hard_link: None,
+            archive: None,
+        }
+    }
+
+    #[test]
+    #[ignore = "nécessite dbus-daemon (cargo test -- --ignored)"]
+    fn repond_aux_lanceurs_depuis_l_index() {
+        let bus = PrivateBus::start().expect("dbus-daemon introuvable");
+
+        // Index local : le test ne touche ni aux index des profils ni aux réglages
+        let index = Arc::new(Index::from_results(vec![
+            result("/données/Été", true),
+            result("/données/Été/rapport_été.pdf", false),
+            result("/données/Factures", true),
+        ]));
+        let search: Search = Arc::new(move |query| index.search(query, MAX_PROVIDER_RESULTS));
+        let expected = "/données/Été/rapport_été.pdf".to_string();
+
+        let builder = Builder::address(bus.address.as_str()).unwrap().name(BUS_NAME).unwrap();
+        let _server = serve_providers(builder, search, |_| {}).unwrap();
+        let client = Builder::address(bus.address.as_str()).unwrap().build().unwrap();
+
+        let reply = client.call_method(
+            Some(BUS_NAME),
+            GNOME_OBJECT_PATH,
+            Some("org.gnome.Shell.SearchProvider2"),
+            "GetInitialResultSet",
+            &(vec!["rapport", "été"],)
+        ).unwrap();
+        let paths: Vec<String> = reply.body().deserialize().unwrap();
+        assert_eq!(paths, vec![expected.clone()]);
+
+        let reply = client.call_method(
+            Some(BUS_NAME),
+            KRUNNER_OBJECT_PATH,
+            Some("org.kde.krunner1"),
+            "Match",
+            &("rapport été",)
+        ).unwrap();
+        let matches: Vec<KRunnerMatch> = reply.body().deserialize().unwrap();
+        assert_eq!(matches.len(), 1);
+        let (id, text, icon, kind, _, _) = &matches[0];
+        assert_eq!(id, &expected);
+        assert_eq!(text, "rapport_été.pdf");
+        assert_eq!(icon, "application-pdf");
+        assert_eq!(*kind, KRUNNER_POSSIBLE_MATCH);
+
+        // Nom identique à la requête, à la casse près
+        let reply = client.call_method(
+            Some(BUS_NAME),
+            KRUNNER_OBJECT_PATH,
+            Some("org.kde.krunner1"),
+            "Match",
+            &("factures",)
+        ).unwrap();
+        let matches: Vec<KRunnerMatch> = reply.body().deserialize().unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].2, "folder");
+        assert_eq!(matches[0].3, KRUNNER_EXACT_MATCH);
+
+        // Requête trop courte : aucun résultat
+        let reply = client.call_method(
+            Some(BUS_NAME),
+            GNOME_OBJECT_PATH,
+            Some("org.gnome.Shell.SearchProvider2"),
+            "GetInitialResultSet",
+            &(vec!["r"],)
+        ).unwrap();
+        assert!(reply.body().deserialize::<Vec<String>>().unwrap().is_empty());
+    }
+}
@@ -5,17 +5,37 @@
  * - filtre de recherche etc..., icon, ouverture a la racine du path..
  */
 
-use crate::utils::get_drives;
+use super::archive::{is_indexable_archive, list_archive};
+use super::catalog::{self, CatalogEntry, VolumeCatalog};
+use super::query::{extension_of, match_spans, SearchQuery, QUERY_FIELDS};
+use super::suggest::{self, Terms};
+use super::snapshot::{IndexSnapshot, SnapshotDiff};
+use super::columns::column_settings;
+use super::index_options::{index_options, IndexOptions};
+use super::pacing::Pacer;
+use super::profiles::{index_profile, index_profiles, update_index_profiles, NamedProfile};
+use super::audit::{self, AuditReport};
+use super::tags::{disable_move_tracking, track_move};
+use super::maintenance::{self, CleanupScan, ProtectedPaths};
+use super::timeline::{ChangeKind, TimelineEntry, TimelineFilter};
+use super::index_errors::{self, record_index_error, IndexError, IndexErrorKind};
+use crate::utils::{load_json, save_json, run_elevated, lower_thread_priority, get_drives, file_attributes, file_id, hard_link_count, FileAttributes, VolumeInfo};
 use serde::{Serialize, Deserialize};
 use rayon::prelude::*;
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
 use ignore::WalkBuilder;
-use num_cpus;
+use ignore::overrides::OverrideBuilder;
 use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::ops::Range;
+use parking_lot::{Mutex, RwLock};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::time::SystemTime;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct SearchResult {
@@ -25,166 +45,1524 @@ pub struct SearchResult {
     pub size: u64,
     pub is_dir: bool,
     pub modified: SystemTime,
+    pub volume: String,
+    pub offline: bool,
+    // Uniquement si une colonne optionnelle en a besoin (voir columns.rs)
+    #[serde(default)]
+    pub attributes: Option<Box<FileAttributes>>,
+    #[serde(default)]
+    pub link_target: Option<String>,
+    // (périphérique, inode) des fichiers qui ont plusieurs liens durs, pour
+    // ne compter leur taille qu'une fois dans les totaux
+    #[serde(default)]
+    pub hard_link: Option<(u64, u64)>,
+    // Chemin de l'archive pour un membre indexé (`path` est alors virtuel)
+    #[serde(default)]
+    pub archive: Option<String>,
 }
 
-static FILES: Lazy<DashMap<u64, SearchResult>> = Lazy::new(|| DashMap::with_capacity(500_000));
-static NAME_INDEX: Lazy<DashMap<String, Vec<u64>>> = Lazy::new(|| DashMap::with_capacity(25_000));
-static PATH_INDEX: Lazy<DashMap<String, Vec<u64>>> = Lazy::new(|| DashMap::with_capacity(25_000));
-static FILE_COUNT: AtomicU64 = AtomicU64::new(0);
-static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+// Résultat accompagné des fragments du nom et du chemin qui ont matché
+pub struct SearchMatch {
+    pub result: SearchResult,
+    pub name_spans: Vec<Range<usize>>,
+    pub path_spans: Vec<Range<usize>>,
+}
 
-pub struct FileSearcher {}
+impl SearchMatch {
+    fn new(result: SearchResult, query: &SearchQuery, words: &[&str]) -> Self {
+        let mut path_words = words.to_vec();
+        if let Some(path) = &query.path {
+            path_words.push(path);
+        }
+
+        Self {
+            name_spans: match_spans(&result.name, words),
+            path_spans: match_spans(&result.path, &path_words),
+            result,
+        }
+    }
+}
+
+// Index en mémoire. Une indexation complète remplit un nouvel `Index` pendant
+// que les recherches continuent sur l'ancien, puis le remplace d'un coup.
+pub(super) struct Index {
+    files: DashMap<u64, SearchResult>,
+    name_index: DashMap<String, Vec<u64>>,
+    path_index: DashMap<String, Vec<u64>>,
+    extensions: DashMap<String, u64>,
+    file_count: AtomicU64,
+    next_id: AtomicU64,
+    indexed_at: Option<SystemTime>,
+}
+
+// Un index par profil nommé (voir profiles.rs)
+static INDEXES: Lazy<RwLock<HashMap<String, Arc<Index>>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+static TERMS: Lazy<RwLock<Arc<Terms>>> = Lazy::new(|| RwLock::new(Arc::new(Vec::new())));
+static TERMS_DIRTY: AtomicBool = AtomicBool::new(true);
+
+const MAX_SUGGESTIONS: usize = 8;
+pub const MAX_RESULTS: usize = 100;
+// Temps accordé à la vérification des résultats sur le disque, par recherche
+const VERIFY_BUDGET: Duration = Duration::from_millis(50);
+// Index sauvegardés pour la ligne de commande et le fournisseur de recherche D-Bus, un fichier par profil
+const SAVED_INDEX_DIR: &str = "indexes";
+// Membres indexés au plus par archive
+const MAX_ARCHIVE_MEMBERS: usize = 50_000;
+
+#[derive(Serialize, Deserialize)]
+struct SavedIndex {
+    indexed_at: Option<SystemTime>,
+    files: Vec<SearchResult>,
+}
+
+pub struct FileSearcher {
+    // Profil dans lequel chercher, None = tous les profils
+    scope: RwLock<Option<String>>,
+    // Profils en attente d'indexation, indexés l'un après l'autre
+    queue: Mutex<VecDeque<String>>,
+}
 
 impl FileSearcher {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            scope: RwLock::new(None),
+            queue: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn profiles(&self) -> Vec<NamedProfile> {
+        index_profiles()
+    }
+
+    pub fn scope(&self) -> Option<String> {
+        self.scope.read().clone()
+    }
+
+    pub fn set_scope(&self, scope: Option<String>) {
+        *self.scope.write() = scope;
+        TERMS_DIRTY.store(true, Ordering::SeqCst);
+    }
+
+    // Crée (`previous` = None) ou modifie un profil. Un profil renommé garde son index.
+    pub fn save_profile(&self, previous: Option<&str>, profile: NamedProfile) -> io::Result<()> {
+        let name = profile.name.trim().to_string();
+        if name.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Le profil doit avoir un nom"));
+        }
+
+        update_index_profiles(|profiles| {
+            if profiles.iter().any(|p| p.name == name && Some(p.name.as_str()) != previous) {
+                return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("Le profil '{}' existe déjà", name)));
+            }
+            let profile = NamedProfile { name: name.clone(), ..profile };
+            match previous.and_then(|previous| profiles.iter_mut().find(|p| p.name == previous)) {
+                Some(existing) => *existing = profile,
+                None => profiles.push(profile),
+            }
+            Ok(())
+        })?;
+
+        if let Some(previous) = previous.filter(|previous| *previous != name) {
+            let mut indexes = INDEXES.write();
+            if let Some(index) = indexes.remove(previous) {
+                indexes.insert(name.clone(), index);
+            }
+            let mut scope = self.scope.write();
+            if scope.as_deref() == Some(previous) {
+                *scope = Some(name);
+            }
+        }
+        Ok(())
     }
 
-    pub fn clear_index(&self) {
-        FILES.clear();
-        NAME_INDEX.clear();
-        PATH_INDEX.clear();
-        FILE_COUNT.store(0, Ordering::Relaxed);
-        NEXT_ID.store(0, Ordering::Relaxed);
-        println!("🧹 Index vidé");
+    pub fn delete_profile(&self, name: &str) -> io::Result<()> {
+        update_index_profiles(|profiles| {
+            if profiles.len() <= 1 {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "Il faut garder au moins un profil"));
+            }
+            profiles.retain(|p| p.name != name);
+            Ok(())
+        })?;
+
+        INDEXES.write().remove(name);
+        if self.scope().as_deref() == Some(name) {
+            self.set_scope(None);
+        }
+        TERMS_DIRTY.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    pub fn queue_indexing(&self, profile: &str) {
+        let mut queue = self.queue.lock();
+        if !queue.iter().any(|queued| queued == profile) {
+            queue.push_back(profile.to_string());
+        }
     }
 
-    pub fn build_index<F>(&self, should_cancel: F)
+    pub fn next_queued(&self) -> Option<String> {
+        self.queue.lock().pop_front()
+    }
+
+    pub fn clear_queue(&self) {
+        self.queue.lock().clear();
+    }
+
+    // Reconstruit l'index d'un profil en arrière-plan : les recherches utilisent
+    // l'index actuel jusqu'à la fin, une indexation annulée est abandonnée
+    pub fn build_index<F>(&self, profile: &str, should_cancel: F)
     where F: Fn() -> bool + Send + Sync + 'static {
-        self.clear_index();
+        let Some(profile) = index_profile(profile) else {
+            return;
+        };
+        let mut index = Index::new();
         
         let start_time = Instant::now();
-        println!("🔄 Démarrage de l'indexation...");
+        println!("🔄 Démarrage de l'indexation du profil {}...", profile.name);
 
-        let drives = get_drives();
-        println!("💾 Disques détectés: {:?}", drives);
+        let options = index_options();
+        let drives = if profile.roots.is_empty() {
+            index_errors::clear_index_errors();
+            index_roots(&options)
+        } else {
+            index_errors::forget_index_errors_under(&profile.roots);
+            profile.roots.clone()
+        };
+        println!("💾 Racines: {:?}", drives);
 
         let should_cancel = Arc::new(should_cancel);
+        let pacer = Pacer::new(&options);
 
-        drives.into_par_iter().for_each(|drive| {
-            let should_cancel = should_cancel.clone();
-            
+        let index_drive = |drive: String| {
             if (should_cancel)() {
                 return;
             }
 
             println!("📂 Indexation du disque: {}", drive);
             
-            WalkBuilder::new(&drive)
-                .hidden(false)
-                .git_ignore(false)
-                .git_global(false)
-                .git_exclude(false)
-                .threads(num_cpus::get())
-                .build_parallel()
-                .run(|| {
-                    let should_cancel = should_cancel.clone();
-                    
-                    Box::new(move |entry| {
-                        let entry = match entry {
-                            Ok(entry) => entry,
-                            Err(_) => return ignore::WalkState::Continue,
-                        };
-
-                        // Vérifier AVANT chaque fichier
-                        if (should_cancel)() {
-                            return ignore::WalkState::Quit;
-                        }
+            index_root(&index, &drive, &options, &profile.exclusions, &pacer, &should_cancel);
+        };
 
-                        if let Ok(metadata) = entry.metadata() {
-                            let count = FILE_COUNT.fetch_add(1, Ordering::Relaxed);
-                            if count % 100_000 == 0 {
-                                println!("⏳ {} fichiers trouvés...", count);
-                            }
-
-                            let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
-                            let name = entry.file_name().to_string_lossy();
-                            let path = entry.path().to_string_lossy();
-
-                            FILES.insert(id, SearchResult {
-                                id,
-                                name: name.to_string(),
-                                path: path.to_string(),
-                                size: metadata.len(),
-                                is_dir: metadata.is_dir(),
-                                modified: metadata.modified().unwrap_or(SystemTime::now()),
-                            });
-
-                            let name_lower = name.to_lowercase();
-                            for word in name_lower
-                                .split(|c: char| !c.is_alphanumeric())
-                                .filter(|s| !s.is_empty() && s.len() > 2) {
-                                
-                                NAME_INDEX.entry(word.to_string())
-                                    .or_insert_with(|| Vec::with_capacity(50))
-                                    .push(id);
-                            }
-
-                            if let Some(parent) = entry.path().parent() {
-                                let last_segment = parent.file_name()
-                                    .map(|s| s.to_string_lossy().to_lowercase());
-                                
-                                if let Some(segment) = last_segment {
-                                    PATH_INDEX.entry(segment.to_string())
-                                        .or_insert_with(|| Vec::with_capacity(50))
-                                        .push(id);
-                                }
-                            }
-
-                            // Vérifier APRÈS chaque insertion
-                            if (should_cancel)() {
-                                return ignore::WalkState::Quit;
-                            }
-                        }
-                        
-                        ignore::WalkState::Continue
-                    })
-                });
-        });
+        // Hors profil rapide, un disque après l'autre pour ne pas les solliciter ensemble
+        if options.profile.parallel_drives() {
+            drives.into_par_iter().for_each(index_drive);
+        } else {
+            drives.into_iter().for_each(index_drive);
+        }
 
         if (should_cancel)() {
             println!("⏹️ Indexation annulée");
             return;
         }
 
+        let file_count = index.file_count.load(Ordering::Relaxed);
+        index.indexed_at = Some(SystemTime::now());
+        swap_index(&profile.name, index);
+
         let duration = start_time.elapsed();
         println!("✅ Indexation terminée!");
         println!("=== Statistiques d'indexation ===");
         println!("⏱️  Temps total: {:.2} secondes", duration.as_secs_f64());
-        println!("📑 Nombre de fichiers indexés: {}", FILE_COUNT.load(Ordering::Relaxed));
+        println!("📑 Nombre de fichiers indexés: {}", file_count);
         println!("📊 Moyenne: {:.2} fichiers/seconde", 
-            FILE_COUNT.load(Ordering::Relaxed) as f64 / duration.as_secs_f64());
+            file_count as f64 / duration.as_secs_f64());
     }
 
-    pub fn search(&self, query: &str) -> Vec<SearchResult> {
-        let query = query.to_lowercase();
-        
-        // Recherche dans l'index des noms (plus rapide)
-        let name_matches: Vec<u64> = NAME_INDEX.iter()
-            .filter(|entry| entry.key().contains(&query))
-            .flat_map(|entry| entry.value().clone())
-            .take(1000)
+    // Indexe un seul volume sans toucher au reste de l'index, dans les
+    // profils qui couvrent tous les disques
+    pub fn index_volume<F>(&self, root: &str, should_cancel: F)
+    where F: Fn() -> bool + Send + Sync + 'static {
+        self.remove_root(root);
+        index_errors::forget_index_errors_under(&[root.to_string()]);
+
+        println!("📂 Indexation du volume: {}", root);
+        let options = index_options();
+        let should_cancel = Arc::new(should_cancel);
+        for profile in index_profiles().into_iter().filter(|profile| profile.roots.is_empty()) {
+            let index = writable_index(&profile);
+            index_root(&index, root, &options, &profile.exclusions, &Pacer::new(&options), &should_cancel);
+        }
+    }
+
+    // Retire les entrées sous `root` de tous les profils
+    pub fn remove_root(&self, root: &str) -> usize {
+        let mut total = 0;
+        for index in all_indexes() {
+            let removed: HashSet<u64> = index.files.iter()
+                .filter(|entry| is_under(&entry.value().path, root))
+                .map(|entry| *entry.key())
+                .collect();
+
+            if !removed.is_empty() {
+                index.remove_ids(&removed);
+                total += removed.len();
+            }
+        }
+
+        if total > 0 {
+            println!("🗑️ {} entrées retirées de l'index ({})", total, root);
+        }
+        total
+    }
+
+    pub fn search(&self, query: &str) -> Vec<SearchMatch> {
+        self.search_in(self.scope().as_deref(), query, MAX_RESULTS)
+    }
+
+    // Recherche dans un profil (None = tous) sans toucher au périmètre choisi
+    // dans l'interface, utilisée aussi par l'API HTTP
+    pub fn search_in(&self, profile: Option<&str>, query: &str, limit: usize) -> Vec<SearchMatch> {
+        let query = SearchQuery::parse(query);
+        let words: Vec<&str> = query.text.split_whitespace().collect();
+        if words.is_empty() && !query.has_filters() {
+            return Vec::new();
+        }
+
+        let options = index_options();
+        // Les profils peuvent se recouvrir : un chemin n'est affiché qu'une fois
+        let mut seen_paths: HashSet<String> = HashSet::new();
+        let mut results: Vec<SearchResult> = Vec::new();
+
+        for index in indexes_for(profile) {
+            if results.len() >= limit {
+                break;
+            }
+
+            let mut found = search_index(&index, &query, &words, limit - results.len());
+            if options.verify_results {
+                verify_results(&index, &mut found, options.follow_links);
+                found.retain(|result| query.matches(result));
+            }
+            results.extend(found.into_iter().filter(|result| seen_paths.insert(result.path.clone())));
+        }
+
+        // Compléter avec les catalogues des volumes débranchés
+        if results.len() < limit {
+            let missing = limit - results.len();
+            results.extend(self.search_offline_catalogs(&query, &words).take(missing));
+        }
+
+        results.into_iter()
+            .map(|result| SearchMatch::new(result, &query, &words))
+            .collect()
+    }
+
+    fn search_offline_catalogs<'a>(&self, query: &'a SearchQuery, words: &'a [&'a str]) -> impl Iterator<Item = SearchResult> + 'a {
+        catalog::catalogs()
+            .into_iter()
+            .filter(|loaded| !catalog::is_mounted(&loaded.catalog.volume.uuid))
+            .flat_map(move |loaded| {
+                let volume = loaded.catalog.volume.display_name();
+                let matches: Vec<SearchResult> = loaded.names_lower.iter()
+                    .enumerate()
+                    .filter(|(_, name)| words.iter().all(|word| name.contains(word)))
+                    .map(|(i, _)| {
+                        let entry = &loaded.catalog.entries[i];
+                        SearchResult {
+                            id: 0,
+                            name: entry.name.clone(),
+                            path: entry.path.clone(),
+                            size: entry.size,
+                            is_dir: entry.is_dir,
+                            modified: entry.modified,
+                            volume: volume.clone(),
+                            offline: true,
+                            attributes: None,
+                            link_target: None,
+                            hard_link: None,
+                            archive: None,
+                        }
+                    })
+                    .filter(|result| query.matches(result))
+                    .take(100)
+                    .collect();
+                matches
+            })
+    }
+
+    pub fn suggest(&self, input: &str) -> Vec<String> {
+        let mut counts: HashMap<String, u64> = HashMap::new();
+        for index in self.scope_indexes() {
+            for entry in index.extensions.iter() {
+                *counts.entry(entry.key().clone()).or_insert(0) += *entry.value();
+            }
+        }
+        let mut extensions: Vec<(String, u64)> = counts.into_iter().collect();
+        extensions.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+        suggest::complete(input, &self.terms(), &extensions, MAX_SUGGESTIONS)
+    }
+
+    pub fn did_you_mean(&self, input: &str) -> Option<String> {
+        let query = SearchQuery::parse(input);
+        if query.text.is_empty() {
+            return None;
+        }
+
+        let corrected = suggest::did_you_mean(&query.text, &self.terms())?;
+        // On remet les filtres de la requête d'origine derrière le texte corrigé
+        let filters: Vec<&str> = input.split_whitespace()
+            .filter(|token| token.split_once(':').is_some_and(|(field, _)| {
+                QUERY_FIELDS.iter().any(|f| f.trim_end_matches(':') == field.to_lowercase())
+            }))
             .collect();
 
-        // Si pas assez de résultats, chercher dans les chemins
-        let path_matches: Vec<u64> = if name_matches.len() < 1000 {
-            PATH_INDEX.iter()
-                .filter(|entry| entry.key().contains(&query))
-                .flat_map(|entry| entry.value().clone())
-                .take(1000 - name_matches.len())
-                .collect()
-        } else {
-            Vec::new()
+        Some(std::iter::once(corrected.as_str()).chain(filters).collect::<Vec<_>>().join(" "))
+    }
+
+    // Liste triée des termes de l'index des noms, reconstruite après chaque modification de l'index
+    fn terms(&self) -> Arc<Terms> {
+        if TERMS_DIRTY.swap(false, Ordering::SeqCst) {
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            for index in self.scope_indexes() {
+                for entry in index.name_index.iter() {
+                    *counts.entry(entry.key().clone()).or_insert(0) += entry.value().len();
+                }
+            }
+            let mut terms: Terms = counts.into_iter().collect();
+            terms.sort_by(|a, b| a.0.cmp(&b.0));
+            *TERMS.write() = Arc::new(terms);
+        }
+        TERMS.read().clone()
+    }
+
+    pub fn mounted_volumes(&self) -> Vec<VolumeInfo> {
+        catalog::refresh_mounted_volumes()
+    }
+
+    pub fn catalogued_volumes(&self) -> Vec<VolumeInfo> {
+        catalog::catalogs()
+            .into_iter()
+            .map(|loaded| loaded.catalog.volume.clone())
+            .collect()
+    }
+
+    pub fn forget_catalog(&self, uuid: &str) -> io::Result<()> {
+        catalog::forget_catalog(uuid)
+    }
+
+    pub fn catalog_volume<F>(&self, volume: &VolumeInfo, should_cancel: F) -> io::Result<usize>
+    where F: Fn() -> bool + Send + Sync + 'static {
+        println!("💽 Catalogage du volume {} ({})", volume.display_name(), volume.uuid);
+
+        let entries = Arc::new(Mutex::new(Vec::new()));
+        let should_cancel = Arc::new(should_cancel);
+
+        configure_walker(&volume.mount_point, &index_options(), &[])
+            .build_parallel()
+            .run(|| {
+                let entries = entries.clone();
+                let should_cancel = should_cancel.clone();
+
+                Box::new(move |entry| {
+                    if (should_cancel)() {
+                        return ignore::WalkState::Quit;
+                    }
+
+                    let Ok(entry) = entry else {
+                        return ignore::WalkState::Continue;
+                    };
+
+                    if let Ok(metadata) = entry.metadata() {
+                        entries.lock().push(CatalogEntry {
+                            name: entry.file_name().to_string_lossy().to_string(),
+                            path: entry.path().to_string_lossy().to_string(),
+                            size: metadata.len(),
+                            is_dir: metadata.is_dir(),
+                            modified: metadata.modified().unwrap_or(SystemTime::now()),
+                        });
+                    }
+
+                    ignore::WalkState::Continue
+                })
+            });
+
+        if (should_cancel)() {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "Catalogage annulé"));
+        }
+
+        let entries = std::mem::take(&mut *entries.lock());
+        VolumeCatalog {
+            volume: volume.clone(),
+            indexed_at: SystemTime::now(),
+            entries,
+        }.save()
+    }
+
+    pub fn take_snapshot(&self, name: &str) -> io::Result<usize> {
+        let mut seen_paths: HashSet<String> = HashSet::new();
+        let mut files = Vec::new();
+        for index in self.scope_indexes() {
+            for entry in index.files.iter() {
+                let file = entry.value();
+                if seen_paths.insert(file.path.clone()) {
+                    files.push((file.path.clone(), file.size, file.is_dir, file.hard_link));
+                }
+            }
+        }
+        let snapshot = IndexSnapshot::from_files(name, files);
+        snapshot.save()?;
+        println!("📸 Snapshot '{}' enregistré ({} entrées)", name, snapshot.entries.len());
+        Ok(snapshot.entries.len())
+    }
+
+    // Dossiers vides, fichiers de zéro octet et liens cassés du périmètre de recherche
+    pub fn find_cleanup_candidates(&self, protected: &ProtectedPaths) -> CleanupScan {
+        let mut seen_paths: HashSet<String> = HashSet::new();
+        let mut entries = Vec::new();
+        for index in self.scope_indexes() {
+            for entry in index.files.iter() {
+                let file = entry.value();
+                if file.archive.is_none() && seen_paths.insert(file.path.clone()) {
+                    entries.push((file.path.clone(), file.is_dir, file.size, file.link_target.is_some()));
+                }
+            }
+        }
+        maintenance::find_candidates(entries, protected)
+    }
+
+    // Audit de sécurité des entrées du périmètre de recherche (voir audit.rs)
+    pub fn run_audit(&self) -> io::Result<AuditReport> {
+        let mut seen_paths: HashSet<String> = HashSet::new();
+        for index in self.scope_indexes() {
+            for entry in index.files.iter() {
+                let file = entry.value();
+                if file.archive.is_none() {
+                    seen_paths.insert(file.path.clone());
+                }
+            }
+        }
+        audit::audit_paths(seen_paths.into_iter().collect())
+    }
+
+    // Entrées modifiées depuis `since`, de la plus récente à la plus ancienne
+    pub fn recent_changes(&self, filter: &TimelineFilter, since: SystemTime) -> Vec<TimelineEntry> {
+        let mut seen_paths: HashSet<String> = HashSet::new();
+        let mut entries = Vec::new();
+        for index in self.scope_indexes() {
+            for entry in index.files.iter() {
+                let file = entry.value();
+                if file.archive.is_some() || file.modified < since || !filter.matches(&file.path, file.is_dir) {
+                    continue;
+                }
+                if !seen_paths.insert(file.path.clone()) {
+                    continue;
+                }
+
+                // La date de création n'est connue que si une colonne l'a demandée
+                let created = file.attributes.as_ref().and_then(|attributes| attributes.created);
+                let kind = if created.is_some_and(|created| created >= since) {
+                    ChangeKind::Created
+                } else {
+                    ChangeKind::Modified
+                };
+                entries.push(TimelineEntry {
+                    path: file.path.clone(),
+                    name: file.name.clone(),
+                    is_dir: file.is_dir,
+                    size: file.size,
+                    time: file.modified,
+                    kind,
+                });
+            }
+        }
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.time));
+        entries
+    }
+
+    // Retire de l'index les chemins supprimés et tout ce qu'ils contenaient
+    pub fn remove_paths(&self, paths: &[String]) -> usize {
+        let removed_paths: HashSet<&str> = paths.iter().map(String::as_str).collect();
+        let mut total = 0;
+        for index in all_indexes() {
+            let removed: HashSet<u64> = index.files.iter()
+                .filter(|entry| Path::new(&entry.value().path)
+                    .ancestors()
+                    .any(|ancestor| ancestor.to_str().is_some_and(|a| removed_paths.contains(a))))
+                .map(|entry| *entry.key())
+                .collect();
+
+            if !removed.is_empty() {
+                index.remove_ids(&removed);
+                total += removed.len();
+            }
+        }
+        total
+    }
+
+    pub fn snapshot_names(&self) -> Vec<String> {
+        IndexSnapshot::list_names()
+    }
+
+    pub fn delete_snapshot(&self, name: &str) -> io::Result<()> {
+        IndexSnapshot::delete(name)
+    }
+
+    pub fn diff_snapshots(&self, old: &str, new: &str) -> io::Result<SnapshotDiff> {
+        let load = |name: &str| IndexSnapshot::load(name).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("Snapshot introuvable: {}", name))
+        });
+        Ok(SnapshotDiff::between(&load(old)?, &load(new)?))
+    }
+
+    pub fn file_count(&self) -> u64 {
+        self.scope_indexes().iter()
+            .map(|index| index.file_count.load(Ordering::Relaxed))
+            .sum()
+    }
+
+    // Fin de la dernière indexation complète dans le périmètre de recherche
+    pub fn last_indexed(&self) -> Option<SystemTime> {
+        self.scope_indexes().iter()
+            .filter_map(|index| index.indexed_at)
+            .max()
+    }
+
+    pub fn profile_last_indexed(&self, profile: &str) -> Option<SystemTime> {
+        profile_index(profile)?.indexed_at
+    }
+
+    pub fn profile_file_count(&self, profile: &str) -> u64 {
+        profile_index(profile).map_or(0, |index| index.file_count.load(Ordering::Relaxed))
+    }
+
+    // Sauvegarde l'index d'un profil sur le disque, pour qu'un autre processus
+    // (`suptool search`, `suptool --search-provider`) puisse chercher sans réindexer
+    pub fn save_index(&self, profile: &str) -> io::Result<usize> {
+        let index = profile_index(profile)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("Le profil '{}' n'est pas indexé", profile)))?;
+        let saved = SavedIndex {
+            indexed_at: index.indexed_at,
+            files: index.files.iter().map(|entry| entry.value().clone()).collect(),
         };
+        save_json(&saved_index_file(profile), &saved)?;
+        Ok(saved.files.len())
+    }
+
+    // Recharge l'index sauvegardé par `save_index`. Renvoie false s'il n'y en a pas.
+    pub fn load_saved_index(&self, profile: &str) -> bool {
+        let Some(saved) = load_json::<SavedIndex>(&saved_index_file(profile)) else {
+            return false;
+        };
+        let mut index = Index::new();
+        for file in saved.files {
+            index.insert(file);
+        }
+        index.indexed_at = saved.indexed_at;
+        swap_index(profile, index);
+        true
+    }
+
+    fn scope_indexes(&self) -> Vec<Arc<Index>> {
+        indexes_for(self.scope().as_deref())
+    }
+
+    pub fn index_errors(&self) -> Vec<IndexError> {
+        index_errors::index_errors()
+    }
+
+    // Réindexe, avec les droits administrateur, les dossiers qui n'ont pas pu
+    // être lus. Le parcours est fait par un second processus (voir
+    // `run_index_helper`) dont le résultat est fusionné dans l'index.
+    pub fn retry_failed_elevated(&self) -> io::Result<usize> {
+        let roots = failed_roots(&index_errors::index_errors());
+        if roots.is_empty() {
+            return Ok(0);
+        }
+
+        let exchange_dir = create_exchange_dir()?;
+        let input = exchange_dir.join("request.json");
+        let output = exchange_dir.join("report.json");
 
-        // Combiner et convertir les résultats
-        let mut results: Vec<SearchResult> = name_matches.into_iter()
-            .chain(path_matches)
-            .filter_map(|id| FILES.get(&id))
-            .map(|entry| entry.value().clone())
+        // Le processus élevé n'a pas accès aux réglages de l'utilisateur : chaque
+        // dossier est reparcouru avec les exclusions de chaque profil qui le couvre
+        let profiles = index_profiles();
+        let walks = roots.iter()
+            .flat_map(|root| profiles.iter()
+                .filter(|profile| profile.covers(root))
+                .map(|profile| RetryWalk {
+                    profile: profile.name.clone(),
+                    root: root.clone(),
+                    exclusions: profile.exclusions.clone(),
+                }))
             .collect();
+        let request = IndexRequest {
+            walks,
+            options: index_options(),
+            capture_attributes: column_settings().wants_attributes(),
+        };
+        let report = exchange_with_helper(&request, &input, &output);
+        let _ = fs::remove_dir_all(&exchange_dir);
+        let report = report?;
+
+        for root in &roots {
+            self.remove_root(root);
+        }
+        index_errors::forget_index_errors_under(&roots);
+
+        let mut count = 0;
+        for (name, result) in report.results {
+            // Un lien suivi peut mener hors des racines du profil
+            if let Some(profile) = profiles.iter().find(|profile| profile.name == name && profile.covers(&result.path)) {
+                writable_index(profile).insert(result);
+                count += 1;
+            }
+        }
+        report.errors.into_iter().for_each(record_index_error);
+        TERMS_DIRTY.store(true, Ordering::SeqCst);
+
+        println!("🔓 {} entrées réindexées avec élévation", count);
+        Ok(count)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct IndexRequest {
+    walks: Vec<RetryWalk>,
+    options: IndexOptions,
+    capture_attributes: bool,
+}
+
+// Dossier à reparcourir pour un profil, avec ses exclusions
+#[derive(Serialize, Deserialize)]
+struct RetryWalk {
+    profile: String,
+    root: String,
+    exclusions: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct IndexReport {
+    // Nom du profil et entrée trouvée
+    results: Vec<(String, SearchResult)>,
+    errors: Vec<IndexError>,
+}
+
+// Dossier d'échange avec le processus élevé, créé pour l'occasion et réservé
+// à l'utilisateur : personne ne peut y avoir préparé de lien symbolique
+fn create_exchange_dir() -> io::Result<PathBuf> {
+    let base = std::env::temp_dir();
+    for attempt in 0..16 {
+        let nanos = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|elapsed| elapsed.subsec_nanos())
+            .unwrap_or(0);
+        let dir = base.join(format!("suptool-retry-{}-{}-{}", std::process::id(), nanos, attempt));
+
+        let mut builder = fs::DirBuilder::new();
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+        // mkdir échoue si le chemin existe déjà, lien compris
+        match builder.create(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Err(io::Error::new(io::ErrorKind::AlreadyExists, "aucun dossier temporaire disponible"))
+}
+
+fn exchange_with_helper(request: &IndexRequest, input: &Path, output: &Path) -> io::Result<IndexReport> {
+    io::Write::write_all(
+        &mut fs::OpenOptions::new().write(true).create_new(true).open(input)?,
+        serde_json::to_string(request)?.as_bytes(),
+    )?;
 
-        results.truncate(100);
-        results
+    let status = run_elevated(
+        &std::env::current_exe()?,
+        &["--index-paths", &input.to_string_lossy(), &output.to_string_lossy()],
+    )?;
+    if !status.success() {
+        return Err(io::Error::new(io::ErrorKind::PermissionDenied, "élévation refusée ou échec du processus"));
     }
-}
\ No newline at end of file
+
+    Ok(serde_json::from_str(&fs::read_to_string(output)?)?)
+}
+
+// Le processus élevé ne fait confiance qu'à un dossier d'échange privé
+// appartenant à l'utilisateur qui l'a lancé, et n'écrit jamais à travers un lien
+fn check_exchange_paths(input: &Path, output: &Path) -> io::Result<()> {
+    let denied = |reason: &str| io::Error::new(io::ErrorKind::PermissionDenied, reason.to_string());
+    let dir = input.parent().ok_or_else(|| denied("requête sans dossier"))?;
+    if output.parent() != Some(dir) {
+        return Err(denied("la requête et le rapport doivent partager leur dossier"));
+    }
+
+    let dir_metadata = fs::symlink_metadata(dir)?;
+    let input_metadata = fs::symlink_metadata(input)?;
+    if !dir_metadata.is_dir() || !input_metadata.is_file() {
+        return Err(denied("dossier d'échange invalide"));
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        // pkexec transmet l'utilisateur d'origine
+        let user = std::env::var("PKEXEC_UID").ok()
+            .and_then(|uid| uid.parse().ok())
+            .unwrap_or_else(|| unsafe { libc::getuid() });
+        if dir_metadata.uid() != user || input_metadata.uid() != user || dir_metadata.mode() & 0o077 != 0 {
+            return Err(denied("le dossier d'échange doit être privé à l'utilisateur"));
+        }
+    }
+    Ok(())
+}
+
+// Racines acceptées par le processus élevé : dossiers réels désignés par un
+// chemin absolu, sans `..` ni lien symbolique à traverser
+fn valid_helper_root(root: &str) -> bool {
+    let path = Path::new(root);
+    path.is_absolute()
+        && path.components().all(|c| !matches!(c, std::path::Component::ParentDir | std::path::Component::CurDir))
+        // Sous Windows, canonicalize renvoie un chemin \\?\ jamais identique
+        && (cfg!(windows) || fs::canonicalize(path).is_ok_and(|canonical| canonical == path))
+        && fs::symlink_metadata(path).is_ok_and(|metadata| metadata.is_dir())
+}
+
+fn create_report_file(output: &Path) -> io::Result<fs::File> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::custom_flags(&mut options, libc::O_NOFOLLOW);
+    options.open(output)
+}
+
+// Point d'entrée du processus lancé par `retry_failed_elevated`
+pub fn run_index_helper(input: &Path, output: &Path) -> io::Result<()> {
+    disable_move_tracking();
+    check_exchange_paths(input, output)?;
+    let request: IndexRequest = serde_json::from_str(&fs::read_to_string(input)?)?;
+    if let Some(walk) = request.walks.iter().find(|walk| !valid_helper_root(&walk.root)) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("racine refusée : {}", walk.root)));
+    }
+    let report_file = create_report_file(output)?;
+    let results = Mutex::new(Vec::new());
+    let never_cancel = Arc::new(|| false);
+    let pacer = Pacer::new(&request.options);
+
+    for walk in &request.walks {
+        walk_root(&walk.root, &request.options, &walk.exclusions, request.capture_attributes, &pacer, &never_cancel, &|result| {
+            results.lock().push((walk.profile.clone(), result));
+        });
+    }
+
+    let report = IndexReport {
+        results: results.into_inner(),
+        errors: index_errors::index_errors(),
+    };
+    serde_json::to_writer(report_file, &report)?;
+    Ok(())
+}
+
+// Dossiers à réessayer : les boucles et chemins disparus sont écartés, ainsi
+// que les dossiers déjà couverts par un parent
+fn failed_roots(errors: &[IndexError]) -> Vec<String> {
+    let mut paths: Vec<String> = errors.iter()
+        .filter(|e| matches!(e.kind, IndexErrorKind::PermissionDenied | IndexErrorKind::Other))
+        .filter(|e| !e.path.is_empty() && Path::new(&e.path).is_dir())
+        .map(|e| e.path.clone())
+        .collect();
+    paths.sort();
+    paths.dedup();
+
+    let mut roots: Vec<String> = Vec::new();
+    for path in paths {
+        if !roots.iter().any(|root| is_under(&path, root)) {
+            roots.push(path);
+        }
+    }
+    roots
+}
+
+fn configure_walker(root: &str, options: &IndexOptions, exclusions: &[String]) -> WalkBuilder {
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .hidden(false)
+        .git_ignore(false)
+        .git_global(false)
+        .git_exclude(false)
+        .follow_links(options.follow_links)
+        .same_file_system(!options.cross_filesystems)
+        .threads(options.profile.threads());
+
+    // Exclusions du profil : chemins absolus ou motifs glob
+    let (paths, globs): (Vec<&String>, Vec<&String>) = exclusions.iter()
+        .partition(|exclusion| Path::new(exclusion.as_str()).is_absolute());
+
+    if !globs.is_empty() {
+        let mut overrides = OverrideBuilder::new(root);
+        for glob in globs {
+            if let Err(e) = overrides.add(&format!("!{}", glob)) {
+                eprintln!("Exclusion invalide {}: {}", glob, e);
+            }
+        }
+        match overrides.build() {
+            Ok(overrides) => {
+                builder.overrides(overrides);
+            }
+            Err(e) => eprintln!("Exclusions ignorées: {}", e),
+        }
+    }
+
+    if !paths.is_empty() {
+        let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+        builder.filter_entry(move |entry| !paths.iter().any(|path| entry.path().starts_with(path)));
+    }
+
+    builder
+}
+
+// Racines à parcourir. Sans franchissement des systèmes de fichiers, chaque
+// volume monté est parcouru séparément pour ne rien perdre sous "/".
+fn index_roots(options: &IndexOptions) -> Vec<String> {
+    let mut roots = get_drives();
+
+    if cfg!(not(windows)) && !options.cross_filesystems {
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut volumes = catalog::refresh_mounted_volumes();
+        volumes.sort_by_key(|v| v.mount_point.len());
+
+        // Un même périphérique peut être monté plusieurs fois (bind mounts)
+        for volume in volumes {
+            if seen.insert(volume.uuid) && !roots.contains(&volume.mount_point) {
+                roots.push(volume.mount_point);
+            }
+        }
+    }
+
+    roots
+}
+
+fn index_root<F>(
+    index: &Index,
+    root: &str,
+    options: &IndexOptions,
+    exclusions: &[String],
+    pacer: &Pacer,
+    should_cancel: &Arc<F>,
+)
+where F: Fn() -> bool + Send + Sync + 'static {
+    let capture_attributes = column_settings().wants_attributes();
+    walk_root(root, options, exclusions, capture_attributes, pacer, should_cancel, &|result| index.insert(result));
+    TERMS_DIRTY.store(true, Ordering::SeqCst);
+}
+
+// Parcourt `root` et passe chaque entrée à `sink`. Les chemins illisibles sont
+// enregistrés dans le rapport d'erreurs au lieu d'être ignorés.
+fn walk_root<F>(
+    root: &str,
+    options: &IndexOptions,
+    exclusions: &[String],
+    capture_attributes: bool,
+    pacer: &Pacer,
+    should_cancel: &Arc<F>,
+    sink: &(dyn Fn(SearchResult) + Sync),
+)
+where F: Fn() -> bool + Send + Sync + 'static {
+    // Dossiers déjà parcourus : en suivant les liens, un même arbre peut être
+    // atteint par plusieurs chemins (les boucles, elles, sont écartées par le walker)
+    let visited_dirs: Arc<DashSet<(u64, u64)>> = Arc::new(DashSet::new());
+
+    configure_walker(root, options, exclusions)
+        .build_parallel()
+        .run(|| {
+            let should_cancel = should_cancel.clone();
+            let visited_dirs = visited_dirs.clone();
+            let follow_links = options.follow_links;
+            // Le visiteur s'exécute dans le thread du walker : la priorité
+            // est abaissée au premier appel
+            let mut lower_priority = options.profile.low_priority();
+
+            Box::new(move |entry| {
+                if lower_priority {
+                    lower_thread_priority();
+                    lower_priority = false;
+                }
+
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(e) => {
+                        record_index_error(IndexError::from_walk_error(&e));
+                        return ignore::WalkState::Continue;
+                    }
+                };
+
+                // Vérifier AVANT chaque fichier
+                if (should_cancel)() || !pacer.pace(&*should_cancel) {
+                    return ignore::WalkState::Quit;
+                }
+
+                let metadata = match entry.metadata() {
+                    Ok(metadata) => metadata,
+                    Err(e) => {
+                        record_index_error(IndexError::from_walk_error(&e));
+                        return ignore::WalkState::Continue;
+                    }
+                };
+
+                let id = file_id(&metadata);
+
+                if follow_links && metadata.is_dir() {
+                    if let Some(id) = id {
+                        if !visited_dirs.insert(id) {
+                            return ignore::WalkState::Skip;
+                        }
+                    }
+                }
+
+                if let Some(id) = id {
+                    track_move(id, entry.path());
+                }
+
+                let hard_link = id.filter(|_| !metadata.is_dir() && hard_link_count(&metadata) > 1);
+                let link_target = entry.path_is_symlink()
+                    .then(|| std::fs::read_link(entry.path()).ok())
+                    .flatten()
+                    .map(|target| target.to_string_lossy().to_string());
+
+                sink(SearchResult {
+                    id: 0,
+                    name: entry.file_name().to_string_lossy().to_string(),
+                    path: entry.path().to_string_lossy().to_string(),
+                    size: metadata.len(),
+                    is_dir: metadata.is_dir(),
+                    modified: metadata.modified().unwrap_or(SystemTime::now()),
+                    volume: String::new(),
+                    offline: false,
+                    attributes: capture_attributes.then(|| Box::new(
+                        file_attributes(entry.path(), &metadata)
+                    )),
+                    link_target,
+                    hard_link,
+                    archive: None,
+                });
+
+                if options.index_archives && metadata.is_file() && is_indexable_archive(entry.path()) {
+                    index_archive_members(entry.path(), &metadata, sink);
+                }
+
+                // Vérifier APRÈS chaque insertion
+                if (should_cancel)() {
+                    return ignore::WalkState::Quit;
+                }
+
+                ignore::WalkState::Continue
+            })
+        });
+}
+
+// Ajoute les membres d'une archive sous forme de chemins virtuels
+// `archive.zip/dossier/fichier`, avec leur propre taille et date
+fn index_archive_members(path: &Path, metadata: &fs::Metadata, sink: &(dyn Fn(SearchResult) + Sync)) {
+    let members = match list_archive(path, MAX_ARCHIVE_MEMBERS) {
+        Ok(members) => members,
+        Err(e) => {
+            record_index_error(IndexError {
+                path: path.to_string_lossy().to_string(),
+                kind: IndexErrorKind::Other,
+                reason: format!("archive illisible : {}", e),
+            });
+            return;
+        }
+    };
+
+    let archive = path.to_string_lossy().to_string();
+    let archive_modified = metadata.modified().unwrap_or(SystemTime::now());
+
+    for member in members {
+        let relative = member.path.trim_end_matches('/');
+        let Some(name) = relative.rsplit('/').next().filter(|name| !name.is_empty()) else { continue };
+        let mut virtual_path = path.to_path_buf();
+        virtual_path.extend(relative.split('/').filter(|part| !part.is_empty() && *part != "." && *part != ".."));
+
+        sink(SearchResult {
+            id: 0,
+            name: name.to_string(),
+            path: virtual_path.to_string_lossy().to_string(),
+            size: member.size,
+            is_dir: member.is_dir,
+            modified: member.modified.unwrap_or(archive_modified),
+            volume: String::new(),
+            offline: false,
+            attributes: None,
+            link_target: None,
+            hard_link: None,
+            archive: Some(archive.clone()),
+        });
+    }
+}
+
+impl Index {
+    fn new() -> Self {
+        Self {
+            files: DashMap::with_capacity(500_000),
+            name_index: DashMap::with_capacity(25_000),
+            path_index: DashMap::with_capacity(25_000),
+            extensions: DashMap::with_capacity(1_000),
+            file_count: AtomicU64::new(0),
+            next_id: AtomicU64::new(0),
+            indexed_at: None,
+        }
+    }
+
+    fn remove_ids(&self, removed: &HashSet<u64>) {
+        let mut count = 0;
+        for id in removed {
+            // Un id déjà retiré (vérification concurrente...) n'est pas recompté
+            let Some((_, result)) = self.files.remove(id) else {
+                continue;
+            };
+            count += 1;
+
+            // Seuls les mots de cette entrée la référencent
+            for word in name_terms(&result.name) {
+                remove_from_term(&self.name_index, &word, *id);
+            }
+            if let Some(segment) = path_term(&result.path) {
+                remove_from_term(&self.path_index, &segment, *id);
+            }
+        }
+        self.file_count.fetch_sub(count, Ordering::Relaxed);
+        TERMS_DIRTY.store(true, Ordering::SeqCst);
+    }
+
+    fn insert(&self, mut result: SearchResult) {
+        let count = self.file_count.fetch_add(1, Ordering::Relaxed);
+        if count.is_multiple_of(100_000) {
+            println!("⏳ {} fichiers trouvés...", count);
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        result.id = id;
+
+        if !result.is_dir {
+            if let Some(extension) = extension_of(&result.name) {
+                *self.extensions.entry(extension).or_insert(0) += 1;
+            }
+        }
+
+        for word in name_terms(&result.name) {
+            self.name_index.entry(word)
+                .or_insert_with(|| Vec::with_capacity(50))
+                .push(id);
+        }
+
+        if let Some(segment) = path_term(&result.path) {
+            self.path_index.entry(segment)
+                .or_insert_with(|| Vec::with_capacity(50))
+                .push(id);
+        }
+
+        self.files.insert(id, result);
+    }
+}
+
+// Mots du nom indexés dans `name_index`
+fn name_terms(name: &str) -> Vec<String> {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty() && s.len() > 2)
+        .map(str::to_string)
+        .collect()
+}
+
+// Dossier parent indexé dans `path_index`
+fn path_term(path: &str) -> Option<String> {
+    Path::new(path).parent()?
+        .file_name()
+        .map(|s| s.to_string_lossy().to_lowercase())
+}
+
+fn remove_from_term(words: &DashMap<String, Vec<u64>>, word: &str, id: u64) {
+    words.remove_if_mut(word, |_, ids| {
+        ids.retain(|other| *other != id);
+        ids.is_empty()
+    });
+}
+
+// Compare les résultats au disque dans la limite de VERIFY_BUDGET : les fichiers
+// disparus sont retirés de l'index, la taille et la date des autres rafraîchies
+fn verify_results(index: &Index, results: &mut Vec<SearchResult>, follow_links: bool) {
+    let deadline = Instant::now() + VERIFY_BUDGET;
+    let mut stale: HashSet<u64> = HashSet::new();
+
+    for result in results.iter_mut() {
+        if Instant::now() >= deadline {
+            break;
+        }
+
+        // Un membre d'archive n'existe que tant que son archive existe
+        if let Some(archive) = &result.archive {
+            if let Err(e) = fs::metadata(archive) {
+                if e.kind() == io::ErrorKind::NotFound {
+                    stale.insert(result.id);
+                }
+            }
+            continue;
+        }
+
+        let metadata = if follow_links {
+            fs::metadata(&result.path)
+        } else {
+            fs::symlink_metadata(&result.path)
+        };
+
+        match metadata {
+            Ok(metadata) => {
+                let modified = metadata.modified().unwrap_or(result.modified);
+                if metadata.len() != result.size || modified != result.modified {
+                    result.size = metadata.len();
+                    result.modified = modified;
+                    if let Some(mut entry) = index.files.get_mut(&result.id) {
+                        entry.size = result.size;
+                        entry.modified = modified;
+                    }
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                stale.insert(result.id);
+            }
+            // Accès refusé, volume indisponible... : on garde l'entrée
+            Err(_) => {}
+        }
+    }
+
+    if !stale.is_empty() {
+        results.retain(|result| !stale.contains(&result.id));
+        index.remove_ids(&stale);
+        println!("🧽 {} entrées obsolètes retirées de l'index", stale.len());
+    }
+}
+
+#[cfg(test)]
+impl Index {
+    // Index autonome, hors des profils : sert aux tests des modules voisins
+    pub(super) fn from_results(results: impl IntoIterator<Item = SearchResult>) -> Self {
+        let index = Index::new();
+        results.into_iter().for_each(|result| index.insert(result));
+        index
+    }
+
+    // Recherche dans cet index seul, sans catalogues ni vérification sur le disque
+    pub(super) fn search(&self, query: &str, limit: usize) -> Vec<SearchMatch> {
+        let query = SearchQuery::parse(query);
+        let words: Vec<&str> = query.text.split_whitespace().collect();
+        search_index(self, &query, &words, limit)
+            .into_iter()
+            .map(|result| SearchMatch::new(result, &query, &words))
+            .collect()
+    }
+}
+
+fn search_index(index: &Index, query: &SearchQuery, words: &[&str], limit: usize) -> Vec<SearchResult> {
+    // Au moins 1000 candidats, davantage pour les grandes pages de l'API
+    let max_candidates = limit.max(1000);
+    let candidates: Vec<u64> = match words.first() {
+        Some(first) => {
+            // Recherche dans l'index des noms (plus rapide)
+            let name_matches: Vec<u64> = index.name_index.iter()
+                .filter(|entry| entry.key().contains(first))
+                .flat_map(|entry| entry.value().clone())
+                .take(max_candidates)
+                .collect();
+
+            // Si pas assez de résultats, chercher dans les chemins
+            let path_matches: Vec<u64> = if name_matches.len() < max_candidates {
+                index.path_index.iter()
+                    .filter(|entry| entry.key().contains(first))
+                    .flat_map(|entry| entry.value().clone())
+                    .take(max_candidates - name_matches.len())
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+            name_matches.into_iter().chain(path_matches).collect()
+        }
+        // Uniquement des filtres (ex: "ext:pdf"), on parcourt tout l'index
+        None => index.files.iter().map(|entry| *entry.key()).collect(),
+    };
+
+    // Combiner et convertir les résultats
+    candidates.into_iter()
+        .filter_map(|id| index.files.get(&id))
+        .map(|entry| {
+            let mut result = entry.value().clone();
+            if let Some(volume) = catalog::volume_of(&result.path) {
+                result.volume = volume.display_name();
+            }
+            result
+        })
+        .filter(|result| contains_words(&result.name, &result.path, &words[1.min(words.len())..]))
+        .filter(|result| query.matches(result))
+        .take(limit)
+        .collect()
+}
+
+// None si le profil n'a pas encore d'index (jamais indexé ou inconnu)
+fn profile_index(profile: &str) -> Option<Arc<Index>> {
+    INDEXES.read().get(profile).cloned()
+}
+
+// Index d'un profil existant, créé vide pour y ajouter des entrées
+// (volume monté, réindexation avec élévation) avant sa première indexation
+fn writable_index(profile: &NamedProfile) -> Arc<Index> {
+    INDEXES.write()
+        .entry(profile.name.clone())
+        .or_insert_with(|| Arc::new(Index::new()))
+        .clone()
+}
+
+fn indexes_for(profile: Option<&str>) -> Vec<Arc<Index>> {
+    match profile {
+        Some(profile) => profile_index(profile).into_iter().collect(),
+        None => index_profiles().iter().filter_map(|profile| profile_index(&profile.name)).collect(),
+    }
+}
+
+fn all_indexes() -> Vec<Arc<Index>> {
+    INDEXES.read().values().cloned().collect()
+}
+
+fn saved_index_file(profile: &str) -> String {
+    // Le nom du profil est choisi par l'utilisateur, on évite les séparateurs de chemin
+    let safe_name: String = profile.chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' { c } else { '_' })
+        .collect();
+    format!("{}/{}.json", SAVED_INDEX_DIR, safe_name)
+}
+
+fn swap_index(profile: &str, index: Index) {
+    INDEXES.write().insert(profile.to_string(), Arc::new(index));
+    TERMS_DIRTY.store(true, Ordering::SeqCst);
+}
+
+fn contains_words(name: &str, path: &str, words: &[&str]) -> bool {
+    if words.is_empty() {
+        return true;
+    }
+    let name = name.to_lowercase();
+    let path = path.to_lowercase();
+    words.iter().all(|word| name.contains(word) || path.contains(word))
+}
+
+// Vrai si `path` est `root` ou se trouve dessous
+fn is_under(path: &str, root: &str) -> bool {
+    if !path.starts_with(root) {
+        return false;
+    }
+    let rest = &path[root.len()..];
+    rest.is_empty()
+        || root.ends_with(std::path::is_separator)
+        || rest.starts_with(std::path::is_separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, path: &str) -> SearchResult {
+        SearchResult {
+            id: 0,
+            name: name.to_string(),
+            path: path.to_string(),
+            size: 0,
+            is_dir: false,
+            modified: SystemTime::UNIX_EPOCH,
+            volume: String::new(),
+            offline: false,
+            attributes: None,
+            link_target: None,
+            hard_link: None,
+            archive: None,
+        }
+    }
+
+    #[test]
+    fn remove_ids_ignore_les_ids_absents() {
+        let index = Index::new();
+        index.insert(entry("rapport.pdf", "/docs/rapport.pdf"));
+
+        // Id inconnu, puis id déjà retiré : le compteur ne doit pas déborder
+        index.remove_ids(&HashSet::from([42]));
+        assert_eq!(index.file_count.load(Ordering::Relaxed), 1);
+        index.remove_ids(&HashSet::from([0]));
+        index.remove_ids(&HashSet::from([0]));
+        assert_eq!(index.file_count.load(Ordering::Relaxed), 0);
+        assert!(index.files.is_empty());
+    }
+
+    #[test]
+    fn remove_ids_nettoie_les_mots_de_l_entree() {
+        let index = Index::new();
+        index.insert(entry("été_vacances.jpg", "/photos/été_vacances.jpg"));
+        index.insert(entry("vacances.txt", "/notes/vacances.txt"));
+
+        index.remove_ids(&HashSet::from([0]));
+
+        // Les mots propres à l'entrée disparaissent, les mots partagés gardent l'autre id
+        assert!(!index.name_index.contains_key("été"));
+        assert!(!index.name_index.contains_key("jpg"));
+        assert!(!index.path_index.contains_key("photos"));
+        assert_eq!(*index.name_index.get("vacances").unwrap(), vec![1]);
+        assert_eq!(*index.path_index.get("notes").unwrap(), vec![1]);
+    }
+
+    #[cfg(unix)]
+    fn exchange_dir(name: &str, mode: u32) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = std::env::temp_dir().join(format!("suptool-echange-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::set_permissions(&dir, fs::Permissions::from_mode(mode)).unwrap();
+        fs::write(dir.join("request.json"), "{}").unwrap();
+        dir
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn check_exchange_paths_exige_un_dossier_prive() {
+        let private = exchange_dir("prive", 0o700);
+        assert!(check_exchange_paths(&private.join("request.json"), &private.join("report.json")).is_ok());
+        // Le rapport doit être écrit à côté de la requête
+        assert!(check_exchange_paths(&private.join("request.json"), Path::new("/etc/report.json")).is_err());
+
+        let shared = exchange_dir("partage", 0o755);
+        assert!(check_exchange_paths(&shared.join("request.json"), &shared.join("report.json")).is_err());
+
+        let _ = fs::remove_dir_all(&private);
+        let _ = fs::remove_dir_all(&shared);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn create_report_file_ne_suit_pas_un_lien_prepare() {
+        let dir = exchange_dir("lien", 0o700);
+        let target = dir.join("cible");
+        fs::write(&target, "intact").unwrap();
+        std::os::unix::fs::symlink(&target, dir.join("report.json")).unwrap();
+
+        assert!(create_report_file(&dir.join("report.json")).is_err());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "intact");
+        assert!(create_report_file(&dir.join("nouveau.json")).is_ok());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn valid_helper_root_refuse_liens_et_chemins_relatifs() {
+        let dir = fs::canonicalize(exchange_dir("racines", 0o700)).unwrap();
+        fs::create_dir_all(dir.join("privé")).unwrap();
+        std::os::unix::fs::symlink("/etc", dir.join("lien")).unwrap();
+        let root = |relative: &str| dir.join(relative).to_string_lossy().to_string();
+
+        assert!(valid_helper_root(&root("privé")));
+        assert!(!valid_helper_root(&root("lien")));
+        assert!(!valid_helper_root(&root("privé/../privé")));
+        assert!(!valid_helper_root(&root("absent")));
+        assert!(!valid_helper_root("privé"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_index_helper_applique_les_exclusions_de_chaque_profil() {
+        let dir = exchange_dir("helper", 0o700);
+        let root = fs::canonicalize(&dir).unwrap().join("données");
+        fs::create_dir_all(root.join("node_modules")).unwrap();
+        fs::write(root.join("node_modules").join("paquet.js"), "").unwrap();
+        fs::write(root.join("rapport.txt"), "").unwrap();
+        let root = root.to_string_lossy().to_string();
+
+        let request = IndexRequest {
+            walks: vec![
+                RetryWalk { profile: "Projets".to_string(), root: root.clone(), exclusions: vec!["node_modules".to_string()] },
+                RetryWalk { profile: "Tout".to_string(), root: root.clone(), exclusions: Vec::new() },
+            ],
+            options: IndexOptions::default(),
+            capture_attributes: false,
+        };
+        let (input, output) = (dir.join("request.json"), dir.join("report.json"));
+        fs::write(&input, serde_json::to_string(&request).unwrap()).unwrap();
+        run_index_helper(&input, &output).unwrap();
+
+        let report: IndexReport = serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+        let names = |profile: &str| {
+            let mut names: Vec<String> = report.results.iter()
+                .filter(|(name, _)| name == profile)
+                .map(|(_, result)| result.name.clone())
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names("Projets"), vec!["données", "rapport.txt"]);
+        assert_eq!(names("Tout"), vec!["données", "node_modules", "paquet.js", "rapport.txt"]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    fn walk(root: &Path, options: &IndexOptions) -> Vec<SearchResult> {
+        let results = Mutex::new(Vec::new());
+        let pacer = Pacer::new(options);
+        walk_root(&root.to_string_lossy(), options, &[], false, &pacer, &Arc::new(|| false), &|result| {
+            results.lock().push(result);
+        });
+        results.into_inner()
+    }
+
+    #[cfg(unix)]
+    fn walk_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("suptool-parcours-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::canonicalize(dir).unwrap()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn walk_root_suit_les_liens_sans_boucler_ni_doubler_les_dossiers() {
+        let dir = walk_dir("boucle");
+        fs::create_dir_all(dir.join("a").join("b")).unwrap();
+        fs::write(dir.join("a").join("b").join("été.txt"), "").unwrap();
+        // Boucle vers un ancêtre, et second chemin vers le même dossier
+        std::os::unix::fs::symlink(dir.join("a"), dir.join("a").join("b").join("retour")).unwrap();
+        std::os::unix::fs::symlink(dir.join("a").join("b"), dir.join("raccourci")).unwrap();
+
+        let options = IndexOptions { follow_links: true, ..IndexOptions::default() };
+        let results = walk(&dir, &options);
+
+        // La racine, puis "a" et "b" une seule fois chacun, quel que soit le
+        // chemin par lequel le walker les a atteints en premier
+        assert_eq!(results.iter().filter(|r| r.is_dir).count(), 3);
+        assert_eq!(results.iter().filter(|r| r.name == "été.txt").count(), 1);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn walk_root_identifie_les_liens_durs() {
+        let dir = walk_dir("liens-durs");
+        fs::write(dir.join("original.bin"), "données").unwrap();
+        fs::hard_link(dir.join("original.bin"), dir.join("copie.bin")).unwrap();
+        fs::write(dir.join("seul.bin"), "x").unwrap();
+
+        let results = walk(&dir, &IndexOptions::default());
+        let hard_link = |name: &str| results.iter().find(|r| r.name == name).unwrap().hard_link;
+
+        assert!(hard_link("original.bin").is_some());
+        assert_eq!(hard_link("original.bin"), hard_link("copie.bin"));
+        assert_eq!(hard_link("seul.bin"), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn walk_root_garde_la_cible_d_un_lien_casse() {
+        let dir = walk_dir("lien-casse");
+        std::os::unix::fs::symlink("/chemin/disparu", dir.join("cassé")).unwrap();
+
+        let results = walk(&dir, &IndexOptions::default());
+        let link = results.iter().find(|r| r.name == "cassé").unwrap();
+        assert_eq!(link.link_target.as_deref(), Some("/chemin/disparu"));
+        assert!(!link.is_dir);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,371 @@
+use crate::utils::{csv_field, get_data_dir, load_json, save_json};
+use serde::{Serialize, Deserialize};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::SystemTime;
+
+const SNAPSHOT_DIR: &str = "snapshots";
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SnapshotEntry {
+    pub path: String,
+    pub size: u64,
+    pub is_dir: bool,
+    // (périphérique, inode) d'un fichier à plusieurs liens durs
+    #[serde(default)]
+    pub hard_link: Option<(u64, u64)>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct IndexSnapshot {
+    pub name: String,
+    pub created: SystemTime,
+    pub entries: Vec<SnapshotEntry>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffKind {
+    Added,
+    Removed,
+    Grown,
+    Shrunk,
+}
+
+impl DiffKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Added => "ajouté",
+            Self::Removed => "supprimé",
+            Self::Grown => "agrandi",
+            Self::Shrunk => "réduit",
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DiffEntry {
+    pub kind: DiffKind,
+    pub path: String,
+    pub is_dir: bool,
+    pub old_size: u64,
+    pub new_size: u64,
+    pub delta: i64,
+}
+
+impl DiffEntry {
+    // Accompagné de l'identifiant de lien dur de l'entrée, pour le total
+    fn new(kind: DiffKind, entry: &SnapshotEntry, old_size: u64, new_size: u64) -> (Self, Option<(u64, u64)>) {
+        let diff = Self {
+            kind,
+            path: entry.path.clone(),
+            is_dir: entry.is_dir,
+            old_size,
+            new_size,
+            delta: new_size as i64 - old_size as i64,
+        };
+        (diff, entry.hard_link)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    pub old: String,
+    pub new: String,
+    pub total_delta: i64,
+    pub entries: Vec<DiffEntry>,
+}
+
+// Chemin, taille, dossier ou non, identifiant de lien dur
+pub type SnapshotFile = (String, u64, bool, Option<(u64, u64)>);
+
+impl IndexSnapshot {
+    // Les dossiers prennent la taille cumulée de leur contenu, sinon un
+    // dossier qui grossit n'apparaîtrait jamais dans le diff. Un fichier à
+    // plusieurs liens durs n'est compté qu'une fois dans ces totaux.
+    pub fn from_files<I>(name: &str, files: I) -> Self
+    where I: IntoIterator<Item = SnapshotFile> {
+        let files: Vec<SnapshotFile> = files.into_iter().collect();
+        let mut dir_sizes: HashMap<&str, u64> = files.iter()
+            .filter(|(_, _, is_dir, _)| *is_dir)
+            .map(|(path, _, _, _)| (path.as_str(), 0))
+            .collect();
+        let mut counted_links: HashSet<(u64, u64)> = HashSet::new();
+
+        for (path, size, is_dir, hard_link) in &files {
+            if *is_dir {
+                continue;
+            }
+            if let Some(id) = hard_link {
+                if !counted_links.insert(*id) {
+                    continue;
+                }
+            }
+            let mut current = Path::new(path.as_str()).parent();
+            while let Some(parent) = current {
+                if let Some(total) = parent.to_str().and_then(|p| dir_sizes.get_mut(p)) {
+                    *total += size;
+                }
+                current = parent.parent();
+            }
+        }
+
+        let entries = files.iter()
+            .map(|(path, size, is_dir, hard_link)| SnapshotEntry {
+                path: path.clone(),
+                size: if *is_dir { dir_sizes.get(path.as_str()).copied().unwrap_or(0) } else { *size },
+                is_dir: *is_dir,
+                hard_link: *hard_link,
+            })
+            .collect();
+
+        Self {
+            name: name.to_string(),
+            created: SystemTime::now(),
+            entries,
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        save_json(&snapshot_file(&self.name), self)
+    }
+
+    pub fn load(name: &str) -> Option<Self> {
+        load_json(&snapshot_file(name))
+    }
+
+    pub fn delete(name: &str) -> io::Result<()> {
+        fs::remove_file(get_data_dir().join(snapshot_file(name)))
+    }
+
+    pub fn list_names() -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(get_data_dir().join(SNAPSHOT_DIR))
+            .map(|entries| entries
+                .filter_map(|e| e.ok())
+                .filter_map(|e| {
+                    let file_name = e.file_name().to_string_lossy().to_string();
+                    file_name.strip_suffix(".json").map(|s| s.to_string())
+                })
+                .collect())
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+}
+
+impl SnapshotDiff {
+    pub fn between(old: &IndexSnapshot, new: &IndexSnapshot) -> Self {
+        let old_entries: HashMap<&str, &SnapshotEntry> = old.entries.iter()
+            .map(|e| (e.path.as_str(), e))
+            .collect();
+        let new_entries: HashMap<&str, &SnapshotEntry> = new.entries.iter()
+            .map(|e| (e.path.as_str(), e))
+            .collect();
+
+        let mut entries = Vec::new();
+
+        for entry in &new.entries {
+            match old_entries.get(entry.path.as_str()) {
+                None => entries.push(DiffEntry::new(DiffKind::Added, entry, 0, entry.size)),
+                Some(previous) if previous.size != entry.size => {
+                    let kind = if entry.size > previous.size { DiffKind::Grown } else { DiffKind::Shrunk };
+                    entries.push(DiffEntry::new(kind, entry, previous.size, entry.size));
+                }
+                Some(_) => {}
+            }
+        }
+
+        for entry in &old.entries {
+            if !new_entries.contains_key(entry.path.as_str()) {
+                entries.push(DiffEntry::new(DiffKind::Removed, entry, entry.size, 0));
+            }
+        }
+
+        // Seuls les fichiers comptent dans le total, les dossiers les incluent
+        // déjà. Comme dans les dossiers, un fichier à plusieurs liens durs n'y
+        // est compté qu'une fois, et retirer un de ses liens ne libère rien.
+        let remaining_links: HashSet<(u64, u64)> = new.entries.iter()
+            .filter_map(|e| e.hard_link)
+            .collect();
+        let mut counted_links: HashSet<(u64, u64)> = HashSet::new();
+        let total_delta = entries.iter()
+            .filter(|(e, _)| !e.is_dir)
+            .filter(|(e, hard_link)| match hard_link {
+                Some(id) if e.kind == DiffKind::Removed && remaining_links.contains(id) => false,
+                Some(id) => counted_links.insert(*id),
+                None => true,
+            })
+            .map(|(e, _)| e.delta)
+            .sum();
+
+        // Les plus gros changements en premier
+        let mut entries: Vec<DiffEntry> = entries.into_iter().map(|(e, _)| e).collect();
+        entries.sort_by_key(|entry| Reverse(entry.delta.unsigned_abs()));
+
+        Self {
+            old: old.name.clone(),
+            new: new.name.clone(),
+            total_delta,
+            entries,
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("changement,chemin,type,ancienne taille,nouvelle taille,écart\n");
+        for entry in &self.entries {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{}\n",
+                entry.kind.label(),
+                csv_field(&entry.path),
+                if entry.is_dir { "dossier" } else { "fichier" },
+                entry.old_size,
+                entry.new_size,
+                entry.delta,
+            ));
+        }
+        csv
+    }
+}
+
+fn snapshot_file(name: &str) -> String {
+    // Le nom est choisi par l'utilisateur, on évite les séparateurs de chemin
+    let safe_name: String = name.chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' { c } else { '_' })
+        .collect();
+    format!("{}/{}.json", SNAPSHOT_DIR, safe_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, size: u64) -> SnapshotFile {
+        (path.to_string(), size, false, None)
+    }
+
+    fn dir(path: &str) -> SnapshotFile {
+        (path.to_string(), 0, true, None)
+    }
+
+    fn size_of(snapshot: &IndexSnapshot, path: &str) -> u64 {
+        snapshot.entries.iter().find(|e| e.path == path).unwrap().size
+    }
+
+    #[test]
+    fn from_files_donne_aux_dossiers_la_taille_de_leur_contenu() {
+        let snapshot = IndexSnapshot::from_files("s", vec![
+            dir("/data"),
+            dir("/data/docs"),
+            file("/data/docs/a.txt", 10),
+            file("/data/docs/b.txt", 5),
+            file("/data/c.bin", 100),
+        ]);
+
+        assert_eq!(size_of(&snapshot, "/data/docs"), 15);
+        assert_eq!(size_of(&snapshot, "/data"), 115);
+        assert_eq!(size_of(&snapshot, "/data/c.bin"), 100);
+    }
+
+    #[test]
+    fn from_files_compte_une_fois_les_liens_durs_dans_les_dossiers() {
+        let snapshot = IndexSnapshot::from_files("s", vec![
+            dir("/data"),
+            ("/data/a".to_string(), 40, false, Some((1, 7))),
+            ("/data/b".to_string(), 40, false, Some((1, 7))),
+        ]);
+
+        assert_eq!(size_of(&snapshot, "/data"), 40);
+        // Chaque lien garde sa taille propre
+        assert_eq!(size_of(&snapshot, "/data/b"), 40);
+    }
+
+    #[test]
+    fn between_signale_ajouts_suppressions_et_changements_de_taille() {
+        let old = IndexSnapshot::from_files("old", vec![
+            dir("/data"),
+            file("/data/kept", 10),
+            file("/data/grows", 10),
+            file("/data/shrinks", 50),
+            file("/data/removed", 7),
+        ]);
+        let new = IndexSnapshot::from_files("new", vec![
+            dir("/data"),
+            file("/data/kept", 10),
+            file("/data/grows", 30),
+            file("/data/shrinks", 20),
+            file("/data/added", 100),
+        ]);
+
+        let diff = SnapshotDiff::between(&old, &new);
+        let kind_of = |path: &str| diff.entries.iter().find(|e| e.path == path).map(|e| e.kind);
+
+        assert_eq!(kind_of("/data/added"), Some(DiffKind::Added));
+        assert_eq!(kind_of("/data/removed"), Some(DiffKind::Removed));
+        assert_eq!(kind_of("/data/grows"), Some(DiffKind::Grown));
+        assert_eq!(kind_of("/data/shrinks"), Some(DiffKind::Shrunk));
+        assert_eq!(kind_of("/data/kept"), None);
+        assert_eq!(kind_of("/data"), Some(DiffKind::Grown));
+
+        // Fichiers seulement : +100 +20 -30 -7, le dossier n'est pas compté deux fois
+        assert_eq!(diff.total_delta, 83);
+        assert_eq!((diff.old.as_str(), diff.new.as_str()), ("old", "new"));
+    }
+
+    #[test]
+    fn between_compte_une_fois_les_liens_durs_dans_le_total() {
+        let link = |path: &str, size: u64| (path.to_string(), size, false, Some((1, 7)));
+        let old = IndexSnapshot::from_files("old", vec![dir("/data"), link("/data/a", 40), link("/data/b", 40)]);
+        let new = IndexSnapshot::from_files("new", vec![dir("/data"), link("/data/a", 100), link("/data/b", 100)]);
+
+        let diff = SnapshotDiff::between(&old, &new);
+        assert_eq!(diff.entries.iter().filter(|e| e.kind == DiffKind::Grown && !e.is_dir).count(), 2);
+        assert_eq!(diff.total_delta, 60);
+
+        // Un lien supprimé ne libère rien tant que l'autre reste
+        let new = IndexSnapshot::from_files("new", vec![dir("/data"), link("/data/a", 40)]);
+        assert_eq!(SnapshotDiff::between(&old, &new).total_delta, 0);
+    }
+
+    #[test]
+    fn between_trie_les_plus_gros_changements_d_abord() {
+        let old = IndexSnapshot::from_files("old", vec![file("/a", 10), file("/b", 1000)]);
+        let new = IndexSnapshot::from_files("new", vec![file("/a", 15), file("/c", 200)]);
+
+        let diff = SnapshotDiff::between(&old, &new);
+        let order: Vec<&str> = diff.entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(order, vec!["/b", "/c", "/a"]);
+        assert_eq!(diff.entries[0].delta, -1000);
+    }
+
+    #[test]
+    fn between_ne_trouve_rien_entre_deux_instantanes_identiques() {
+        let files = vec![dir("/data"), file("/data/a", 1)];
+        let diff = SnapshotDiff::between(
+            &IndexSnapshot::from_files("a", files.clone()),
+            &IndexSnapshot::from_files("b", files)
+        );
+        assert!(diff.entries.is_empty());
+        assert_eq!(diff.total_delta, 0);
+    }
+
+    #[test]
+    fn to_csv_echappe_les_chemins() {
+        let old = IndexSnapshot::from_files("old", Vec::new());
+        let new = IndexSnapshot::from_files("new", vec![file("/a,b", 3)]);
+        let csv = SnapshotDiff::between(&old, &new).to_csv();
+        assert_eq!(csv.lines().nth(1), Some("ajouté,\"/a,b\",fichier,0,3,3"));
+    }
+
+    #[test]
+    fn snapshot_file_reste_dans_le_dossier_des_instantanes() {
+        assert_eq!(snapshot_file("../etc/passwd"), "snapshots/___etc_passwd.json");
+        assert_eq!(snapshot_file("Before update"), "snapshots/Before update.json");
+    }
+}
@@ -0,0 +1,200 @@
+use super::query::{QUERY_FIELDS, TYPE_VALUES};
+use super::tags::all_tags;
+
+const MAX_DISTANCE: usize = 2;
+// Les mots plus courts ne sont pas indexés (voir `name_terms` dans searcher.rs) :
+// absents des termes, ils seraient toujours « corrigés »
+const MIN_TERM_BYTES: usize = 3;
+
+// Termes de l'index triés par ordre alphabétique, avec leur nombre d'occurrences
+pub type Terms = Vec<(String, usize)>;
+
+pub fn complete(input: &str, terms: &Terms, extensions: &[(String, u64)], limit: usize) -> Vec<String> {
+    // On ne complète que le dernier mot, le reste de la requête est conservé
+    let split_at = input.rfind(char::is_whitespace).map(|i| i + 1).unwrap_or(0);
+    let (head, token) = input.split_at(split_at);
+    let token = token.to_lowercase();
+
+    if token.is_empty() {
+        return Vec::new();
+    }
+
+    let completions: Vec<String> = match token.split_once(':') {
+        Some(("ext", partial)) => {
+            // Les extensions arrivent triées par fréquence
+            let partial = partial.trim_start_matches('.');
+            extensions.iter()
+                .filter(|(ext, _)| ext.starts_with(partial) && ext.as_str() != partial)
+                .take(limit)
+                .map(|(ext, _)| format!("ext:{}", ext))
+                .collect()
+        }
+        Some(("type", partial)) => TYPE_VALUES.iter()
+            .filter(|value| value.starts_with(partial) && **value != partial)
+            .map(|value| format!("type:{}", value))
+            .collect(),
+        Some(("tag", partial)) => all_tags().into_iter()
+            .filter(|tag| tag.starts_with(partial) && tag != partial)
+            .take(limit)
+            .map(|tag| format!("tag:{}", tag))
+            .collect(),
+        Some(_) => Vec::new(),
+        None => {
+            let fields = QUERY_FIELDS.iter()
+                .filter(|field| field.starts_with(token.as_str()))
+                .map(|field| field.to_string());
+
+            let start = terms.partition_point(|(term, _)| term.as_str() < token.as_str());
+            let mut words: Vec<&(String, usize)> = terms[start..].iter()
+                .take_while(|(term, _)| term.starts_with(token.as_str()))
+                .filter(|(term, _)| *term != token)
+                .collect();
+            words.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+            fields
+                .chain(words.into_iter().map(|(term, _)| term.clone()))
+                .take(limit)
+                .collect()
+        }
+    };
+
+    completions.into_iter()
+        .map(|completion| format!("{}{}", head, completion))
+        .collect()
+}
+
+pub fn did_you_mean(text: &str, terms: &Terms) -> Option<String> {
+    let mut changed = false;
+    let corrected: Vec<String> = text.split_whitespace()
+        .map(|word| {
+            let word = word.to_lowercase();
+            if word.len() < MIN_TERM_BYTES
+                || terms.binary_search_by(|(term, _)| term.as_str().cmp(word.as_str())).is_ok() {
+                return word;
+            }
+            match closest_term(&word, terms) {
+                Some(term) => {
+                    changed = true;
+                    term
+                }
+                None => word,
+            }
+        })
+        .collect();
+
+    changed.then(|| corrected.join(" "))
+}
+
+fn closest_term(word: &str, terms: &Terms) -> Option<String> {
+    let length = word.chars().count();
+
+    terms.iter()
+        .filter(|(term, _)| term.chars().count().abs_diff(length) <= MAX_DISTANCE)
+        .filter_map(|(term, count)| {
+            let distance = levenshtein(word, term);
+            (distance <= MAX_DISTANCE).then_some((distance, *count, term))
+        })
+        // La plus petite distance d'abord, puis le terme le plus fréquent
+        .min_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)))
+        .map(|(_, _, term)| term.clone())
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            current[j + 1] = (previous[j] + cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(words: &[(&str, usize)]) -> Terms {
+        let mut terms: Terms = words.iter().map(|(w, c)| (w.to_string(), *c)).collect();
+        terms.sort();
+        terms
+    }
+
+    #[test]
+    fn levenshtein_compte_les_caracteres_et_non_les_octets() {
+        assert_eq!(levenshtein("rapport", "rapport"), 0);
+        assert_eq!(levenshtein("rapprot", "rapport"), 2);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("abc", ""), 3);
+        assert_eq!(levenshtein("ete", "été"), 2);
+        assert_eq!(levenshtein("facture", "factüre"), 1);
+        assert_eq!(levenshtein("noël", "noel"), 1);
+    }
+
+    #[test]
+    fn did_you_mean_ne_corrige_que_les_mots_inconnus() {
+        let terms = terms(&[("rapport", 10), ("annuel", 4), ("facture", 2)]);
+        assert_eq!(did_you_mean("rapprot annuel", &terms), Some("rapport annuel".to_string()));
+        assert_eq!(did_you_mean("rapport annuel", &terms), None);
+        // Trop loin de tout terme connu
+        assert_eq!(did_you_mean("zzzzzz", &terms), None);
+    }
+
+    #[test]
+    fn did_you_mean_laisse_les_mots_trop_courts_pour_l_index() {
+        let terms = terms(&[("rapport", 10), ("ion", 4), ("ofx", 2), ("vue", 3)]);
+        assert_eq!(did_you_mean("io of v2", &terms), None);
+        assert_eq!(did_you_mean("a rapprot", &terms), Some("a rapport".to_string()));
+    }
+
+    #[test]
+    fn did_you_mean_gere_accents_et_majuscules() {
+        let terms = terms(&[("données", 3), ("été", 5), ("école", 1)]);
+        assert_eq!(did_you_mean("Donnees", &terms), Some("données".to_string()));
+        assert_eq!(did_you_mean("ÉTÉ", &terms), None);
+        assert_eq!(did_you_mean("ecole", &terms), Some("école".to_string()));
+    }
+
+    #[test]
+    fn did_you_mean_prefere_le_plus_proche_puis_le_plus_frequent() {
+        let terms = terms(&[("chat", 1), ("chats", 50), ("chut", 20)]);
+        // "chat" est à distance 1 de "chas", comme "chats" : le plus fréquent gagne
+        assert_eq!(did_you_mean("chas", &terms), Some("chats".to_string()));
+        assert_eq!(did_you_mean("chaat", &terms), Some("chat".to_string()));
+    }
+
+    #[test]
+    fn complete_le_dernier_mot_avec_les_termes_les_plus_frequents() {
+        let terms = terms(&[("rapport", 10), ("rapide", 30), ("rappel", 5), ("radio", 1)]);
+        assert_eq!(
+            complete("budget rap", &terms, &[], 10),
+            vec!["budget rapide", "budget rapport", "budget rappel"]
+        );
+        assert_eq!(complete("rap", &terms, &[], 1), vec!["rapide"]);
+        assert!(complete("budget ", &terms, &[], 10).is_empty());
+    }
+
+    #[test]
+    fn complete_les_prefixes_non_ascii() {
+        let terms = terms(&[("élève", 2), ("élan", 7), ("elle", 9)]);
+        assert_eq!(complete("Él", &terms, &[], 10), vec!["élan", "élève"]);
+        assert_eq!(complete("mes é", &terms, &[], 10), vec!["mes élan", "mes élève"]);
+    }
+
+    #[test]
+    fn complete_les_champs_de_requete_et_leurs_valeurs() {
+        let extensions = vec![("pdf".to_string(), 40), ("png".to_string(), 12), ("py".to_string(), 3)];
+        assert_eq!(complete("ext:p", &Terms::new(), &extensions, 10), vec!["ext:pdf", "ext:png", "ext:py"]);
+        assert_eq!(complete("ext:.pn", &Terms::new(), &extensions, 10), vec!["ext:png"]);
+        assert_eq!(complete("type:f", &Terms::new(), &[], 10), vec!["type:file", "type:folder"]);
+        assert_eq!(complete("ty", &Terms::new(), &[], 10), vec!["type:"]);
+        assert!(complete("size:1", &Terms::new(), &[], 10).is_empty());
+    }
+}
@@ -0,0 +1,179 @@
+use crate::utils::{file_id, load_json, save_json};
+use serde::{Serialize, Deserialize};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const TAGS_FILE: &str = "tags.json";
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct FileTags {
+    pub tags: Vec<String>,
+    pub note: String,
+    // (périphérique, inode) au moment de l'annotation, pour retrouver le
+    // fichier s'il est déplacé (non disponible sous Windows)
+    #[serde(default)]
+    pub file_id: Option<(u64, u64)>,
+}
+
+// Annotations indexées par chemin, avec l'index inverse des inodes
+// consulté pour chaque fichier pendant l'indexation
+struct TagStore {
+    files: HashMap<String, FileTags>,
+    ids: HashMap<(u64, u64), String>,
+}
+
+impl TagStore {
+    fn load() -> Self {
+        let mut store = Self {
+            files: load_json(TAGS_FILE).unwrap_or_default(),
+            ids: HashMap::new(),
+        };
+        store.ids = store.files.iter()
+            .filter_map(|(path, entry)| entry.file_id.map(|id| (id, path.clone())))
+            .collect();
+        store
+    }
+
+    fn remove(&mut self, path: &str) -> Option<FileTags> {
+        let entry = self.files.remove(path)?;
+        if let Some(id) = entry.file_id {
+            self.ids.remove(&id);
+        }
+        Some(entry)
+    }
+
+    fn insert(&mut self, path: String, entry: FileTags) {
+        self.remove(&path);
+        if let Some(id) = entry.file_id {
+            self.ids.insert(id, path.clone());
+        }
+        self.files.insert(path, entry);
+    }
+
+    fn save(&self) -> io::Result<()> {
+        save_json(TAGS_FILE, &self.files)
+    }
+}
+
+static TAGS: Lazy<RwLock<TagStore>> = Lazy::new(|| RwLock::new(TagStore::load()));
+static TRACK_MOVES: AtomicBool = AtomicBool::new(true);
+
+pub fn file_tags(path: &str) -> Option<FileTags> {
+    TAGS.read().files.get(path).cloned()
+}
+
+// `tags` est la saisie brute, séparée par des virgules ou des espaces.
+// Sans tag ni note, l'entrée est supprimée.
+pub fn set_file_tags(path: &str, tags: &str, note: &str) -> io::Result<()> {
+    let tags = parse_tags(tags);
+    let note = note.trim().to_string();
+
+    let mut store = TAGS.write();
+    if tags.is_empty() && note.is_empty() {
+        store.remove(path);
+    } else {
+        let file_id = fs::symlink_metadata(path).ok().and_then(|metadata| file_id(&metadata));
+        store.insert(path.to_string(), FileTags { tags, note, file_id });
+    }
+    store.save()
+}
+
+// Tags en minuscules, sans `#`, dédoublonnés et triés
+fn parse_tags(input: &str) -> Vec<String> {
+    input.split(|c: char| c == ',' || c.is_whitespace())
+        .map(|tag| tag.trim().trim_start_matches('#').to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+pub fn has_tag(path: &str, tag: &str) -> bool {
+    TAGS.read()
+        .files
+        .get(path)
+        .is_some_and(|entry| entry.tags.iter().any(|t| t == tag))
+}
+
+// Tous les tags utilisés, triés par nombre de fichiers
+pub fn all_tags() -> Vec<String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for entry in TAGS.read().files.values() {
+        for tag in &entry.tags {
+            *counts.entry(tag.clone()).or_insert(0) += 1;
+        }
+    }
+    let mut tags: Vec<(String, usize)> = counts.into_iter().collect();
+    tags.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    tags.into_iter().map(|(tag, _)| tag).collect()
+}
+
+// Pour le processus d'indexation élevé : il tourne en root et ouvrirait la
+// base de tags de root au lieu de celle de l'utilisateur
+pub fn disable_move_tracking() {
+    TRACK_MOVES.store(false, Ordering::Relaxed);
+}
+
+// Appelé pour chaque fichier indexé : si un fichier annoté a disparu de son
+// ancien chemin et que son inode réapparaît ailleurs, l'annotation le suit
+pub fn track_move(id: (u64, u64), path: &Path) {
+    if !TRACK_MOVES.load(Ordering::Relaxed) {
+        return;
+    }
+    let old_path = {
+        let store = TAGS.read();
+        match store.ids.get(&id) {
+            Some(old_path) if Path::new(old_path) != path => old_path.clone(),
+            _ => return,
+        }
+    };
+    // Lien dur ou inode réutilisé : l'ancien chemin existe encore
+    if Path::new(&old_path).exists() {
+        return;
+    }
+
+    let mut store = TAGS.write();
+    if let Some(entry) = store.remove(&old_path) {
+        let new_path = path.to_string_lossy().to_string();
+        println!("🏷️ Annotations déplacées : {} → {}", old_path, new_path);
+        store.insert(new_path, entry);
+        if let Err(e) = store.save() {
+            eprintln!("Impossible d'enregistrer les tags: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(file_id: Option<(u64, u64)>) -> FileTags {
+        FileTags { tags: vec!["projet".to_string()], note: String::new(), file_id }
+    }
+
+    #[test]
+    fn parse_tags_normalise_la_saisie() {
+        assert_eq!(parse_tags("#Été, projet  urgent,,PROJET"), vec!["projet", "urgent", "été"]);
+        assert!(parse_tags(" , # ").is_empty());
+    }
+
+    #[test]
+    fn tag_store_garde_l_index_des_inodes_a_jour() {
+        let mut store = TagStore { files: HashMap::new(), ids: HashMap::new() };
+        store.insert("/docs/été.txt".to_string(), entry(Some((1, 42))));
+        assert_eq!(store.ids.get(&(1, 42)).map(String::as_str), Some("/docs/été.txt"));
+
+        // Réannoté avec un autre inode : l'ancien ne pointe plus vers le fichier
+        store.insert("/docs/été.txt".to_string(), entry(Some((1, 43))));
+        assert!(!store.ids.contains_key(&(1, 42)));
+
+        assert!(store.remove("/docs/été.txt").is_some());
+        assert!(store.ids.is_empty() && store.files.is_empty());
+        assert!(store.remove("/docs/été.txt").is_none());
+    }
+}
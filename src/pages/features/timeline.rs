@@ -0,0 +1,241 @@
+use super::profiles::index_profiles;
+use crate::utils::get_drives;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+// Regroupement des événements du watcher avant de rafraîchir l'affichage
+const WATCH_BATCH_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TimeWindow {
+    Hour,
+    Day,
+    Week,
+    Month,
+}
+
+impl TimeWindow {
+    // Même ordre que la liste déroulante de la page Timeline
+    pub fn from_index(index: i32) -> Self {
+        match index {
+            1 => TimeWindow::Day,
+            2 => TimeWindow::Week,
+            3 => TimeWindow::Month,
+            _ => TimeWindow::Hour,
+        }
+    }
+
+    pub fn since(&self) -> SystemTime {
+        let hours = match self {
+            TimeWindow::Hour => 1,
+            TimeWindow::Day => 24,
+            TimeWindow::Week => 24 * 7,
+            TimeWindow::Month => 24 * 30,
+        };
+        SystemTime::now() - Duration::from_secs(hours * 3600)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Created,
+    Modified,
+}
+
+impl ChangeKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeKind::Created => "créé",
+            ChangeKind::Modified => "modifié",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct TimelineEntry {
+    pub path: String,
+    pub name: String,
+    pub is_dir: bool,
+    pub size: u64,
+    pub time: SystemTime,
+    pub kind: ChangeKind,
+}
+
+impl TimelineEntry {
+    // Entrée relevée sur le disque (événement du watcher)
+    fn from_disk(path: &Path, kind: ChangeKind) -> Option<Self> {
+        let metadata = fs::symlink_metadata(path).ok()?;
+        Some(Self {
+            path: path.to_string_lossy().to_string(),
+            name: path.file_name()?.to_string_lossy().to_string(),
+            is_dir: metadata.is_dir(),
+            size: metadata.len(),
+            time: metadata.modified().unwrap_or_else(|_| SystemTime::now()),
+            kind,
+        })
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct TimelineFilter {
+    // None = toutes les racines
+    pub root: Option<String>,
+    // None = fichiers et dossiers
+    pub is_dir: Option<bool>,
+}
+
+impl TimelineFilter {
+    pub fn matches(&self, path: &str, is_dir: bool) -> bool {
+        self.is_dir.is_none_or(|wanted| wanted == is_dir)
+            && self.root.as_ref().is_none_or(|root| Path::new(path).starts_with(root))
+    }
+}
+
+// Racines proposées dans le filtre : celles des profils (ou du seul profil
+// `scope`), ou les disques pour un profil qui couvre tout le système
+pub fn timeline_roots(scope: Option<&str>) -> Vec<String> {
+    let mut roots: Vec<String> = Vec::new();
+    let profiles = index_profiles()
+        .into_iter()
+        .filter(|profile| scope.is_none_or(|scope| profile.name == scope));
+    for profile in profiles {
+        let profile_roots = if profile.roots.is_empty() { get_drives() } else { profile.roots };
+        for root in profile_roots {
+            if !roots.contains(&root) {
+                roots.push(root);
+            }
+        }
+    }
+    roots
+}
+
+// Ajoute les entrées récentes à `entries` (un chemin n'apparaît qu'une fois,
+// avec son changement le plus récent) et trie du plus récent au plus ancien
+pub fn merge_entries(entries: &mut Vec<TimelineEntry>, recent: Vec<TimelineEntry>) {
+    let mut by_path: HashMap<String, TimelineEntry> = entries.drain(..)
+        .map(|entry| (entry.path.clone(), entry))
+        .collect();
+    for mut entry in recent {
+        match by_path.get(&entry.path) {
+            Some(existing) if existing.time > entry.time => {}
+            existing => {
+                // Un fichier créé puis écrit (téléchargement...) reste « créé »
+                if existing.is_some_and(|existing| existing.kind == ChangeKind::Created) {
+                    entry.kind = ChangeKind::Created;
+                }
+                by_path.insert(entry.path.clone(), entry);
+            }
+        }
+    }
+    entries.extend(by_path.into_values());
+    entries.sort_by_key(|entry| Reverse(entry.time));
+}
+
+// Sous Linux et macOS, surveiller tout un disque pose un watch par dossier :
+// seules les racines plus ciblées sont suivies en direct
+fn can_watch(root: &str) -> bool {
+    cfg!(windows) || Path::new(root).parent().is_some()
+}
+
+// Surveille `roots` et passe les fichiers créés ou modifiés à `on_changes`,
+// par lots. Renvoie aussi le nombre de racines suivies ; le watcher s'arrête
+// quand il est détruit.
+pub fn watch_roots<F>(roots: &[String], on_changes: F) -> notify::Result<(RecommendedWatcher, usize)>
+where F: Fn(Vec<TimelineEntry>) + Send + 'static {
+    let (sender, receiver) = mpsc::channel::<Event>();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        if let Ok(event) = event {
+            let _ = sender.send(event);
+        }
+    })?;
+
+    let mut watched = 0;
+    for root in roots.iter().filter(|root| can_watch(root)) {
+        // Limite de watches atteinte, dossier illisible... : les autres racines restent suivies
+        match watcher.watch(Path::new(root), RecursiveMode::Recursive) {
+            Ok(()) => watched += 1,
+            Err(e) => eprintln!("Impossible de surveiller {}: {}", root, e),
+        }
+    }
+
+    // Le thread s'arrête quand le watcher (et donc l'émetteur) est détruit
+    thread::spawn(move || {
+        while let Ok(event) = receiver.recv() {
+            let mut events = vec![event];
+            thread::sleep(WATCH_BATCH_DELAY);
+            events.extend(receiver.try_iter());
+
+            let mut changes: Vec<TimelineEntry> = Vec::new();
+            for event in events {
+                let kind = match event.kind {
+                    EventKind::Create(_) => ChangeKind::Created,
+                    EventKind::Modify(_) => ChangeKind::Modified,
+                    _ => continue,
+                };
+                changes.extend(event.paths.iter().filter_map(|path| TimelineEntry::from_disk(path, kind)));
+            }
+
+            if !changes.is_empty() {
+                on_changes(changes);
+            }
+        }
+    });
+
+    Ok((watcher, watched))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, seconds: u64, kind: ChangeKind) -> TimelineEntry {
+        TimelineEntry {
+            path: path.to_string(),
+            name: Path::new(path).file_name().unwrap().to_string_lossy().to_string(),
+            is_dir: false,
+            size: 0,
+            time: SystemTime::UNIX_EPOCH + Duration::from_secs(seconds),
+            kind,
+        }
+    }
+
+    #[test]
+    fn merge_entries_garde_le_changement_le_plus_recent() {
+        let mut entries = vec![entry("/docs/a.txt", 10, ChangeKind::Modified), entry("/docs/b.txt", 30, ChangeKind::Modified)];
+        merge_entries(&mut entries, vec![
+            entry("/docs/a.txt", 40, ChangeKind::Modified),
+            // Plus ancien que l'entrée connue : ignoré
+            entry("/docs/b.txt", 20, ChangeKind::Created),
+            entry("/docs/été.txt", 35, ChangeKind::Created),
+        ]);
+
+        let paths: Vec<&str> = entries.iter().map(|entry| entry.path.as_str()).collect();
+        assert_eq!(paths, ["/docs/a.txt", "/docs/été.txt", "/docs/b.txt"]);
+        assert!(entries[2].kind == ChangeKind::Modified);
+    }
+
+    #[test]
+    fn merge_entries_un_fichier_cree_puis_modifie_reste_cree() {
+        let mut entries = vec![entry("/dl/film.mkv", 10, ChangeKind::Created)];
+        merge_entries(&mut entries, vec![entry("/dl/film.mkv", 20, ChangeKind::Modified)]);
+
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].kind == ChangeKind::Created);
+        assert_eq!(entries[0].time, SystemTime::UNIX_EPOCH + Duration::from_secs(20));
+    }
+
+    #[test]
+    fn filter_compare_les_racines_par_composants() {
+        let filter = TimelineFilter { root: Some("/data/Projets".to_string()), is_dir: Some(false) };
+        assert!(filter.matches("/data/Projets/notes.txt", false));
+        assert!(!filter.matches("/data/Projets2/notes.txt", false));
+        assert!(!filter.matches("/data/Projets/sous-dossier", true));
+        assert!(TimelineFilter::default().matches("/n'importe/où", true));
+    }
+}
@@ -0,0 +1,247 @@
+use crate::slint_generated::{MainWindow, AppLogic, CleanupItem};
+use crate::pages::features::{remove_candidates, CleanupCandidate, CleanupKind, FileSearcher, ProtectedPaths};
+use crate::widgets::show_notification;
+use slint::{ComponentHandle, Model, Weak, ModelRc, VecModel, SharedString};
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::thread;
+
+// Candidats du dernier scan et leur case cochée
+type Selection = Arc<Mutex<Vec<(CleanupCandidate, bool)>>>;
+
+// `searcher` est celui d'EverySup : le scan porte sur le profil sélectionné
+pub fn init(window: &Weak<MainWindow>, searcher: Arc<FileSearcher>) {
+    let selection: Selection = Arc::new(Mutex::new(Vec::new()));
+    let protected = Arc::new(Mutex::new(ProtectedPaths::load()));
+
+    if let Some(window) = window.upgrade() {
+        let logic = window.global::<AppLogic>();
+        update_protected_paths(&logic, &protected.lock());
+
+        logic.on_scan_cleanup({
+            let searcher = searcher.clone();
+            let selection = selection.clone();
+            let protected = protected.clone();
+            let window_weak = window.as_weak();
+            move || {
+                if searcher.file_count() == 0 {
+                    show_notification(
+                        &window_weak,
+                        "cleanup-empty",
+                        "Index vide",
+                        "Lancez une indexation avant de chercher les éléments à nettoyer",
+                        "warning"
+                    );
+                    return;
+                }
+
+                if let Some(window) = window_weak.upgrade() {
+                    window.global::<AppLogic>().set_cleanup_scanning(true);
+                }
+
+                let searcher = searcher.clone();
+                let selection = selection.clone();
+                let protected = protected.lock().clone();
+                let window_weak = window_weak.clone();
+
+                thread::spawn(move || {
+                    let scan = searcher.find_cleanup_candidates(&protected);
+                    let hidden = scan.protected;
+                    *selection.lock() = scan.candidates.into_iter().map(|c| (c, false)).collect();
+
+                    slint::invoke_from_event_loop(move || {
+                        if let Some(window) = window_weak.upgrade() {
+                            let logic = window.global::<AppLogic>();
+                            logic.set_cleanup_scanning(false);
+                            update_items(&logic, &selection.lock());
+                            logic.set_cleanup_summary(summary(&selection.lock(), hidden));
+                        }
+                    }).unwrap();
+                });
+            }
+        });
+
+        logic.on_toggle_cleanup_item({
+            let selection = selection.clone();
+            let window_weak = window.as_weak();
+            move |index, checked| {
+                let mut selection = selection.lock();
+                let Some(entry) = selection.get_mut(index as usize) else {
+                    return;
+                };
+                entry.1 = checked;
+
+                if let Some(window) = window_weak.upgrade() {
+                    let logic = window.global::<AppLogic>();
+                    logic.get_cleanup_items().set_row_data(index as usize, to_item(entry));
+                    logic.set_cleanup_selected(selected_count(&selection));
+                }
+            }
+        });
+
+        logic.on_select_all_cleanup({
+            let selection = selection.clone();
+            let window_weak = window.as_weak();
+            move |checked| {
+                let mut selection = selection.lock();
+                selection.iter_mut().for_each(|entry| entry.1 = checked);
+                if let Some(window) = window_weak.upgrade() {
+                    update_items(&window.global::<AppLogic>(), &selection);
+                }
+            }
+        });
+
+        logic.on_remove_cleanup({
+            let searcher = searcher.clone();
+            let selection = selection.clone();
+            let protected = protected.clone();
+            let window_weak = window.as_weak();
+            move |to_trash| {
+                let candidates: Vec<CleanupCandidate> = selection.lock()
+                    .iter()
+                    .filter(|(_, checked)| *checked)
+                    .map(|(candidate, _)| candidate.clone())
+                    .collect();
+                if candidates.is_empty() {
+                    return;
+                }
+
+                let searcher = searcher.clone();
+                let selection = selection.clone();
+                let protected = protected.lock().clone();
+                let window_weak = window_weak.clone();
+
+                thread::spawn(move || {
+                    let report = remove_candidates(&candidates, to_trash, &protected);
+                    searcher.remove_paths(&report.removed);
+                    selection.lock().retain(|(candidate, _)| !report.removed.contains(&candidate.path));
+
+                    slint::invoke_from_event_loop(move || {
+                        if let Some(window) = window_weak.upgrade() {
+                            let logic = window.global::<AppLogic>();
+                            update_items(&logic, &selection.lock());
+                            logic.set_cleanup_summary(summary(&selection.lock(), 0));
+                        }
+
+                        let action = if to_trash { "mises à la corbeille" } else { "supprimées" };
+                        if report.failed.is_empty() {
+                            show_notification(
+                                &window_weak,
+                                "cleanup-done",
+                                "Nettoyage terminé",
+                                &format!("{} entrées {}", report.removed.len(), action),
+                                "success"
+                            );
+                        } else {
+                            let (path, reason) = &report.failed[0];
+                            show_notification(
+                                &window_weak,
+                                "cleanup-done",
+                                "Nettoyage incomplet",
+                                &format!(
+                                    "{} entrées {}, {} échecs ({} : {})",
+                                    report.removed.len(),
+                                    action,
+                                    report.failed.len(),
+                                    path,
+                                    reason
+                                ),
+                                "warning"
+                            );
+                        }
+                    }).unwrap();
+                });
+            }
+        });
+
+        logic.on_add_protected_path({
+            let protected = protected.clone();
+            let selection = selection.clone();
+            let window_weak = window.as_weak();
+            move || {
+                let Some(folder) = rfd::FileDialog::new().pick_folder() else {
+                    return;
+                };
+
+                let mut protected = protected.lock();
+                let folder = folder.to_string_lossy().to_string();
+                if !protected.paths.contains(&folder) {
+                    protected.paths.push(folder);
+                }
+                save_protected_paths(&window_weak, &protected);
+
+                // Les candidats désormais protégés disparaissent de la liste
+                selection.lock().retain(|(candidate, _)| !protected.is_protected(&candidate.path));
+                if let Some(window) = window_weak.upgrade() {
+                    let logic = window.global::<AppLogic>();
+                    update_protected_paths(&logic, &protected);
+                    update_items(&logic, &selection.lock());
+                }
+            }
+        });
+
+        logic.on_remove_protected_path({
+            let protected = protected.clone();
+            let window_weak = window.as_weak();
+            move |index| {
+                let mut protected = protected.lock();
+                if (index as usize) < protected.paths.len() {
+                    protected.paths.remove(index as usize);
+                    save_protected_paths(&window_weak, &protected);
+                }
+                if let Some(window) = window_weak.upgrade() {
+                    update_protected_paths(&window.global::<AppLogic>(), &protected);
+                }
+            }
+        });
+    }
+}
+
+fn save_protected_paths(window_weak: &Weak<MainWindow>, protected: &ProtectedPaths) {
+    if let Err(e) = protected.save() {
+        show_notification(
+            window_weak,
+            "protected-paths",
+            "Erreur de sauvegarde",
+            &e.to_string(),
+            "error"
+        );
+    }
+}
+
+fn to_item((candidate, checked): &(CleanupCandidate, bool)) -> CleanupItem {
+    CleanupItem {
+        path: candidate.path.clone().into(),
+        kind: candidate.kind.label().into(),
+        checked: *checked,
+    }
+}
+
+fn selected_count(selection: &[(CleanupCandidate, bool)]) -> i32 {
+    selection.iter().filter(|(_, checked)| *checked).count() as i32
+}
+
+fn update_items(logic: &AppLogic, selection: &[(CleanupCandidate, bool)]) {
+    let items: Vec<CleanupItem> = selection.iter().map(to_item).collect();
+    logic.set_cleanup_items(ModelRc::new(VecModel::from(items)));
+    logic.set_cleanup_selected(selected_count(selection));
+}
+
+fn update_protected_paths(logic: &AppLogic, protected: &ProtectedPaths) {
+    let paths: Vec<SharedString> = protected.paths.iter().map(SharedString::from).collect();
+    logic.set_protected_paths(ModelRc::new(VecModel::from(paths)));
+}
+
+fn summary(selection: &[(CleanupCandidate, bool)], protected: usize) -> SharedString {
+    let count = |kind: CleanupKind| selection.iter().filter(|(c, _)| c.kind == kind).count();
+    let mut summary = format!(
+        "{} dossiers vides, {} fichiers vides, {} liens cassés",
+        count(CleanupKind::EmptyFolder),
+        count(CleanupKind::EmptyFile),
+        count(CleanupKind::BrokenLink)
+    );
+    if protected > 0 {
+        summary.push_str(&format!(" ({} protégés, masqués)", protected));
+    }
+    summary.into()
+}
@@ -4,3 +4,8 @@ pub mod about;
 pub mod everysup;
 pub mod features;
 pub mod cleartool;
+pub mod snapshots;
+pub mod maintenance;
+pub mod audit;
+pub mod timeline;
+pub mod quick_search;
@@ -0,0 +1,153 @@
+use crate::slint_generated::{QuickSearchWindow, QuickResult};
+use crate::pages::features::{split_member_path, FileSearcher};
+use crate::utils::open_path;
+use slint::{ComponentHandle, Weak, ModelRc, VecModel};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+const QUICK_RESULTS: usize = 8;
+const EMPTY_STATUS: &str = "Tapez pour chercher · Entrée ouvrir · Tab résultats · Maj+Entrée dossier · Ctrl+Entrée EverySup";
+
+// `on_open_in_everysup` reçoit la requête à reprendre dans la fenêtre principale
+pub fn init<F>(window: &Weak<QuickSearchWindow>, searcher: Arc<FileSearcher>, on_open_in_everysup: F)
+where F: Fn(String) + 'static {
+    let Some(window) = window.upgrade() else { return };
+    // Chaque saisie incrémente le compteur : une recherche arrivée en retard est ignorée
+    let generation = Arc::new(AtomicU64::new(0));
+    window.set_status(EMPTY_STATUS.into());
+
+    let run_search = {
+        let window_weak = window.as_weak();
+        move |query: String| {
+            let current = generation.fetch_add(1, Ordering::SeqCst) + 1;
+            let generation = generation.clone();
+            let searcher = searcher.clone();
+            let window_weak = window_weak.clone();
+
+            if let Some(window) = window_weak.upgrade() {
+                window.set_selected(0);
+            }
+
+            thread::spawn(move || {
+                let hits = searcher.search(&query);
+                let count = hits.len();
+                let results: Vec<QuickResult> = hits.into_iter()
+                    .take(QUICK_RESULTS)
+                    .map(|hit| {
+                        let folder = Path::new(&hit.result.path)
+                            .parent()
+                            .map(|parent| parent.to_string_lossy().into_owned())
+                            .unwrap_or_default();
+                        QuickResult {
+                            name: hit.result.name.into(),
+                            folder: folder.into(),
+                            path: hit.result.path.into(),
+                            is_dir: hit.result.is_dir,
+                        }
+                    })
+                    .collect();
+
+                slint::invoke_from_event_loop(move || {
+                    if generation.load(Ordering::SeqCst) != current {
+                        return;
+                    }
+                    if let Some(window) = window_weak.upgrade() {
+                        let status = match count {
+                            _ if query.trim().is_empty() => EMPTY_STATUS.to_string(),
+                            0 => "Aucun résultat".to_string(),
+                            1 => "1 résultat".to_string(),
+                            count => format!("{} résultats", count),
+                        };
+                        window.set_status(status.into());
+                        window.set_results(ModelRc::new(VecModel::from(results)));
+                    }
+                }).unwrap();
+            });
+        }
+    };
+
+    window.on_query_edited(move |query| run_search(query.to_string()));
+
+    window.on_open_result({
+        let window_weak = window.as_weak();
+        move |path| {
+            open_and_dismiss(&window_weak, Path::new(path.as_str()));
+        }
+    });
+
+    window.on_open_folder({
+        let window_weak = window.as_weak();
+        move |path| {
+            if let Some(parent) = Path::new(path.as_str()).parent() {
+                open_and_dismiss(&window_weak, parent);
+            }
+        }
+    });
+
+    window.on_open_in_everysup({
+        let window_weak = window.as_weak();
+        move |query| {
+            dismiss(&window_weak);
+            on_open_in_everysup(query.to_string());
+        }
+    });
+
+    window.on_dismiss({
+        let window_weak = window.as_weak();
+        move || dismiss(&window_weak)
+    });
+}
+
+// Sans instance déjà lancée, l'index n'existe encore que sur le disque
+// (`suptool index`, indexation depuis EverySup) : la saisie en cours est
+// relancée une fois chargé
+pub fn load_saved_index(window: &Weak<QuickSearchWindow>, searcher: Arc<FileSearcher>) {
+    let window_weak = window.clone();
+    thread::spawn(move || {
+        let loaded = searcher.profiles()
+            .iter()
+            .filter(|profile| searcher.load_saved_index(&profile.name))
+            .count();
+
+        slint::invoke_from_event_loop(move || {
+            let Some(window) = window_weak.upgrade() else { return };
+            if loaded == 0 {
+                window.set_status("Aucun index sauvegardé : lancez `suptool index` ou indexez depuis EverySup".into());
+            } else if !window.get_query().is_empty() {
+                window.invoke_query_edited(window.get_query());
+            }
+        }).unwrap();
+    });
+}
+
+// Affiche la fenêtre avec une saisie vide
+pub fn show(window: &QuickSearchWindow) {
+    window.set_query("".into());
+    window.set_selected(0);
+    window.set_status(EMPTY_STATUS.into());
+    window.set_results(ModelRc::new(VecModel::from(Vec::<QuickResult>::new())));
+    if let Err(e) = window.show() {
+        eprintln!("Impossible d'afficher la recherche rapide: {}", e);
+    }
+}
+
+fn open_and_dismiss(window_weak: &Weak<QuickSearchWindow>, path: &Path) {
+    // Un membre d'archive n'existe pas sur le disque : on ouvre l'archive
+    let path = split_member_path(path).map_or_else(|| path.to_path_buf(), |(archive, _)| archive);
+    match open_path(&path) {
+        Ok(()) => dismiss(window_weak),
+        Err(e) => {
+            if let Some(window) = window_weak.upgrade() {
+                window.set_status(format!("Ouverture impossible : {}", e).into());
+            }
+        }
+    }
+}
+
+fn dismiss(window_weak: &Weak<QuickSearchWindow>) {
+    if let Some(window) = window_weak.upgrade() {
+        let _ = window.hide();
+    }
+}
@@ -1,11 +1,58 @@
 use slint::{ComponentHandle, Weak};
 use crate::slint_generated::{MainWindow, AppLogic};
 use crate::widgets::show_notification;
+use crate::pages::features::{index_options, set_index_options, IndexOptions, IndexProfile};
 
 pub fn init(window: &Weak<MainWindow>) {
     let window_weak = window.clone();
     
     if let Some(window) = window_weak.upgrade() {
+        let options = index_options();
+        window.global::<AppLogic>().set_index_follow_links(options.follow_links);
+        window.global::<AppLogic>().set_index_cross_filesystems(options.cross_filesystems);
+        window.global::<AppLogic>().set_index_profile(options.profile.index());
+        window.global::<AppLogic>().set_index_only_when_idle(options.only_when_idle);
+        window.global::<AppLogic>().set_index_verify_results(options.verify_results);
+        window.global::<AppLogic>().set_index_archives(options.index_archives);
+
+        let window_weak_clone = window.as_weak();
+        window.global::<AppLogic>().on_index_options_changed(move |follow_links, cross_filesystems, profile, only_when_idle, verify_results, index_archives| {
+            let result = set_index_options(IndexOptions {
+                follow_links,
+                cross_filesystems,
+                profile: IndexProfile::from_index(profile),
+                only_when_idle,
+                verify_results,
+                index_archives,
+            });
+
+            if let Some(window) = window_weak_clone.upgrade() {
+                window.global::<AppLogic>().set_index_follow_links(follow_links);
+                window.global::<AppLogic>().set_index_cross_filesystems(cross_filesystems);
+                window.global::<AppLogic>().set_index_profile(profile);
+                window.global::<AppLogic>().set_index_only_when_idle(only_when_idle);
+                window.global::<AppLogic>().set_index_verify_results(verify_results);
+                window.global::<AppLogic>().set_index_archives(index_archives);
+            }
+
+            match result {
+                Ok(()) => show_notification(
+                    &window_weak_clone,
+                    "index-options",
+                    "Options d'indexation",
+                    "Prises en compte à la prochaine indexation",
+                    "info"
+                ),
+                Err(e) => show_notification(
+                    &window_weak_clone,
+                    "index-options",
+                    "Erreur de sauvegarde",
+                    &e.to_string(),
+                    "error"
+                ),
+            }
+        });
+
         window.global::<AppLogic>().on_settings_changed(move |setting, id| {
             println!("Paramètre modifié: {}", setting);
             show_notification(
@@ -0,0 +1,212 @@
+use crate::slint_generated::{MainWindow, AppLogic};
+use crate::pages::features::{FileSearcher, SnapshotDiff};
+use crate::utils::{format_size, pick_export_path};
+use crate::widgets::show_notification;
+use slint::{ComponentHandle, Weak, ModelRc, VecModel, SharedString, StandardListViewItem};
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::thread;
+
+// Le tableau n'affiche que les plus gros changements, l'export contient tout
+const MAX_DIFF_ROWS: usize = 1000;
+
+pub fn init(window: &Weak<MainWindow>) {
+    let searcher = Arc::new(FileSearcher::new());
+    let last_diff: Arc<Mutex<Option<SnapshotDiff>>> = Arc::new(Mutex::new(None));
+
+    if let Some(window) = window.upgrade() {
+        let logic = window.global::<AppLogic>();
+        update_snapshot_names(&logic, &searcher);
+
+        logic.on_create_snapshot({
+            let searcher = searcher.clone();
+            let window_weak = window.as_weak();
+            move |name| {
+                if searcher.file_count() == 0 {
+                    show_notification(
+                        &window_weak,
+                        "snapshot-empty",
+                        "Index vide",
+                        "Lancez une indexation avant de créer un snapshot",
+                        "warning"
+                    );
+                    return;
+                }
+
+                let searcher = searcher.clone();
+                let window_weak = window_weak.clone();
+                let name = name.to_string();
+
+                thread::spawn(move || {
+                    let result = searcher.take_snapshot(&name);
+
+                    slint::invoke_from_event_loop(move || {
+                        match result {
+                            Ok(count) => show_notification(
+                                &window_weak,
+                                "snapshot-created",
+                                "Snapshot créé",
+                                &format!("'{}' : {} entrées", name, count),
+                                "success"
+                            ),
+                            Err(e) => show_notification(
+                                &window_weak,
+                                "snapshot-error",
+                                "Erreur de snapshot",
+                                &e.to_string(),
+                                "error"
+                            ),
+                        }
+                        if let Some(window) = window_weak.upgrade() {
+                            update_snapshot_names(&window.global::<AppLogic>(), &searcher);
+                        }
+                    }).unwrap();
+                });
+            }
+        });
+
+        logic.on_delete_snapshot({
+            let searcher = searcher.clone();
+            let window_weak = window.as_weak();
+            move |name| {
+                if let Err(e) = searcher.delete_snapshot(&name) {
+                    show_notification(
+                        &window_weak,
+                        "snapshot-error",
+                        "Erreur de snapshot",
+                        &e.to_string(),
+                        "error"
+                    );
+                }
+                if let Some(window) = window_weak.upgrade() {
+                    update_snapshot_names(&window.global::<AppLogic>(), &searcher);
+                }
+            }
+        });
+
+        logic.on_compare_snapshots({
+            let searcher = searcher.clone();
+            let last_diff = last_diff.clone();
+            let window_weak = window.as_weak();
+            move |old, new| {
+                let searcher = searcher.clone();
+                let last_diff = last_diff.clone();
+                let window_weak = window_weak.clone();
+                let (old, new) = (old.to_string(), new.to_string());
+
+                thread::spawn(move || {
+                    let result = searcher.diff_snapshots(&old, &new);
+
+                    slint::invoke_from_event_loop(move || {
+                        let diff = match result {
+                            Ok(diff) => diff,
+                            Err(e) => {
+                                show_notification(
+                                    &window_weak,
+                                    "snapshot-error",
+                                    "Erreur de comparaison",
+                                    &e.to_string(),
+                                    "error"
+                                );
+                                return;
+                            }
+                        };
+
+                        if let Some(window) = window_weak.upgrade() {
+                            let logic = window.global::<AppLogic>();
+                            logic.set_snapshot_diff_summary(diff_summary(&diff));
+                            logic.set_snapshot_diff_rows(diff_rows(&diff));
+                        }
+                        *last_diff.lock() = Some(diff);
+                    }).unwrap();
+                });
+            }
+        });
+
+        logic.on_export_snapshot_diff({
+            let last_diff = last_diff.clone();
+            let window_weak = window.as_weak();
+            move |format| {
+                let last_diff = last_diff.lock();
+                let Some(diff) = last_diff.as_ref() else {
+                    return;
+                };
+
+                let default_name = format!("diff_{}_{}", diff.old, diff.new);
+                let Some(path) = pick_export_path(&default_name, &format) else {
+                    return;
+                };
+
+                let content = if format == "json" {
+                    diff.to_json().unwrap_or_default()
+                } else {
+                    diff.to_csv()
+                };
+
+                match std::fs::write(&path, content) {
+                    Ok(()) => show_notification(
+                        &window_weak,
+                        "snapshot-export",
+                        "Export terminé",
+                        &path.to_string_lossy(),
+                        "success"
+                    ),
+                    Err(e) => show_notification(
+                        &window_weak,
+                        "snapshot-export",
+                        "Erreur d'export",
+                        &e.to_string(),
+                        "error"
+                    ),
+                }
+            }
+        });
+    }
+}
+
+fn update_snapshot_names(logic: &AppLogic, searcher: &FileSearcher) {
+    let names: Vec<SharedString> = searcher.snapshot_names()
+        .into_iter()
+        .map(SharedString::from)
+        .collect();
+    logic.set_snapshot_names(ModelRc::new(VecModel::from(names)));
+}
+
+fn format_delta(delta: i64) -> String {
+    let sign = if delta < 0 { "-" } else { "+" };
+    format!("{}{}", sign, format_size(delta.unsigned_abs()))
+}
+
+fn diff_summary(diff: &SnapshotDiff) -> SharedString {
+    let shown = diff.entries.len().min(MAX_DIFF_ROWS);
+    SharedString::from(format!(
+        "{} → {} : {} changements, {} ({} affichés)",
+        diff.old,
+        diff.new,
+        diff.entries.len(),
+        format_delta(diff.total_delta),
+        shown
+    ))
+}
+
+fn diff_rows(diff: &SnapshotDiff) -> ModelRc<ModelRc<StandardListViewItem>> {
+    let rows: Vec<ModelRc<StandardListViewItem>> = diff.entries.iter()
+        .take(MAX_DIFF_ROWS)
+        .map(|entry| {
+            let row = vec![
+                entry.kind.label().to_string(),
+                entry.path.clone(),
+                if entry.is_dir { "Dossier".into() } else { "Fichier".into() },
+                format_size(entry.old_size).to_string(),
+                format_size(entry.new_size).to_string(),
+                format_delta(entry.delta),
+            ];
+            ModelRc::new(VecModel::from(
+                row.into_iter()
+                    .map(|s| StandardListViewItem::from(SharedString::from(s)))
+                    .collect::<Vec<_>>()
+            ))
+        })
+        .collect();
+    ModelRc::new(VecModel::from(rows))
+}
@@ -0,0 +1,251 @@
+use crate::slint_generated::{MainWindow, AppLogic};
+use crate::pages::features::{merge_entries, timeline_roots, watch_roots, FileSearcher, TimeWindow, TimelineEntry, TimelineFilter};
+use crate::utils::{format_size, format_time, open_path};
+use crate::widgets::show_notification;
+use slint::{ComponentHandle, Weak, ModelRc, VecModel, SharedString, StandardListViewItem};
+use notify::RecommendedWatcher;
+use parking_lot::Mutex;
+use std::path::Path;
+use std::sync::Arc;
+use std::thread;
+
+const MAX_TIMELINE_ROWS: usize = 5000;
+
+#[derive(Default)]
+struct TimelineState {
+    roots: Vec<String>,
+    filter: TimelineFilter,
+    window: Option<TimeWindow>,
+    // Entrées trouvées dans l'index et relevées par le watcher depuis l'ouverture de la page
+    indexed: Vec<TimelineEntry>,
+    live: Vec<TimelineEntry>,
+    // Lignes affichées, pour retrouver le chemin d'une ligne
+    shown: Vec<TimelineEntry>,
+    // Présent tant que la page est ouverte
+    watcher: Option<RecommendedWatcher>,
+    active: bool,
+    live_status: String,
+    // Incrémenté à chaque ouverture ou fermeture de la page : le watcher et le
+    // statut d'une ouverture précédente arrivés en retard sont ignorés
+    generation: u64,
+    // Incrémenté à chaque relecture de l'index : seule la dernière est affichée
+    refresh_generation: u64,
+}
+
+impl TimelineState {
+    fn time_window(&self) -> TimeWindow {
+        self.window.unwrap_or(TimeWindow::Hour)
+    }
+}
+
+// `searcher` est celui d'EverySup : la page suit le profil sélectionné
+pub fn init(window: &Weak<MainWindow>, searcher: Arc<FileSearcher>) {
+    let state = Arc::new(Mutex::new(TimelineState::default()));
+
+    if let Some(window) = window.upgrade() {
+        let logic = window.global::<AppLogic>();
+
+        logic.on_timeline_active({
+            let searcher = searcher.clone();
+            let state = state.clone();
+            let window_weak = window.as_weak();
+            move |active| {
+                let mut locked = state.lock();
+                locked.active = active;
+                locked.generation += 1;
+                if !active {
+                    // Arrête la surveillance en quittant la page
+                    locked.watcher = None;
+                    locked.live.clear();
+                    return;
+                }
+
+                // La page est recréée avec ses filtres par défaut
+                locked.filter = TimelineFilter::default();
+                locked.window = None;
+                locked.roots = timeline_roots(searcher.scope().as_deref());
+                locked.live_status = "démarrage du suivi en direct...".to_string();
+                if let Some(window) = window_weak.upgrade() {
+                    let roots: Vec<SharedString> = std::iter::once(SharedString::from("Toutes les racines"))
+                        .chain(locked.roots.iter().map(SharedString::from))
+                        .collect();
+                    window.global::<AppLogic>().set_timeline_roots(ModelRc::new(VecModel::from(roots)));
+                }
+                let roots = locked.roots.clone();
+                let generation = locked.generation;
+                drop(locked);
+
+                refresh(&searcher, &state, &window_weak);
+                start_watcher(roots, generation, &state, &window_weak);
+            }
+        });
+
+        logic.on_timeline_filter_changed({
+            let searcher = searcher.clone();
+            let state = state.clone();
+            let window_weak = window.as_weak();
+            move |root, kind, time_window| {
+                {
+                    let mut state = state.lock();
+                    // 0 = toutes les racines, les suivantes suivent `state.roots`
+                    state.filter.root = (root > 0)
+                        .then(|| state.roots.get(root as usize - 1).cloned())
+                        .flatten();
+                    state.filter.is_dir = match kind {
+                        1 => Some(false),
+                        2 => Some(true),
+                        _ => None,
+                    };
+                    state.window = Some(TimeWindow::from_index(time_window));
+                }
+                refresh(&searcher, &state, &window_weak);
+            }
+        });
+
+        logic.on_timeline_refresh({
+            let searcher = searcher.clone();
+            let state = state.clone();
+            let window_weak = window.as_weak();
+            move || refresh(&searcher, &state, &window_weak)
+        });
+
+        logic.on_timeline_open({
+            let state = state.clone();
+            let window_weak = window.as_weak();
+            move |row| {
+                let Some(entry) = state.lock().shown.get(row as usize).cloned() else {
+                    return;
+                };
+                if let Err(e) = open_path(Path::new(&entry.path)) {
+                    show_notification(
+                        &window_weak,
+                        "timeline-open",
+                        "Ouverture impossible",
+                        &e.to_string(),
+                        "error"
+                    );
+                }
+            }
+        });
+    }
+}
+
+// Relit l'index pour la fenêtre de temps et les filtres courants
+fn refresh(searcher: &Arc<FileSearcher>, state: &Arc<Mutex<TimelineState>>, window_weak: &Weak<MainWindow>) {
+    let searcher = searcher.clone();
+    let state = state.clone();
+    let window_weak = window_weak.clone();
+    let (filter, since, current) = {
+        let mut state = state.lock();
+        state.refresh_generation += 1;
+        (state.filter.clone(), state.time_window().since(), state.refresh_generation)
+    };
+
+    thread::spawn(move || {
+        let indexed = searcher.recent_changes(&filter, since);
+        let mut locked = state.lock();
+        // Filtres changés ou page quittée entre-temps
+        if locked.refresh_generation != current || !locked.active {
+            return;
+        }
+        locked.indexed = indexed;
+        drop(locked);
+        update_later(&state, &window_weak);
+    });
+}
+
+fn start_watcher(roots: Vec<String>, generation: u64, state: &Arc<Mutex<TimelineState>>, window_weak: &Weak<MainWindow>) {
+    let state = state.clone();
+    let window_weak = window_weak.clone();
+
+    // Poser les watches récursifs peut prendre du temps sur un gros dossier
+    thread::spawn(move || {
+        let result = watch_roots(&roots, {
+            let state = state.clone();
+            let window_weak = window_weak.clone();
+            move |changes| {
+                let mut locked = state.lock();
+                if locked.generation != generation {
+                    return;
+                }
+                merge_entries(&mut locked.live, changes);
+                drop(locked);
+                update_later(&state, &window_weak);
+            }
+        });
+
+        let mut locked = state.lock();
+        // Page quittée (ou rouverte) entre-temps : ce watcher est détruit ici
+        if locked.generation != generation {
+            return;
+        }
+        match result {
+            Ok((_, 0)) => {
+                locked.live_status = "suivi en direct indisponible pour les disques entiers".to_string();
+            }
+            Ok((watcher, watched)) => {
+                locked.watcher = Some(watcher);
+                locked.live_status = format!("suivi en direct sur {} racines sur {}", watched, roots.len());
+            }
+            Err(e) => locked.live_status = format!("suivi en direct indisponible : {}", e),
+        }
+        drop(locked);
+        update_later(&state, &window_weak);
+    });
+}
+
+fn update_later(state: &Arc<Mutex<TimelineState>>, window_weak: &Weak<MainWindow>) {
+    let state = state.clone();
+    let window_weak = window_weak.clone();
+    slint::invoke_from_event_loop(move || {
+        if let Some(window) = window_weak.upgrade() {
+            update_timeline(&window.global::<AppLogic>(), &mut state.lock());
+        }
+    }).unwrap();
+}
+
+fn update_timeline(logic: &AppLogic, state: &mut TimelineState) {
+    let since = state.time_window().since();
+    let filter = state.filter.clone();
+    // Les entrées relevées en direct sortent de la liste quand elles quittent la fenêtre
+    state.live.retain(|entry| entry.time >= since);
+
+    let mut entries = state.indexed.clone();
+    entries.retain(|entry| entry.time >= since);
+    merge_entries(
+        &mut entries,
+        state.live.iter().filter(|entry| filter.matches(&entry.path, entry.is_dir)).cloned().collect()
+    );
+    let total = entries.len();
+    entries.truncate(MAX_TIMELINE_ROWS);
+
+    let rows: Vec<ModelRc<StandardListViewItem>> = entries.iter()
+        .map(|entry| {
+            let folder = Path::new(&entry.path)
+                .parent()
+                .map(|parent| parent.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let size = if entry.is_dir { SharedString::new() } else { format_size(entry.size) };
+            let row = [
+                SharedString::from(format_time(entry.time)),
+                SharedString::from(entry.kind.label()),
+                SharedString::from(entry.name.as_str()),
+                SharedString::from(folder),
+                size,
+            ];
+            ModelRc::new(VecModel::from(
+                row.into_iter().map(StandardListViewItem::from).collect::<Vec<_>>()
+            ))
+        })
+        .collect();
+    logic.set_timeline_rows(ModelRc::new(VecModel::from(rows)));
+
+    let mut summary = format!("{} changements", total);
+    if total > MAX_TIMELINE_ROWS {
+        summary.push_str(&format!(" ({} plus récents affichés)", MAX_TIMELINE_ROWS));
+    }
+    summary.push_str(&format!(", {}", state.live_status));
+    logic.set_timeline_summary(summary.into());
+
+    state.shown = entries;
+}
@@ -82,6 +82,22 @@ impl Updater {
         }
     }
 
+    // Sans fenêtre ni notifications, pour la ligne de commande
+    pub fn headless() -> Self {
+        Self::new(&Weak::default())
+    }
+
+    pub fn current_version(&self) -> &str {
+        &self.version
+    }
+
+    // Version trouvée par `check_for_updates` si elle est plus récente
+    pub fn available_version(&self) -> Option<String> {
+        self.update_available.read()
+            .as_ref()
+            .map(|release| release.tag_name.trim_start_matches('v').to_string())
+    }
+
     // fn get_metadata_path() -> PathBuf {
     //     let local_app_data = dirs::data_local_dir()
     //         .unwrap_or_else(|| PathBuf::from("./"));
@@ -0,0 +1,19 @@
+use std::cell::RefCell;
+use std::io;
+
+thread_local! {
+    // Sous Linux (X11), le contenu copié disparaît avec le presse-papiers :
+    // il est conservé pour toute la durée de l'application
+    static CLIPBOARD: RefCell<Option<arboard::Clipboard>> = const { RefCell::new(None) };
+}
+
+pub fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    CLIPBOARD.with(|clipboard| {
+        let mut clipboard = clipboard.borrow_mut();
+        if clipboard.is_none() {
+            *clipboard = Some(arboard::Clipboard::new().map_err(io::Error::other)?);
+        }
+        clipboard.as_mut()
+            .map_or(Ok(()), |clipboard| clipboard.set_text(text).map_err(io::Error::other))
+    })
+}
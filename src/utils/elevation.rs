@@ -0,0 +1,18 @@
+use std::io;
+use std::path::Path;
+use std::process::{Command, ExitStatus};
+
+// Lance `exe` avec les droits administrateur et attend qu'il se termine
+pub fn run_elevated(exe: &Path, args: &[&str]) -> io::Result<ExitStatus> {
+    #[cfg(windows)]
+    {
+        // L'application est déjà relancée en administrateur au démarrage (voir app.rs)
+        Command::new(exe).args(args).status()
+    }
+
+    #[cfg(not(windows))]
+    {
+        // pkexec demande le mot de passe via l'agent polkit de la session
+        Command::new("pkexec").arg(exe).args(args).status()
+    }
+}
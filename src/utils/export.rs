@@ -0,0 +1,18 @@
+use rfd::FileDialog;
+use std::path::PathBuf;
+
+pub fn pick_export_path(default_name: &str, extension: &str) -> Option<PathBuf> {
+    FileDialog::new()
+        .set_title("Exporter")
+        .set_file_name(&format!("{}.{}", default_name, extension))
+        .add_filter(&extension.to_uppercase(), &[extension])
+        .save_file()
+}
+
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,231 @@
+use serde::{Serialize, Deserialize};
+use std::fs::{self, Metadata};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+pub struct FileDetails {
+    pub path: PathBuf,
+    pub size: u64,
+    pub is_dir: bool,
+    // Pas toujours disponible (certains systèmes de fichiers Linux)
+    pub created: Option<SystemTime>,
+    pub modified: Option<SystemTime>,
+    pub accessed: Option<SystemTime>,
+    pub permissions: String,
+    pub owner: String,
+}
+
+pub fn file_details(path: &Path) -> io::Result<FileDetails> {
+    let metadata = fs::metadata(path)?;
+
+    Ok(FileDetails {
+        path: path.to_path_buf(),
+        size: metadata.len(),
+        is_dir: metadata.is_dir(),
+        created: metadata.created().ok(),
+        modified: metadata.modified().ok(),
+        accessed: metadata.accessed().ok(),
+        permissions: permissions_string(&metadata),
+        owner: owner_name(path, &metadata).unwrap_or_else(|| "?".to_string()),
+    })
+}
+
+// Attributs facultatifs relevés pendant l'indexation (colonnes optionnelles)
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct FileAttributes {
+    pub created: Option<SystemTime>,
+    pub accessed: Option<SystemTime>,
+    pub permissions: String,
+    pub owner: String,
+    // Non disponibles sous Windows sans ouvrir le fichier
+    pub inode: Option<u64>,
+    pub links: Option<u64>,
+    pub hidden: bool,
+}
+
+pub fn file_attributes(path: &Path, metadata: &Metadata) -> FileAttributes {
+    #[cfg(unix)]
+    let (inode, links) = {
+        use std::os::unix::fs::MetadataExt;
+        (Some(metadata.ino()), Some(metadata.nlink()))
+    };
+    #[cfg(windows)]
+    let (inode, links) = (None, None);
+
+    FileAttributes {
+        created: metadata.created().ok(),
+        accessed: metadata.accessed().ok(),
+        permissions: permissions_string(metadata),
+        owner: owner_name(path, metadata).unwrap_or_else(|| "?".to_string()),
+        inode,
+        links,
+        hidden: is_hidden(path, metadata),
+    }
+}
+
+// Identifiant (périphérique, inode) d'un fichier, pour reconnaître les liens durs
+// et les dossiers déjà visités. Non disponible sous Windows sans ouvrir le fichier.
+#[cfg(unix)]
+pub fn file_id(metadata: &Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(windows)]
+pub fn file_id(_metadata: &Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+// Nombre de liens durs vers le fichier (1 si inconnu)
+#[cfg(unix)]
+pub fn hard_link_count(metadata: &Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.nlink()
+}
+
+#[cfg(windows)]
+pub fn hard_link_count(_metadata: &Metadata) -> u64 {
+    1
+}
+
+#[cfg(unix)]
+fn is_hidden(path: &Path, _metadata: &Metadata) -> bool {
+    path.file_name()
+        .map(|name| name.to_string_lossy().starts_with('.'))
+        .unwrap_or(false)
+}
+
+#[cfg(windows)]
+fn is_hidden(_path: &Path, metadata: &Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    metadata.file_attributes() & 0x2 != 0
+}
+
+#[cfg(unix)]
+fn permissions_string(metadata: &Metadata) -> String {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = metadata.permissions().mode();
+    let kind = if metadata.is_dir() { 'd' } else { '-' };
+    let flags: String = [(0o400, 'r'), (0o200, 'w'), (0o100, 'x'),
+                         (0o040, 'r'), (0o020, 'w'), (0o010, 'x'),
+                         (0o004, 'r'), (0o002, 'w'), (0o001, 'x')]
+        .iter()
+        .map(|&(bit, c)| if mode & bit != 0 { c } else { '-' })
+        .collect();
+
+    format!("{}{} ({:o})", kind, flags, mode & 0o7777)
+}
+
+#[cfg(windows)]
+fn permissions_string(metadata: &Metadata) -> String {
+    use std::os::windows::fs::MetadataExt;
+
+    const ATTRIBUTES: &[(u32, &str)] = &[
+        (0x1, "lecture seule"),
+        (0x2, "caché"),
+        (0x4, "système"),
+        (0x20, "archive"),
+    ];
+
+    let attributes = metadata.file_attributes();
+    let flags: Vec<&str> = ATTRIBUTES.iter()
+        .filter(|(bit, _)| attributes & bit != 0)
+        .map(|(_, name)| *name)
+        .collect();
+
+    if flags.is_empty() {
+        "normal".to_string()
+    } else {
+        flags.join(", ")
+    }
+}
+
+#[cfg(unix)]
+fn owner_name(_path: &Path, metadata: &Metadata) -> Option<String> {
+    use dashmap::DashMap;
+    use once_cell::sync::Lazy;
+    use std::ffi::CStr;
+    use std::os::unix::fs::MetadataExt;
+
+    // Beaucoup de fichiers partagent le même propriétaire pendant l'indexation
+    static OWNERS: Lazy<DashMap<u32, String>> = Lazy::new(DashMap::new);
+
+    let uid = metadata.uid();
+    if let Some(name) = OWNERS.get(&uid) {
+        return Some(name.clone());
+    }
+
+    let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut result = std::ptr::null_mut();
+    let mut buffer = vec![0 as libc::c_char; 1024];
+
+    let status = unsafe {
+        libc::getpwuid_r(uid, &mut passwd, buffer.as_mut_ptr(), buffer.len(), &mut result)
+    };
+
+    if status != 0 || result.is_null() {
+        // Utilisateur inconnu du système (ex: disque venant d'une autre machine)
+        return Some(uid.to_string());
+    }
+
+    let name = unsafe { CStr::from_ptr(passwd.pw_name) }.to_string_lossy().to_string();
+    OWNERS.insert(uid, name.clone());
+    Some(name)
+}
+
+#[cfg(windows)]
+fn owner_name(path: &Path, _metadata: &Metadata) -> Option<String> {
+    use windows::core::{HSTRING, PWSTR};
+    use windows::Win32::Foundation::{LocalFree, HLOCAL, PSID};
+    use windows::Win32::Security::{
+        LookupAccountSidW, OWNER_SECURITY_INFORMATION, PSECURITY_DESCRIPTOR, SID_NAME_USE,
+    };
+    use windows::Win32::Security::Authorization::{GetNamedSecurityInfoW, SE_FILE_OBJECT};
+
+    let path = HSTRING::from(path.as_os_str());
+    let mut owner = PSID::default();
+    let mut descriptor = PSECURITY_DESCRIPTOR::default();
+
+    unsafe {
+        GetNamedSecurityInfoW(
+            &path,
+            SE_FILE_OBJECT,
+            OWNER_SECURITY_INFORMATION,
+            Some(&mut owner),
+            None,
+            None,
+            None,
+            &mut descriptor,
+        ).ok().ok()?;
+    }
+
+    let mut name = [0u16; 256];
+    let mut domain = [0u16; 256];
+    let mut name_len = name.len() as u32;
+    let mut domain_len = domain.len() as u32;
+    let mut sid_use = SID_NAME_USE::default();
+
+    let found = unsafe {
+        LookupAccountSidW(
+            None,
+            owner,
+            PWSTR(name.as_mut_ptr()),
+            &mut name_len,
+            PWSTR(domain.as_mut_ptr()),
+            &mut domain_len,
+            &mut sid_use,
+        )
+    };
+
+    unsafe {
+        let _ = LocalFree(HLOCAL(descriptor.0 as isize));
+    }
+
+    found.ok()?;
+    let name = String::from_utf16_lossy(&name[..name_len as usize]);
+    let domain = String::from_utf16_lossy(&domain[..domain_len as usize]);
+
+    Some(if domain.is_empty() { name } else { format!("{}\\{}", domain, name) })
+}
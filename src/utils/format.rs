@@ -1,5 +1,6 @@
 use slint::SharedString;
 use chrono::DateTime;
+use std::ops::Range;
 use std::time::SystemTime;
 
 pub fn format_size(size: u64) -> SharedString {
@@ -24,4 +25,81 @@ pub fn format_size(size: u64) -> SharedString {
 pub fn format_time(time: SystemTime) -> String {
     let datetime = DateTime::<chrono::Local>::from(time);
     datetime.format("%Y-%m-%d %H:%M").to_string()
-}
\ No newline at end of file
+}
+
+// Découpe le texte en fragments surlignés ou non d'après `spans`. Au-delà de
+// `max_chars` caractères, le milieu est remplacé par "…" : le début et la fin
+// d'un chemin sont les parties les plus parlantes.
+pub fn elide_spans(text: &str, spans: &[Range<usize>], max_chars: usize) -> Vec<(String, bool)> {
+    let mut chars: Vec<(char, bool)> = text.char_indices()
+        .map(|(i, c)| (c, spans.iter().any(|span| span.contains(&i))))
+        .collect();
+
+    if chars.len() > max_chars && max_chars > 3 {
+        let head = (max_chars - 1) / 3;
+        let tail = max_chars - 1 - head;
+        chars.splice(head..chars.len() - tail, [('…', false)]);
+    }
+
+    let mut fragments: Vec<(String, bool)> = Vec::new();
+    for (c, highlighted) in chars {
+        match fragments.last_mut() {
+            Some((text, last)) if *last == highlighted => text.push(c),
+            _ => fragments.push((c.to_string(), highlighted)),
+        }
+    }
+    fragments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(fragments: &[(String, bool)]) -> String {
+        fragments.iter()
+            .map(|(text, highlighted)| if *highlighted { format!("[{}]", text) } else { text.clone() })
+            .collect()
+    }
+
+    fn elide(text: &str, span: Range<usize>, max_chars: usize) -> String {
+        render(&elide_spans(text, &[span], max_chars))
+    }
+
+    #[test]
+    fn elide_spans_coupe_un_texte_court_aux_changements_de_surlignage() {
+        assert_eq!(elide("rapport.pdf", 0..3, 40), "[rap]port.pdf");
+    }
+
+    #[test]
+    fn elide_spans_utilise_des_plages_d_octets() {
+        // "é" occupe deux octets : le span 0..3 couvre "Ét"
+        assert_eq!(elide("Été.jpg", 0..3, 40), "[Ét]é.jpg");
+    }
+
+    #[test]
+    fn elide_spans_coupe_un_texte_long_au_milieu() {
+        let text = "/home/user/documents/projets/2024/rapport.pdf";
+        let elided = render(&elide_spans(text, &[], 20));
+        assert_eq!(elided.chars().count(), 20);
+        assert_eq!(elided, "/home/…4/rapport.pdf");
+    }
+
+    #[test]
+    fn elide_spans_surligne_les_deux_bouts_d_une_plage_coupee() {
+        // Le span couvre la coupure : l'ellipse n'est pas surlignée, les deux bouts le sont
+        let text = "abcdefghijklmnopqrstuvwxyz";
+        assert_eq!(elide(text, 1..22, 10), "a[bc]…[uv]wxyz");
+        // Span qui s'arrête pile à la fin du début conservé, ou commence pile au début de la fin
+        assert_eq!(elide(text, 0..3, 10), "[abc]…uvwxyz");
+        assert_eq!(elide(text, 20..26, 10), "abc…[uvwxyz]");
+        // Span entièrement dans la partie coupée
+        assert_eq!(elide(text, 5..15, 10), "abc…uvwxyz");
+    }
+
+    #[test]
+    fn elide_spans_coupe_sur_des_limites_de_caracteres() {
+        let text = "éééééééééé/àààààààààà";
+        let elided = elide(text, 0..4, 9);
+        assert_eq!(elided, "[éé]…àààààà");
+    }
+}
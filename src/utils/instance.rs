@@ -0,0 +1,60 @@
+// Communication avec l'instance déjà lancée : elle écoute sur un port local
+// choisi au hasard, noté dans le dossier de données. Une commande par
+// connexion, sur une ligne ("quick"...).
+
+use super::storage::get_data_dir;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+const PORT_FILE: &str = "instance.port";
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(300);
+// Réponse de l'instance, pour ne pas confondre avec un autre programme qui
+// aurait repris le port
+const ACK: &str = "ok";
+
+// Envoie `command` à l'instance en cours. Renvoie false si aucune ne répond.
+pub fn send_to_running_instance(command: &str) -> bool {
+    let Some(port) = fs::read_to_string(get_data_dir().join(PORT_FILE))
+        .ok()
+        .and_then(|port| port.trim().parse::<u16>().ok())
+    else {
+        return false;
+    };
+
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let send = || -> io::Result<bool> {
+        let mut stream = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)?;
+        stream.set_read_timeout(Some(CONNECT_TIMEOUT))?;
+        writeln!(stream, "{}", command)?;
+        let mut reply = String::new();
+        BufReader::new(stream).read_line(&mut reply)?;
+        Ok(reply.trim() == ACK)
+    };
+    send().unwrap_or(false)
+}
+
+// Reçoit les commandes des instances lancées ensuite
+pub fn listen_for_commands<F>(on_command: F) -> io::Result<()>
+where F: Fn(String) + Send + 'static {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    let port = listener.local_addr()?.port();
+    fs::create_dir_all(get_data_dir())?;
+    fs::write(get_data_dir().join(PORT_FILE), port.to_string())?;
+
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let _ = stream.set_read_timeout(Some(CONNECT_TIMEOUT));
+            let mut command = String::new();
+            let mut reader = BufReader::new(&stream);
+            if reader.read_line(&mut command).is_err() {
+                continue;
+            }
+            let _ = writeln!(&stream, "{}", ACK);
+            on_command(command.trim().to_string());
+        }
+    });
+    Ok(())
+}
@@ -1,5 +1,23 @@
 mod get_drive;
 mod format;
+mod storage;
+mod export;
+mod volumes;
+mod file_info;
+mod elevation;
+mod scheduling;
+mod open;
+mod instance;
+mod clipboard;
 
 pub use get_drive::get_drives;
-pub use format::{format_size, format_time};
\ No newline at end of file
+pub use format::{format_size, format_time, elide_spans};
+pub use storage::{get_data_dir, load_json, save_json};
+pub use export::{pick_export_path, csv_field};
+pub use volumes::{list_volumes, VolumeInfo};
+pub use file_info::{file_details, file_attributes, file_id, hard_link_count, FileDetails, FileAttributes};
+pub use elevation::run_elevated;
+pub use scheduling::{lower_thread_priority, system_is_idle};
+pub use open::open_path;
+pub use clipboard::copy_to_clipboard;
+pub use instance::{send_to_running_instance, listen_for_commands};
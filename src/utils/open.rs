@@ -0,0 +1,16 @@
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+// Ouvre un fichier ou un dossier avec l'application par défaut du système
+pub fn open_path(path: &Path) -> io::Result<()> {
+    #[cfg(windows)]
+    let mut command = Command::new("explorer");
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("open");
+    #[cfg(not(any(windows, target_os = "macos")))]
+    let mut command = Command::new("xdg-open");
+
+    // Le processus lancé vit sa vie, on ne l'attend pas
+    command.arg(path).spawn().map(|_| ())
+}
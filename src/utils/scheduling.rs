@@ -0,0 +1,68 @@
+// Priorité des threads et détection de l'inactivité du système, utilisées par
+// les profils d'indexation
+
+// Abaisse la priorité CPU et E/S du thread courant (pas du processus, pour que
+// l'interface reste réactive)
+pub fn lower_thread_priority() {
+    #[cfg(windows)]
+    unsafe {
+        use windows::Win32::System::Threading::{GetCurrentThread, SetThreadPriority, THREAD_MODE_BACKGROUND_BEGIN};
+        let _ = SetThreadPriority(GetCurrentThread(), THREAD_MODE_BACKGROUND_BEGIN);
+    }
+
+    #[cfg(target_os = "linux")]
+    unsafe {
+        // Sous Linux, setpriority et ioprio_set avec l'id du thread ne touchent que ce thread
+        const IOPRIO_WHO_PROCESS: libc::c_long = 1;
+        const IOPRIO_CLASS_IDLE: libc::c_long = 3;
+        const IOPRIO_CLASS_SHIFT: libc::c_long = 13;
+
+        let tid = libc::syscall(libc::SYS_gettid) as libc::id_t;
+        libc::setpriority(libc::PRIO_PROCESS, tid, 10);
+        libc::syscall(
+            libc::SYS_ioprio_set,
+            IOPRIO_WHO_PROCESS,
+            tid as libc::c_long,
+            IOPRIO_CLASS_IDLE << IOPRIO_CLASS_SHIFT,
+        );
+    }
+}
+
+// Délai sans clavier ni souris au-delà duquel Windows est considéré inactif
+#[cfg(windows)]
+const IDLE_AFTER_MS: u32 = 2 * 60 * 1000;
+
+// Windows : aucune saisie depuis deux minutes.
+// Ailleurs : charge moyenne sur une minute faible, hors threads d'indexation.
+pub fn system_is_idle(own_threads: usize) -> bool {
+    #[cfg(windows)]
+    {
+        let _ = own_threads;
+        use windows::Win32::System::SystemInformation::GetTickCount;
+        use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+        let mut info = LASTINPUTINFO {
+            cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+            dwTime: 0,
+        };
+        unsafe {
+            if !GetLastInputInfo(&mut info).as_bool() {
+                return true;
+            }
+            GetTickCount().wrapping_sub(info.dwTime) >= IDLE_AFTER_MS
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        let Ok(loadavg) = std::fs::read_to_string("/proc/loadavg") else {
+            return true;
+        };
+        let load: f64 = loadavg.split_whitespace()
+            .next()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0.0);
+
+        load - (own_threads as f64) < num_cpus::get() as f64 * 0.25
+    }
+}
@@ -0,0 +1,37 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+pub fn get_data_dir() -> PathBuf {
+    let local_app_data = dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("./"));
+    local_app_data.join("suptool")
+}
+
+pub fn load_json<T: DeserializeOwned>(relative_path: &str) -> Option<T> {
+    let path = get_data_dir().join(relative_path);
+    let content = fs::read_to_string(&path).ok()?;
+
+    match serde_json::from_str(&content) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            eprintln!("Fichier {} illisible: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+pub fn save_json<T: Serialize>(relative_path: &str, value: &T) -> io::Result<()> {
+    let path = get_data_dir().join(relative_path);
+
+    // Créer le dossier si nécessaire
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let content = serde_json::to_string(value)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    fs::write(path, content)
+}
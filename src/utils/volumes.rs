@@ -0,0 +1,198 @@
+use serde::{Serialize, Deserialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VolumeInfo {
+    pub label: String,
+    pub uuid: String,
+    pub mount_point: String,
+    pub removable: bool,
+}
+
+impl VolumeInfo {
+    pub fn display_name(&self) -> String {
+        if self.label.is_empty() {
+            self.mount_point.clone()
+        } else {
+            self.label.clone()
+        }
+    }
+}
+
+pub fn list_volumes() -> Vec<VolumeInfo> {
+    #[cfg(windows)]
+    {
+        use windows::core::HSTRING;
+        use windows::Win32::Storage::FileSystem::{GetDriveTypeW, GetVolumeInformationW};
+        use windows::Win32::System::WindowsProgramming::DRIVE_REMOVABLE;
+
+        super::get_drives()
+            .into_iter()
+            .filter_map(|drive| {
+                let root = HSTRING::from(drive.as_str());
+                let mut label = [0u16; 261];
+                let mut serial = 0u32;
+
+                unsafe {
+                    GetVolumeInformationW(&root, Some(&mut label), Some(&mut serial), None, None, None).ok()?;
+                }
+
+                let label_len = label.iter().position(|&c| c == 0).unwrap_or(label.len());
+                Some(VolumeInfo {
+                    label: String::from_utf16_lossy(&label[..label_len]),
+                    uuid: format!("{:08X}", serial),
+                    removable: unsafe { GetDriveTypeW(&root) } == DRIVE_REMOVABLE,
+                    mount_point: drive,
+                })
+            })
+            .collect()
+    }
+
+    #[cfg(not(windows))]
+    {
+        use std::collections::HashMap;
+        use std::fs;
+        use std::path::{Path, PathBuf};
+
+        // Associe chaque périphérique (/dev/sdb1...) à son nom dans /dev/disk/by-*
+        fn device_names(dir: &str) -> HashMap<PathBuf, String> {
+            fs::read_dir(dir)
+                .map(|entries| entries
+                    .filter_map(|e| e.ok())
+                    .filter_map(|e| {
+                        let device = fs::canonicalize(e.path()).ok()?;
+                        Some((device, unescape_mount(&e.file_name().to_string_lossy())))
+                    })
+                    .collect())
+                .unwrap_or_default()
+        }
+
+        fn is_removable(device: &Path) -> bool {
+            let Some(name) = device.file_name() else {
+                return false;
+            };
+            let Ok(sys_path) = fs::canonicalize(Path::new("/sys/class/block").join(name)) else {
+                return false;
+            };
+            // Une partition n'a pas de fichier `removable`, c'est le disque parent qui l'a
+            [sys_path.join("removable"), sys_path.join("../removable")]
+                .iter()
+                .filter_map(|p| fs::read_to_string(p).ok())
+                .any(|v| v.trim() == "1")
+        }
+
+        let uuids = device_names("/dev/disk/by-uuid");
+        let labels = device_names("/dev/disk/by-label");
+
+        let mountinfo = fs::read_to_string("/proc/self/mountinfo").unwrap_or_default();
+        parse_mountinfo(&mountinfo)
+            .into_iter()
+            .filter(|(_, source)| source.starts_with("/dev/"))
+            .map(|(mount_point, source)| {
+                let device = fs::canonicalize(&source).unwrap_or_else(|_| PathBuf::from(&source));
+                VolumeInfo {
+                    label: labels.get(&device).cloned().unwrap_or_default(),
+                    uuid: uuids.get(&device).cloned().unwrap_or(source),
+                    removable: is_removable(&device),
+                    mount_point,
+                }
+            })
+            .collect()
+    }
+}
+
+// Retourne les couples (point de montage, source) de /proc/self/mountinfo
+#[cfg(not(windows))]
+pub fn parse_mountinfo(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(' ').collect();
+            let mount_point = fields.get(4)?;
+            let separator = fields.iter().position(|f| *f == "-")?;
+            let source = fields.get(separator + 2)?;
+            Some((unescape_mount(mount_point), unescape_mount(source)))
+        })
+        .collect()
+}
+
+// Le noyau échappe les espaces et caractères spéciaux en octal (\040) ou en hexa (\x20)
+#[cfg(not(windows))]
+fn unescape_mount(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'\\' {
+            let octal = value.get(i + 1..i + 4).and_then(|s| u8::from_str_radix(s, 8).ok());
+            let hex = value.get(i + 1..i + 4)
+                .filter(|s| s.starts_with('x'))
+                .and_then(|s| u8::from_str_radix(&s[1..], 16).ok());
+            if let Some(byte) = hex.or(octal) {
+                result.push(byte);
+                i += 4;
+                continue;
+            }
+        }
+        result.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&result).to_string()
+}
+
+#[cfg(all(test, not(windows)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_mountinfo_lit_de_vraies_lignes() {
+        let content = "\
+22 1 259:2 / / rw,relatime shared:1 - ext4 /dev/nvme0n1p2 rw,errors=remount-ro
+25 22 0:21 / /proc rw,nosuid,nodev,noexec,relatime shared:12 - proc proc rw
+120 22 8:17 / /media/user/USB\\040KEY rw,nosuid,nodev,relatime shared:64 master:1 - vfat /dev/sdb1 rw,uid=1000
+";
+        let mounts = parse_mountinfo(content);
+        assert_eq!(mounts, vec![
+            ("/".to_string(), "/dev/nvme0n1p2".to_string()),
+            ("/proc".to_string(), "proc".to_string()),
+            ("/media/user/USB KEY".to_string(), "/dev/sdb1".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn parse_mountinfo_saute_les_champs_optionnels() {
+        // Aucun champ optionnel, puis plusieurs
+        let content = "\
+30 22 0:25 / /run rw,nosuid - tmpfs tmpfs rw
+31 22 8:1 / /mnt/data rw shared:5 master:2 propagate_from:3 unbindable - ext4 /dev/sda1 rw
+";
+        let mounts = parse_mountinfo(content);
+        assert_eq!(mounts[0], ("/run".to_string(), "tmpfs".to_string()));
+        assert_eq!(mounts[1], ("/mnt/data".to_string(), "/dev/sda1".to_string()));
+    }
+
+    #[test]
+    fn parse_mountinfo_ignore_les_lignes_invalides() {
+        assert!(parse_mountinfo("").is_empty());
+        assert!(parse_mountinfo("garbage line without separator").is_empty());
+        assert!(parse_mountinfo("1 2 3:4 / /mnt rw -").is_empty());
+    }
+
+    #[test]
+    fn unescape_mount_decode_octal_et_hexadecimal() {
+        assert_eq!(unescape_mount("/media/My\\040Disk"), "/media/My Disk");
+        assert_eq!(unescape_mount("tab\\011and\\134backslash"), "tab\tand\\backslash");
+        assert_eq!(unescape_mount("Mes\\x20photos"), "Mes photos");
+        // Nom de /dev/disk/by-label avec un caractère accentué encodé octet par octet
+        assert_eq!(unescape_mount("Donn\\xc3\\xa9es"), "Données");
+    }
+
+    #[test]
+    fn unescape_mount_garde_les_sequences_invalides() {
+        assert_eq!(unescape_mount("a\\999b"), "a\\999b");
+        assert_eq!(unescape_mount("trailing\\"), "trailing\\");
+        assert_eq!(unescape_mount("short\\04"), "short\\04");
+        assert_eq!(unescape_mount("déjà"), "déjà");
+    }
+}
@@ -1,3 +1,3 @@
 mod notifications;
 
-pub use notifications::show_notification;
\ No newline at end of file
+pub use notifications::{init as init_notifications, show_notification, show_action_notification};
\ No newline at end of file
@@ -1,5 +1,6 @@
-use crate::slint_generated::{MainWindow, NotificationData};
+use crate::slint_generated::{MainWindow, NotificationData, NotificationHandler};
 use slint::{ComponentHandle, Weak, Timer};
+use std::cell::RefCell;
 use std::collections::VecDeque;
 use std::sync::Mutex;
 use once_cell::sync::Lazy;
@@ -11,7 +12,14 @@ static NOTIFICATION_QUEUE: Lazy<Mutex<VecDeque<NotificationData>>> = Lazy::new(|
 static CURRENT_TOKEN: AtomicU64 = AtomicU64::new(0);
 static ACTIVE_TOKENS: Lazy<Mutex<HashMap<String, u64>>> = Lazy::new(|| Mutex::new(HashMap::new()));
 
+thread_local! {
+    // Les actions ne sont déclenchées que depuis la boucle d'événements Slint
+    static ACTION_HANDLERS: RefCell<HashMap<String, Box<dyn Fn()>>> = RefCell::new(HashMap::new());
+}
+
 const MAX_VISIBLE_NOTIFICATIONS: usize = 3;
+const AUTO_HIDE_DELAY: Duration = Duration::from_secs(5);
+const ACTION_AUTO_HIDE_DELAY: Duration = Duration::from_secs(15);
 //const MAX_QUEUE_SIZE: usize = 10;
 
 fn print_queue_state(queue: &VecDeque<NotificationData>) {
@@ -26,12 +34,60 @@ fn print_queue_state(queue: &VecDeque<NotificationData>) {
     }
 }
 
+pub fn init(window: &Weak<MainWindow>) {
+    if let Some(window) = window.upgrade() {
+        let handler = window.global::<NotificationHandler>();
+
+        handler.on_hide_notification({
+            let window_weak = window.as_weak();
+            move |id| hide_notification(&window_weak, &id)
+        });
+
+        handler.on_action_clicked({
+            let window_weak = window.as_weak();
+            move |id| {
+                let handler = ACTION_HANDLERS.with(|handlers| handlers.borrow_mut().remove(id.as_str()));
+                if let Some(handler) = handler {
+                    handler();
+                }
+                hide_notification(&window_weak, &id);
+            }
+        });
+    }
+}
+
 pub fn show_notification(
     window: &Weak<MainWindow>,
     id: &str,
     title: &str,
     message: &str,
     notification_type: &str
+) {
+    ACTION_HANDLERS.with(|handlers| handlers.borrow_mut().remove(id));
+    push_notification(window, id, title, message, notification_type, "", AUTO_HIDE_DELAY);
+}
+
+pub fn show_action_notification<F>(
+    window: &Weak<MainWindow>,
+    id: &str,
+    title: &str,
+    message: &str,
+    notification_type: &str,
+    action: &str,
+    on_action: F
+) where F: Fn() + 'static {
+    ACTION_HANDLERS.with(|handlers| handlers.borrow_mut().insert(id.to_string(), Box::new(on_action)));
+    push_notification(window, id, title, message, notification_type, action, ACTION_AUTO_HIDE_DELAY);
+}
+
+fn push_notification(
+    window: &Weak<MainWindow>,
+    id: &str,
+    title: &str,
+    message: &str,
+    notification_type: &str,
+    action: &str,
+    auto_hide_delay: Duration
 ) {
     println!("\n=== TENTATIVE D'AFFICHAGE DE NOTIFICATION ===");
     println!("ID: {}, Type: {}", id, notification_type);
@@ -54,6 +110,7 @@ pub fn show_notification(
                 .duration_since(UNIX_EPOCH)
                 .unwrap_or_default()
                 .as_secs() as i32,
+            action: action.into(),
         };
 
         if let Ok(mut queue) = NOTIFICATION_QUEUE.lock() {
@@ -75,7 +132,7 @@ pub fn show_notification(
         let window_weak = window.as_weak();
         let id_owned = id.to_string();
         let type_owned = notification_type.to_string();
-        Timer::single_shot(Duration::from_secs(5), move || {
+        Timer::single_shot(auto_hide_delay, move || {
             println!("\n=== VÉRIFICATION AUTO-HIDE ===");
             println!("ID: {}, Type: {}", id_owned, type_owned);
             
@@ -111,6 +168,7 @@ pub fn hide_notification(window: &Weak<MainWindow>, id: &str) {
     if let Ok(mut tokens) = ACTIVE_TOKENS.lock() {
         tokens.remove(id);
     }
+    ACTION_HANDLERS.with(|handlers| handlers.borrow_mut().remove(id));
 
     if let Some(window) = window.upgrade() {
         if let Ok(mut queue) = NOTIFICATION_QUEUE.lock() {